@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::create_ability;
+use crate::repositories::pokemon::Repository;
+
+use super::problem::Problem;
+use super::status_code::Status;
+
+#[derive(Deserialize)]
+struct Request {
+    name: String,
+    description: String,
+}
+
+#[derive(Serialize)]
+struct Response {
+    name: String,
+    description: String,
+}
+
+pub fn serve(repo: Arc<dyn Repository>, req: &rouille::Request) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => create_ability::Request {
+            name: req.name,
+            description: req.description,
+        },
+        Err(e) => return rouille::Response::from(Problem::invalid_body(e)),
+    };
+
+    match create_ability::execute(repo, req) {
+        Ok(res) => rouille::Response::json(&Response {
+            name: res.name,
+            description: res.description,
+        }),
+        Err(create_ability::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation_failed(errors))
+        }
+        Err(create_ability::Error::Conflict) => rouille::Response::from(Status::Conflict),
+        Err(create_ability::Error::Unknown(e)) => rouille::Response::from(Problem::storage(e)),
+    }
+}
@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::create_evolution;
+use crate::repositories::pokemon::Repository;
+
+use super::problem::Problem;
+use super::status_code::Status;
+
+#[derive(Deserialize)]
+struct Request {
+    to: u16,
+    trigger: String,
+    #[serde(default)]
+    conditions: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct Response {
+    from: u16,
+    to: u16,
+    trigger: String,
+    conditions: Vec<String>,
+}
+
+pub fn serve(repo: Arc<dyn Repository>, from: u16, req: &rouille::Request) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => create_evolution::Request {
+            from,
+            to: req.to,
+            trigger: req.trigger,
+            conditions: req.conditions,
+        },
+        Err(e) => return rouille::Response::from(Problem::invalid_body(e)),
+    };
+
+    match create_evolution::execute(repo, req) {
+        Ok(res) => rouille::Response::json(&Response {
+            from: res.from,
+            to: res.to,
+            trigger: res.trigger,
+            conditions: res.conditions,
+        }),
+        Err(create_evolution::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation_failed(errors))
+        }
+        Err(create_evolution::Error::NotFound(number)) => rouille::Response::from(
+            Problem::new(Status::NotFound).detail(format!("Pokemon {number} does not exist")),
+        ),
+        Err(create_evolution::Error::Conflict) => rouille::Response::from(
+            Problem::new(Status::Conflict).detail("the Pokemon already evolves from another one"),
+        ),
+        Err(create_evolution::Error::Cycle) => rouille::Response::from(
+            Problem::new(Status::Conflict)
+                .code("evolution_cycle")
+                .detail("the Pokemon would evolve into itself"),
+        ),
+        Err(create_evolution::Error::Unknown(e)) => rouille::Response::from(Problem::storage(e)),
+    }
+}
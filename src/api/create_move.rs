@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::create_move;
+use crate::repositories::pokemon::Repository;
+
+use super::moves::{MoveBody, MoveResponse};
+use super::problem::Problem;
+use super::status_code::Status;
+
+#[derive(Deserialize, Serialize)]
+struct Request {
+    id: u16,
+    #[serde(flatten)]
+    body: MoveBody,
+}
+
+pub fn serve(repo: Arc<dyn Repository>, req: &rouille::Request) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => req.body.into_request(req.id),
+        Err(e) => return rouille::Response::from(Problem::invalid_body(e)),
+    };
+
+    match create_move::execute(repo, req) {
+        Ok(res) => rouille::Response::json(&MoveResponse::from(res)),
+        Err(create_move::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation_failed(errors))
+        }
+        Err(create_move::Error::Conflict) => rouille::Response::from(Status::Conflict),
+        Err(create_move::Error::Unknown(e)) => rouille::Response::from(Problem::storage(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    use super::*;
+
+    fn request(id: u16, power: Option<u16>) -> rouille::Request {
+        let body = Request {
+            id,
+            body: MoveBody {
+                name: "Thunder Wave".to_owned(),
+                tipe: "Electric".to_owned(),
+                category: "status".to_owned(),
+                power,
+                accuracy: Some(90),
+                pp: 20,
+            },
+        };
+        let data = serde_json::to_string(&body).unwrap().into_bytes();
+        let headers = vec![("Content-Type".to_owned(), "application/json".to_owned())];
+        rouille::Request::fake_http("POST", "/moves", headers, data)
+    }
+
+    #[test]
+    fn it_should_return_ok_when_body_is_valid() {
+        // Arrange
+        let repo = Arc::new(InMemoryRepository::new());
+
+        // Act
+        let res = serve(repo.clone(), &request(86, None));
+
+        // Assert
+        assert_eq!(res.status_code, 200);
+        assert_eq!(repo.fetch_moves().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn it_should_return_bad_request_when_a_status_move_has_power() {
+        // Arrange
+        let repo = Arc::new(InMemoryRepository::new());
+
+        // Act
+        let res = serve(repo, &request(86, Some(40)));
+
+        // Assert
+        assert_eq!(res.status_code, 400);
+    }
+
+    #[test]
+    fn it_should_return_conflict_when_the_move_already_exists() {
+        // Arrange
+        let repo = Arc::new(InMemoryRepository::new());
+        serve(repo.clone(), &request(86, None));
+
+        // Act
+        let res = serve(repo, &request(86, None));
+
+        // Assert
+        assert_eq!(res.status_code, 409);
+    }
+}
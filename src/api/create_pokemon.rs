@@ -5,52 +5,86 @@ use serde::{Deserialize, Serialize};
 use crate::domain::create_pokemon;
 use crate::repositories::pokemon::Repository;
 
+use super::problem::Problem;
+use super::stats::{Stats, StatsResponse};
 use super::status_code::Status;
 
 #[derive(Deserialize, Serialize)]
 struct Request {
     number: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    form: Option<String>,
     name: String,
     types: Vec<String>,
+    stats: Stats,
 }
 
 #[derive(Serialize)]
 struct Response {
     number: u16,
+    generation: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    form: Option<String>,
     name: String,
     types: Vec<String>,
+    stats: StatsResponse,
 }
 
-pub fn serve(repo: Arc<dyn Repository>, req: &rouille::Request) -> rouille::Response {
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    national_dex_limit: u16,
+    req: &rouille::Request,
+) -> rouille::Response {
     let req = match rouille::input::json_input::<Request>(req) {
         Ok(req) => create_pokemon::Request {
             number: req.number,
+            form: req.form,
             name: req.name,
             types: req.types,
+            stats: <[u16; 6]>::from(req.stats),
+            national_dex_limit,
         },
-        _ => return rouille::Response::from(Status::BadRequest),
+        Err(e) => return rouille::Response::from(Problem::invalid_body(e)),
     };
 
     let res = create_pokemon::execute(repo, req);
     match res {
         Ok(res) => rouille::Response::json(&Response {
             number: res.number,
+            generation: res.generation,
+            form: res.form,
             name: res.name,
             types: res.types,
+            stats: StatsResponse::new(res.stats, res.total),
         }),
         Err(create_pokemon::Error::Conflict) => rouille::Response::from(Status::Conflict),
-        Err(create_pokemon::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
-        Err(create_pokemon::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+        Err(create_pokemon::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation_failed(errors))
+        }
+        Err(create_pokemon::Error::Unknown(e)) => rouille::Response::from(Problem::storage(e)),
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::domain::entities::{PokemonName, PokemonNumber, PokemonTypes};
+    use crate::domain::entities::{
+        PokemonName, PokemonNumber, PokemonStats, PokemonTypes, DEFAULT_NATIONAL_DEX_LIMIT,
+    };
     use crate::repositories::inmemory_pokemon::InMemoryRepository;
 
     use super::*;
 
+    fn stats() -> Stats {
+        Stats {
+            hp: 35,
+            attack: 55,
+            defense: 40,
+            special_attack: 50,
+            special_defense: 50,
+            speed: 90,
+        }
+    }
+
     fn request(body: Option<Request>) -> rouille::Request {
         match body {
             Some(body) => {
@@ -69,10 +103,37 @@ mod tests {
         let repo = Arc::new(InMemoryRepository::new());
 
         // Act
-        let res = serve(repo, &req);
+        let res = serve(repo, DEFAULT_NATIONAL_DEX_LIMIT, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 400);
+    }
+
+    #[test]
+    fn it_should_describe_invalid_fields_as_a_problem() {
+        // Arrange
+        let req = Request {
+            number: 0,
+            form: None,
+            name: String::from("Vulpix"),
+            types: vec![String::from("Fire")],
+            stats: stats(),
+        };
+        let req = request(Some(req));
+        let repo = Arc::new(InMemoryRepository::new());
+
+        // Act
+        let res = serve(repo, DEFAULT_NATIONAL_DEX_LIMIT, &req);
 
         // Assert
         assert_eq!(res.status_code, 400);
+        let content_type = res.headers.iter().find(|(k, _)| k == "Content-Type");
+        assert_eq!(content_type.unwrap().1, "application/problem+json");
+        let (mut reader, _) = res.data.into_reader_and_size();
+        let body: serde_json::Value = serde_json::from_reader(&mut reader).unwrap();
+        assert_eq!(body["code"], "validation_failed");
+        assert_eq!(body["invalid-params"][0]["name"], "number");
+        assert_eq!(body["invalid-params"][0]["code"], "out_of_range");
     }
 
     #[test]
@@ -80,56 +141,116 @@ mod tests {
         // Arrange
         let req = Request {
             number: 20,
+            form: None,
             name: String::from("Vulpix"),
             types: vec![String::from("Fire")],
+            stats: stats(),
         };
         let req = request(Some(req));
         let repo = Arc::new(InMemoryRepository::new());
 
         // Act
-        let res = serve(repo, &req);
+        let res = serve(repo, DEFAULT_NATIONAL_DEX_LIMIT, &req);
 
         // Assert
         assert_eq!(res.status_code, 200);
     }
 
+    #[test]
+    fn it_should_accept_paldea_numbers() {
+        // Arrange
+        let req = Request {
+            number: 906,
+            form: None,
+            name: String::from("Sprigatito"),
+            types: vec![String::from("Grass")],
+            stats: stats(),
+        };
+        let req = request(Some(req));
+        let repo = Arc::new(InMemoryRepository::new());
+
+        // Act
+        let res = serve(repo, DEFAULT_NATIONAL_DEX_LIMIT, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 200);
+        let (mut reader, _) = res.data.into_reader_and_size();
+        let body: serde_json::Value = serde_json::from_reader(&mut reader).unwrap();
+        assert_eq!(body["generation"], 9);
+    }
+
     #[test]
     fn it_should_return_server_error_when_repo_error_happens() {
         // Arrange
         let req = Request {
             number: 20,
+            form: None,
             name: "Electabuzz".to_owned(),
             types: vec!["Electric".to_owned()],
+            stats: stats(),
         };
         let req = request(Some(req));
         let repo = Arc::new(InMemoryRepository::new().with_error());
 
         // Act
-        let res = serve(repo, &req);
+        let res = serve(repo, DEFAULT_NATIONAL_DEX_LIMIT, &req);
 
         // Assert
         assert_eq!(res.status_code, 500);
     }
 
+    #[test]
+    fn it_should_create_another_form_of_an_existing_number() {
+        // Arrange
+        let req = Request {
+            number: 25,
+            form: Some(String::from("Partner")),
+            name: String::from("Pikachu"),
+            types: vec![String::from("Electric")],
+            stats: stats(),
+        };
+        let req = request(Some(req));
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu().into(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .unwrap();
+
+        // Act
+        let res = serve(repo, DEFAULT_NATIONAL_DEX_LIMIT, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 200);
+        let (mut reader, _) = res.data.into_reader_and_size();
+        let body: serde_json::Value = serde_json::from_reader(&mut reader).unwrap();
+        assert_eq!(body["form"], "partner");
+    }
+
     #[test]
     fn it_should_return_conflict_when_number_exists() {
         // Arrange
         let req = Request {
             number: 25,
+            form: None,
             name: "Electabuzz".to_owned(),
             types: vec!["Electric".to_owned()],
+            stats: stats(),
         };
         let req = request(Some(req));
         let repo = Arc::new(InMemoryRepository::new());
         repo.insert(
-            PokemonNumber::pikachu(),
+            PokemonNumber::pikachu().into(),
             PokemonName::pikachu(),
             PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
         )
         .expect("error inserting pikachu");
 
         // Act
-        let res = serve(repo, &req);
+        let res = serve(repo, DEFAULT_NATIONAL_DEX_LIMIT, &req);
 
         // Assert
         assert_eq!(res.status_code, 409);
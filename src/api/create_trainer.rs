@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::create_trainer;
+use crate::repositories::pokemon::Repository;
+
+use super::problem::Problem;
+use super::status_code::Status;
+
+#[derive(Deserialize)]
+struct Request {
+    id: String,
+    name: String,
+}
+
+#[derive(Serialize)]
+struct Response {
+    id: String,
+    name: String,
+}
+
+pub fn serve(repo: Arc<dyn Repository>, req: &rouille::Request) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => create_trainer::Request {
+            id: req.id,
+            name: req.name,
+        },
+        Err(e) => return rouille::Response::from(Problem::invalid_body(e)),
+    };
+
+    match create_trainer::execute(repo, req) {
+        Ok(res) => rouille::Response::json(&Response {
+            id: res.id,
+            name: res.name,
+        }),
+        Err(create_trainer::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation_failed(errors))
+        }
+        Err(create_trainer::Error::Conflict) => rouille::Response::from(Status::Conflict),
+        Err(create_trainer::Error::Unknown(e)) => rouille::Response::from(Problem::storage(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    use super::*;
+
+    fn request(id: &str) -> rouille::Request {
+        let data = serde_json::json!({ "id": id, "name": "Red" })
+            .to_string()
+            .into_bytes();
+        let headers = vec![("Content-Type".to_owned(), "application/json".to_owned())];
+        rouille::Request::fake_http("POST", "/trainers", headers, data)
+    }
+
+    #[test]
+    fn it_should_return_conflict_when_the_trainer_already_exists() {
+        // Arrange
+        let repo = Arc::new(InMemoryRepository::new());
+
+        // Act
+        let created = serve(repo.clone(), &request("red"));
+        let duplicate = serve(repo, &request("red"));
+
+        // Assert
+        assert_eq!(created.status_code, 200);
+        assert_eq!(duplicate.status_code, 409);
+    }
+
+    #[test]
+    fn it_should_return_bad_request_when_the_id_is_invalid() {
+        // Arrange
+        let repo = Arc::new(InMemoryRepository::new());
+
+        // Act
+        let res = serve(repo, &request("Red Trainer"));
+
+        // Assert
+        assert_eq!(res.status_code, 400);
+    }
+}
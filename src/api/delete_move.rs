@@ -0,0 +1,15 @@
+use std::sync::Arc;
+
+use super::problem::Problem;
+use super::status_code::Status;
+use crate::domain::delete_move;
+use crate::repositories::pokemon::Repository;
+
+pub fn serve(repo: Arc<dyn Repository>, id: u16) -> rouille::Response {
+    match delete_move::execute(repo, delete_move::Request { id }) {
+        Ok(_) => rouille::Response::from(Status::Ok),
+        Err(delete_move::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(delete_move::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(delete_move::Error::Unknown(e)) => rouille::Response::from(Problem::storage(e)),
+    }
+}
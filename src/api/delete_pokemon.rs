@@ -1,15 +1,17 @@
 use std::sync::Arc;
 
+use super::problem::Problem;
 use super::status_code::Status;
 use crate::domain::delete_pokemon;
 use crate::repositories::pokemon::Repository;
 
-pub fn serve(repo: Arc<dyn Repository>, number: u16) -> rouille::Response {
-    let req = delete_pokemon::Request { number };
+/// Without a form, deletes the base form of the number and every other form.
+pub fn serve(repo: Arc<dyn Repository>, number: u16, form: Option<String>) -> rouille::Response {
+    let req = delete_pokemon::Request { number, form };
     match delete_pokemon::execute(repo, req) {
         Ok(_) => rouille::Response::from(Status::Ok),
         Err(delete_pokemon::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
         Err(delete_pokemon::Error::NotFound) => rouille::Response::from(Status::NotFound),
-        Err(delete_pokemon::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+        Err(delete_pokemon::Error::Unknown(e)) => rouille::Response::from(Problem::storage(e)),
     }
 }
@@ -0,0 +1,133 @@
+use std::io::{self, Read};
+use std::sync::Arc;
+
+use crate::domain::export_pokemons;
+use crate::repositories::pokemon::Repository;
+
+use super::problem::Problem;
+use super::status_code::Status;
+
+/// Feeds the exported chunks to the HTTP body as they are produced.
+struct ExportReader {
+    export: export_pokemons::Response,
+    chunk: Vec<u8>,
+    position: usize,
+}
+
+impl Read for ExportReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.position == self.chunk.len() {
+            match self.export.next() {
+                Some(Ok(chunk)) => {
+                    self.chunk = chunk.into_bytes();
+                    self.position = 0;
+                }
+                Some(Err(e)) => return Err(io::Error::other(e)),
+                None => return Ok(0),
+            }
+        }
+        let read = (&self.chunk[self.position..]).read(buf)?;
+        self.position += read;
+        Ok(read)
+    }
+}
+
+pub fn serve(repo: Arc<dyn Repository>, req: &rouille::Request) -> rouille::Response {
+    let format = req.get_param("format").unwrap_or_else(|| String::from("json"));
+
+    let req = export_pokemons::Request { format };
+    match export_pokemons::execute(repo, req) {
+        Ok(export) => {
+            let content_type = match export.format() {
+                export_pokemons::Format::Csv => "text/csv; charset=utf-8",
+                export_pokemons::Format::JsonLines => "application/x-ndjson",
+                export_pokemons::Format::Json => "application/json",
+            };
+            let reader = ExportReader {
+                export,
+                chunk: vec![],
+                position: 0,
+            };
+            rouille::Response {
+                status_code: Status::Ok.code(),
+                headers: vec![("Content-Type".into(), content_type.into())],
+                data: rouille::ResponseBody::from_reader(reader),
+                upgrade: None,
+            }
+        }
+        Err(export_pokemons::Error::BadRequest) => rouille::Response::from(
+            Problem::new(Status::BadRequest)
+                .code("invalid_query")
+                .detail("query parameter format must be one of csv, json or jsonl"),
+        ),
+        Err(export_pokemons::Error::Unknown(e)) => rouille::Response::from(Problem::storage(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::entities::{PokemonName, PokemonNumber, PokemonStats, PokemonTypes};
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    use super::*;
+
+    #[test]
+    fn it_should_stream_the_export() {
+        // Arrange
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu().into(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .ok();
+        let req = rouille::Request::fake_http("GET", "/export?format=csv", vec![], vec![]);
+
+        // Act
+        let res = serve(repo, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 200);
+        let mut body = String::new();
+        res.data.into_reader_and_size().0.read_to_string(&mut body).unwrap();
+        assert_eq!(
+            body,
+            "#schema_version=1\n\
+            number,name,types,hp,attack,defense,special_attack,special_defense,speed,form\n\
+            25,Pikachu,Electric,35,55,40,50,50,90,\n"
+        );
+    }
+
+    #[test]
+    fn it_should_label_the_body_with_the_format_regardless_of_case() {
+        // Arrange
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = rouille::Request::fake_http("GET", "/export?format=CSV", vec![], vec![]);
+
+        // Act
+        let res = serve(repo, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 200);
+        let content_type = res
+            .headers
+            .iter()
+            .find(|(name, _)| name == "Content-Type")
+            .map(|(_, value)| value.to_string());
+        assert_eq!(content_type.as_deref(), Some("text/csv; charset=utf-8"));
+    }
+
+    #[test]
+    fn it_should_return_bad_request_when_format_is_unknown() {
+        // Arrange
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = rouille::Request::fake_http("GET", "/export?format=xml", vec![], vec![]);
+
+        // Act
+        let res = serve(repo, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 400);
+    }
+}
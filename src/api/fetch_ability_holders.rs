@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::domain::fetch_ability_holders;
+use crate::repositories::pokemon::Repository;
+
+use super::problem::Problem;
+use super::status_code::Status;
+
+#[derive(Serialize)]
+struct Response {
+    number: u16,
+    name: String,
+    hidden: bool,
+}
+
+pub fn serve(repo: Arc<dyn Repository>, name: String) -> rouille::Response {
+    match fetch_ability_holders::execute(repo, fetch_ability_holders::Request::new(name)) {
+        Ok(holders) => rouille::Response::json(
+            &holders
+                .into_iter()
+                .map(|holder| Response {
+                    number: holder.number,
+                    name: holder.name,
+                    hidden: holder.hidden,
+                })
+                .collect::<Vec<Response>>(),
+        ),
+        Err(fetch_ability_holders::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(fetch_ability_holders::Error::Unknown(e)) => {
+            rouille::Response::from(Problem::storage(e))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::entities::{Ability, Pokemon, PokemonAbilities, DEFAULT_NATIONAL_DEX_LIMIT};
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    use super::*;
+
+    #[test]
+    fn it_should_find_abilities_with_spaces_in_their_name() {
+        // Arrange
+        let repo: Arc<dyn Repository> = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![Pokemon::pikachu()]).unwrap();
+        repo.insert_ability(Ability::lightning_rod()).unwrap();
+        let abilities =
+            PokemonAbilities::try_from((vec![String::from("Lightning Rod")], None)).unwrap();
+        repo.replace_abilities(Pokemon::pikachu().number, abilities)
+            .unwrap();
+        let req = rouille::Request::fake_http(
+            "GET",
+            "/abilities/lightning%20rod/pokemons",
+            vec![],
+            vec![],
+        );
+
+        // Act
+        let res = crate::api::route(&repo, DEFAULT_NATIONAL_DEX_LIMIT, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 200);
+    }
+
+    #[test]
+    fn it_should_return_not_found_when_the_ability_does_not_exist() {
+        // Arrange
+        let repo = Arc::new(InMemoryRepository::new());
+
+        // Act
+        let res = serve(repo, String::from("Static"));
+
+        // Assert
+        assert_eq!(res.status_code, 404);
+    }
+}
@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::domain::fetch_all_abilities;
+use crate::repositories::pokemon::Repository;
+
+use super::problem::Problem;
+
+#[derive(Serialize)]
+struct Response {
+    name: String,
+    description: String,
+}
+
+pub fn serve(repo: Arc<dyn Repository>) -> rouille::Response {
+    match fetch_all_abilities::execute(repo) {
+        Ok(abilities) => rouille::Response::json(
+            &abilities
+                .into_iter()
+                .map(|ability| Response {
+                    name: ability.name,
+                    description: ability.description,
+                })
+                .collect::<Vec<Response>>(),
+        ),
+        Err(fetch_all_abilities::Error::Unknown(e)) => rouille::Response::from(Problem::storage(e)),
+    }
+}
@@ -0,0 +1,16 @@
+use std::sync::Arc;
+
+use crate::domain::fetch_all_moves;
+use crate::repositories::pokemon::Repository;
+
+use super::moves::MoveResponse;
+use super::problem::Problem;
+
+pub fn serve(repo: Arc<dyn Repository>) -> rouille::Response {
+    match fetch_all_moves::execute(repo) {
+        Ok(moves) => rouille::Response::json(
+            &moves.into_iter().map(MoveResponse::from).collect::<Vec<MoveResponse>>(),
+        ),
+        Err(fetch_all_moves::Error::Unknown(e)) => rouille::Response::from(Problem::storage(e)),
+    }
+}
@@ -1,3 +1,4 @@
+use std::str::FromStr;
 use std::sync::Arc;
 
 use serde::Serialize;
@@ -6,24 +7,200 @@ use crate::repositories::pokemon::Repository;
 
 use crate::domain::fetch_all_pokemons;
 
+use super::problem::Problem;
+use super::stats::StatsResponse;
 use super::status_code::Status;
 
 #[derive(Serialize)]
 pub struct Response {
     number: u16,
+    generation: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    form: Option<String>,
     name: String,
     types: Vec<String>,
+    stats: StatsResponse,
 }
 
-pub fn serve(repo: Arc<dyn Repository>) -> rouille::Response {
-    match fetch_all_pokemons::execute(repo) {
-        Ok(pokemons) => rouille::Response::json(&pokemons.into_iter().map(|pokemon| Response {
-            number: pokemon.number,
-            name: pokemon.name,
-            types: pokemon.types,
-        }).collect::<Vec<Response>>()),
-        Err(fetch_all_pokemons::Error::Unknown) => {
-            rouille::Response::from(Status::InternalServerError)
-        }
+#[derive(Serialize)]
+pub struct Pagination {
+    offset: usize,
+    limit: Option<usize>,
+    next_offset: Option<usize>,
+}
+
+#[derive(Serialize)]
+pub struct Page {
+    pokemons: Vec<Response>,
+    pagination: Pagination,
+}
+
+fn param<T: FromStr>(req: &rouille::Request, name: &str) -> Result<Option<T>, Problem> {
+    match req.get_param(name).map(|value| value.parse::<T>()) {
+        Some(Ok(value)) => Ok(Some(value)),
+        Some(Err(_)) => Err(Problem::new(Status::BadRequest)
+            .code("invalid_query")
+            .detail(format!("query parameter {name} must be a positive integer"))),
+        None => Ok(None),
+    }
+}
+
+/// Largest page `GET /` serves.
+pub const MAX_LIMIT: usize = 1000;
+/// Furthest a page of `GET /` can start, well past every Pokemon and form.
+pub const MAX_OFFSET: usize = 100_000;
+
+/// A pagination parameter, rejected above `max`.
+fn bounded(req: &rouille::Request, name: &str, max: usize) -> Result<Option<usize>, Problem> {
+    match param::<usize>(req, name)? {
+        Some(value) if value > max => Err(Problem::new(Status::BadRequest)
+            .code("invalid_query")
+            .detail(format!("query parameter {name} must not exceed {max}"))),
+        value => Ok(value),
+    }
+}
+
+fn request(req: &rouille::Request) -> Result<fetch_all_pokemons::Request, Problem> {
+    Ok(fetch_all_pokemons::Request {
+        tipe: req.get_param("type"),
+        name: req.get_param("name"),
+        min_number: param(req, "min_number")?,
+        max_number: param(req, "max_number")?,
+        generation: param(req, "generation")?,
+        sort: req.get_param("sort"),
+        direction: req.get_param("direction"),
+        limit: bounded(req, "limit", MAX_LIMIT)?,
+        offset: bounded(req, "offset", MAX_OFFSET)?,
+    })
+}
+
+pub fn serve(repo: Arc<dyn Repository>, req: &rouille::Request) -> rouille::Response {
+    let req = match request(req) {
+        Ok(req) => req,
+        Err(problem) => return rouille::Response::from(problem),
+    };
+
+    match fetch_all_pokemons::execute(repo, req) {
+        Ok(page) => rouille::Response::json(&Page {
+            pokemons: page
+                .pokemons
+                .into_iter()
+                .map(|pokemon| Response {
+                    number: pokemon.number,
+                    generation: pokemon.generation,
+                    form: pokemon.form,
+                    name: pokemon.name,
+                    types: pokemon.types,
+                    stats: StatsResponse::new(pokemon.stats, pokemon.total),
+                })
+                .collect::<Vec<Response>>(),
+            pagination: Pagination {
+                offset: page.offset,
+                limit: page.limit,
+                next_offset: page.next_offset,
+            },
+        }),
+        Err(fetch_all_pokemons::Error::BadRequest) => rouille::Response::from(
+            Problem::new(Status::BadRequest)
+                .code("invalid_query")
+                .detail("the filters, sort or pagination parameters are invalid"),
+        ),
+        Err(fetch_all_pokemons::Error::Unknown(e)) => rouille::Response::from(Problem::storage(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::entities::{PokemonName, PokemonNumber, PokemonStats, PokemonTypes};
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    use super::*;
+
+    fn repo_with_pikachu() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu().into(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .expect("error inserting pikachu");
+        repo
+    }
+
+    #[test]
+    fn it_should_return_bad_request_when_query_is_invalid() {
+        // Arrange
+        let req = rouille::Request::fake_http("GET", "/?limit=many", vec![], vec![]);
+        let repo = repo_with_pikachu();
+
+        // Act
+        let res = serve(repo, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 400);
+    }
+
+    #[test]
+    fn it_should_return_bad_request_when_pagination_is_too_large() {
+        // Arrange
+        let limit = format!("/?limit={}", usize::MAX);
+        let offset = format!("/?limit=1&offset={}", usize::MAX);
+        let limit = rouille::Request::fake_http("GET", limit, vec![], vec![]);
+        let offset = rouille::Request::fake_http("GET", offset, vec![], vec![]);
+        let largest = format!("/?limit={MAX_LIMIT}&offset={MAX_OFFSET}");
+        let largest = rouille::Request::fake_http("GET", largest, vec![], vec![]);
+        let repo = repo_with_pikachu();
+
+        // Act
+        let limit = serve(repo.clone(), &limit);
+        let offset = serve(repo.clone(), &offset);
+        let largest = serve(repo, &largest);
+
+        // Assert
+        assert_eq!(limit.status_code, 400);
+        let (mut reader, _) = limit.data.into_reader_and_size();
+        let body: serde_json::Value = serde_json::from_reader(&mut reader).unwrap();
+        assert_eq!(body["detail"], "query parameter limit must not exceed 1000");
+        assert_eq!(offset.status_code, 400);
+        assert_eq!(largest.status_code, 200);
+    }
+
+    #[test]
+    fn it_should_return_ok_when_query_is_valid() {
+        // Arrange
+        let req = rouille::Request::fake_http(
+            "GET",
+            "/?type=Electric&sort=name&direction=desc&limit=10&offset=0",
+            vec![],
+            vec![],
+        );
+        let repo = repo_with_pikachu();
+
+        // Act
+        let res = serve(repo, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 200);
+    }
+
+    #[test]
+    fn it_should_filter_by_generation_and_expose_it() {
+        // Arrange
+        let kanto = rouille::Request::fake_http("GET", "/?generation=1", vec![], vec![]);
+        let paldea = rouille::Request::fake_http("GET", "/?generation=9", vec![], vec![]);
+        let repo = repo_with_pikachu();
+
+        // Act
+        let kanto = serve(repo.clone(), &kanto);
+        let paldea = serve(repo, &paldea);
+
+        // Assert
+        let (mut reader, _) = kanto.data.into_reader_and_size();
+        let kanto: serde_json::Value = serde_json::from_reader(&mut reader).unwrap();
+        assert_eq!(kanto["pokemons"][0]["generation"], 1);
+        let (mut reader, _) = paldea.data.into_reader_and_size();
+        let paldea: serde_json::Value = serde_json::from_reader(&mut reader).unwrap();
+        assert_eq!(paldea["pokemons"], serde_json::json!([]));
     }
 }
@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::domain::fetch_dex_completion;
+use crate::repositories::pokemon::Repository;
+
+use super::problem::Problem;
+use super::status_code::Status;
+
+#[derive(Serialize)]
+struct Completion {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    generation: Option<u8>,
+    total: usize,
+    seen: usize,
+    caught: usize,
+    shiny: usize,
+    percentage: f64,
+}
+
+impl Completion {
+    fn new(generation: Option<u8>, completion: fetch_dex_completion::Completion) -> Self {
+        Self {
+            generation,
+            total: completion.total,
+            seen: completion.seen,
+            caught: completion.caught,
+            shiny: completion.shiny,
+            percentage: completion.percentage,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Response {
+    overall: Completion,
+    generations: Vec<Completion>,
+}
+
+pub fn serve(repo: Arc<dyn Repository>, trainer: String) -> rouille::Response {
+    match fetch_dex_completion::execute(repo, fetch_dex_completion::Request::new(trainer)) {
+        Ok(res) => rouille::Response::json(&Response {
+            overall: Completion::new(None, res.overall),
+            generations: res
+                .generations
+                .into_iter()
+                .map(|(generation, completion)| Completion::new(Some(generation), completion))
+                .collect(),
+        }),
+        Err(fetch_dex_completion::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(fetch_dex_completion::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(fetch_dex_completion::Error::Unknown(e)) => {
+            rouille::Response::from(Problem::storage(e))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::entities::{DexMark, DexStatus, Pokemon, Trainer, DEFAULT_NATIONAL_DEX_LIMIT};
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    use super::*;
+
+    #[test]
+    fn it_should_return_the_completion_of_the_trainer() {
+        // Arrange
+        let repo: Arc<dyn Repository> = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![Pokemon::pikachu(), Pokemon::vulpix()])
+            .unwrap();
+        repo.insert_trainer(Trainer::red()).unwrap();
+        let caught = DexStatus::default().mark(DexMark::Caught);
+        repo.replace_dex_status(Trainer::red().id, Pokemon::pikachu().number, caught)
+            .unwrap();
+        let req =
+            rouille::Request::fake_http("GET", "/trainers/red/dex/completion", vec![], vec![]);
+
+        // Act
+        let res = crate::api::route(&repo, DEFAULT_NATIONAL_DEX_LIMIT, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 200);
+        let (mut reader, _) = res.data.into_reader_and_size();
+        let body: serde_json::Value = serde_json::from_reader(&mut reader).unwrap();
+        assert_eq!(body["overall"]["total"], 2);
+        assert_eq!(body["overall"]["percentage"], 50.0);
+        assert_eq!(body["generations"][0]["generation"], 1);
+    }
+}
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::domain::fetch_evolution_chain;
+use crate::repositories::pokemon::Repository;
+
+use super::problem::Problem;
+use super::status_code::Status;
+
+#[derive(Serialize)]
+struct Stage {
+    number: u16,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    trigger: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    conditions: Vec<String>,
+    evolutions: Vec<Stage>,
+}
+
+impl From<fetch_evolution_chain::Stage> for Stage {
+    fn from(stage: fetch_evolution_chain::Stage) -> Self {
+        Self {
+            number: stage.number,
+            name: stage.name,
+            trigger: stage.trigger,
+            conditions: stage.conditions,
+            evolutions: stage.evolutions.into_iter().map(Stage::from).collect(),
+        }
+    }
+}
+
+pub fn serve(repo: Arc<dyn Repository>, number: u16) -> rouille::Response {
+    let req = fetch_evolution_chain::Request::new(number);
+    match fetch_evolution_chain::execute(repo, req) {
+        Ok(stage) => rouille::Response::json(&Stage::from(stage)),
+        Err(fetch_evolution_chain::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(fetch_evolution_chain::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(fetch_evolution_chain::Error::Unknown(e)) => rouille::Response::from(Problem::storage(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::entities::{Evolution, EvolutionTrigger, Pokemon, PokemonNumber};
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    use super::*;
+
+    #[test]
+    fn it_should_return_the_chain_as_a_tree() {
+        // Arrange
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![Pokemon::pikachu(), Pokemon::vulpix()]).unwrap();
+        repo.insert_evolution(Evolution::new(
+            PokemonNumber::pikachu(),
+            PokemonNumber::vulpix(),
+            EvolutionTrigger::Level(30),
+            vec![],
+        ))
+        .unwrap();
+
+        // Act
+        let res = serve(repo, 37);
+
+        // Assert
+        assert_eq!(res.status_code, 200);
+        let (mut reader, _) = res.data.into_reader_and_size();
+        let body: serde_json::Value = serde_json::from_reader(&mut reader).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "number": 25,
+                "name": "Pikachu",
+                "evolutions": [{
+                    "number": 37,
+                    "name": "Vulpix",
+                    "trigger": "level:30",
+                    "evolutions": [],
+                }],
+            })
+        );
+    }
+}
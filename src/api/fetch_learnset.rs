@@ -0,0 +1,22 @@
+use std::sync::Arc;
+
+use crate::domain::fetch_learnset;
+use crate::repositories::pokemon::Repository;
+
+use super::moves::LearnedMoveResponse;
+use super::problem::Problem;
+use super::status_code::Status;
+
+pub fn serve(repo: Arc<dyn Repository>, number: u16) -> rouille::Response {
+    match fetch_learnset::execute(repo, fetch_learnset::Request::new(number)) {
+        Ok(moves) => rouille::Response::json(
+            &moves
+                .into_iter()
+                .map(LearnedMoveResponse::from)
+                .collect::<Vec<LearnedMoveResponse>>(),
+        ),
+        Err(fetch_learnset::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(fetch_learnset::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(fetch_learnset::Error::Unknown(e)) => rouille::Response::from(Problem::storage(e)),
+    }
+}
@@ -0,0 +1,17 @@
+use std::sync::Arc;
+
+use crate::domain::fetch_move;
+use crate::repositories::pokemon::Repository;
+
+use super::moves::MoveResponse;
+use super::problem::Problem;
+use super::status_code::Status;
+
+pub fn serve(repo: Arc<dyn Repository>, id: u16) -> rouille::Response {
+    match fetch_move::execute(repo, fetch_move::Request::new(id)) {
+        Ok(res) => rouille::Response::json(&MoveResponse::from(res)),
+        Err(fetch_move::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(fetch_move::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(fetch_move::Error::Unknown(e)) => rouille::Response::from(Problem::storage(e)),
+    }
+}
@@ -6,25 +6,44 @@ use crate::repositories::pokemon::Repository;
 
 use crate::domain::fetch_pokemon;
 
+use super::stats::StatsResponse;
+use super::problem::Problem;
 use super::status_code::Status;
 
 #[derive(Serialize)]
 pub struct Response {
     number: u16,
+    generation: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    form: Option<String>,
     name: String,
     types: Vec<String>,
+    stats: StatsResponse,
 }
 
-pub fn serve(repo: Arc<dyn Repository>, number: u16) -> rouille::Response {
-    let req = fetch_pokemon::Request::new(number);
-    match fetch_pokemon::execute(repo, req) {
-        Ok(pokemon) => rouille::Response::json(&Response {
+impl From<fetch_pokemon::Response> for Response {
+    fn from(pokemon: fetch_pokemon::Response) -> Self {
+        Self {
             number: pokemon.number,
+            generation: pokemon.generation,
+            form: pokemon.form,
             name: pokemon.name,
             types: pokemon.types,
-        }),
+            stats: StatsResponse::new(pokemon.stats, pokemon.total),
+        }
+    }
+}
+
+/// Without a form, serves the base form of the number.
+pub fn serve(repo: Arc<dyn Repository>, number: u16, form: Option<String>) -> rouille::Response {
+    let mut req = fetch_pokemon::Request::new(number);
+    if let Some(form) = form {
+        req = req.with_form(form);
+    }
+    match fetch_pokemon::execute(repo, req) {
+        Ok(pokemon) => rouille::Response::json(&Response::from(pokemon)),
         Err(fetch_pokemon::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
         Err(fetch_pokemon::Error::NotFound) => rouille::Response::from(Status::NotFound),
-        Err(fetch_pokemon::Error::Unknown) => rouille::Response::from(Status::InternalServerError),
+        Err(fetch_pokemon::Error::Unknown(e)) => rouille::Response::from(Problem::storage(e)),
     }
 }
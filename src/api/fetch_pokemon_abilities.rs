@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::domain::fetch_pokemon_abilities;
+use crate::repositories::pokemon::Repository;
+
+use super::problem::Problem;
+use super::status_code::Status;
+
+#[derive(Serialize)]
+pub(super) struct Response {
+    pub regular: Vec<String>,
+    pub hidden: Option<String>,
+}
+
+pub fn serve(repo: Arc<dyn Repository>, number: u16) -> rouille::Response {
+    let req = fetch_pokemon_abilities::Request::new(number);
+    match fetch_pokemon_abilities::execute(repo, req) {
+        Ok(res) => rouille::Response::json(&Response {
+            regular: res.regular,
+            hidden: res.hidden,
+        }),
+        Err(fetch_pokemon_abilities::Error::BadRequest) => {
+            rouille::Response::from(Status::BadRequest)
+        }
+        Err(fetch_pokemon_abilities::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(fetch_pokemon_abilities::Error::Unknown(e)) => {
+            rouille::Response::from(Problem::storage(e))
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use crate::domain::fetch_pokemon_forms;
+use crate::repositories::pokemon::Repository;
+
+use super::fetch_pokemon::Response;
+use super::problem::Problem;
+use super::status_code::Status;
+
+pub fn serve(repo: Arc<dyn Repository>, number: u16) -> rouille::Response {
+    match fetch_pokemon_forms::execute(repo, fetch_pokemon_forms::Request::new(number)) {
+        Ok(forms) => rouille::Response::json(
+            &forms
+                .into_iter()
+                .map(Response::from)
+                .collect::<Vec<Response>>(),
+        ),
+        Err(fetch_pokemon_forms::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(fetch_pokemon_forms::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(fetch_pokemon_forms::Error::Unknown(e)) => rouille::Response::from(Problem::storage(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::entities::{
+        PokemonForm, PokemonKey, PokemonName, PokemonNumber, PokemonStats, PokemonTypes,
+        DEFAULT_NATIONAL_DEX_LIMIT,
+    };
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    use super::*;
+
+    fn repo_with_vulpixes() -> Arc<dyn Repository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::vulpix().into(),
+            PokemonName::vulpix(),
+            PokemonTypes::vulpix(),
+            PokemonStats::vulpix(),
+        )
+        .unwrap();
+        repo.insert(
+            PokemonKey::new(PokemonNumber::vulpix(), Some(PokemonForm::alola())),
+            PokemonName::vulpix(),
+            PokemonTypes::alolan_vulpix(),
+            PokemonStats::vulpix(),
+        )
+        .unwrap();
+        repo
+    }
+
+    fn body(res: rouille::Response) -> serde_json::Value {
+        let (mut reader, _) = res.data.into_reader_and_size();
+        serde_json::from_reader(&mut reader).unwrap()
+    }
+
+    #[test]
+    fn it_should_list_every_form_of_the_number() {
+        // Arrange
+        let repo = repo_with_vulpixes();
+        let req = rouille::Request::fake_http("GET", "/37/forms", vec![], vec![]);
+
+        // Act
+        let res = crate::api::route(&repo, DEFAULT_NATIONAL_DEX_LIMIT, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 200);
+        let body = body(res);
+        assert_eq!(body[0].get("form"), None);
+        assert_eq!(body[1]["form"], "alola");
+        assert_eq!(body[1]["types"], serde_json::json!(["Ice"]));
+    }
+
+    #[test]
+    fn it_should_serve_a_single_form() {
+        // Arrange
+        let repo = repo_with_vulpixes();
+        let req = rouille::Request::fake_http("GET", "/37/forms/alola", vec![], vec![]);
+
+        // Act
+        let res = crate::api::route(&repo, DEFAULT_NATIONAL_DEX_LIMIT, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 200);
+        assert_eq!(body(res)["types"], serde_json::json!(["Ice"]));
+    }
+
+    #[test]
+    fn it_should_only_delete_the_requested_form() {
+        // Arrange
+        let repo = repo_with_vulpixes();
+        let req = rouille::Request::fake_http("DELETE", "/37/forms/alola", vec![], vec![]);
+
+        // Act
+        let res = crate::api::route(&repo, DEFAULT_NATIONAL_DEX_LIMIT, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 200);
+        let req = rouille::Request::fake_http("GET", "/37/forms", vec![], vec![]);
+        let forms = body(crate::api::route(&repo, DEFAULT_NATIONAL_DEX_LIMIT, &req));
+        assert_eq!(forms.as_array().unwrap().len(), 1);
+    }
+}
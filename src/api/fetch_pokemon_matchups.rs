@@ -0,0 +1,146 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::repositories::pokemon::Repository;
+
+use crate::domain::fetch_pokemon_matchups;
+
+use super::problem::Problem;
+use super::status_code::Status;
+
+#[derive(Serialize)]
+pub struct Matchup {
+    #[serde(rename = "type")]
+    tipe: String,
+    multiplier: f32,
+}
+
+#[derive(Serialize)]
+pub struct Response {
+    number: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    form: Option<String>,
+    name: String,
+    types: Vec<String>,
+    weaknesses: Vec<Matchup>,
+    resistances: Vec<Matchup>,
+    immunities: Vec<Matchup>,
+}
+
+fn matchups(matchups: Vec<fetch_pokemon_matchups::Matchup>) -> Vec<Matchup> {
+    matchups
+        .into_iter()
+        .map(|m| Matchup {
+            tipe: m.tipe,
+            multiplier: m.multiplier,
+        })
+        .collect()
+}
+
+/// Without a form, serves the matchups of the base form of the number.
+pub fn serve(repo: Arc<dyn Repository>, number: u16, form: Option<String>) -> rouille::Response {
+    let mut req = fetch_pokemon_matchups::Request::new(number);
+    if let Some(form) = form {
+        req = req.with_form(form);
+    }
+    match fetch_pokemon_matchups::execute(repo, req) {
+        Ok(res) => rouille::Response::json(&Response {
+            number: res.number,
+            form: res.form,
+            name: res.name,
+            types: res.types,
+            weaknesses: matchups(res.weaknesses),
+            resistances: matchups(res.resistances),
+            immunities: matchups(res.immunities),
+        }),
+        Err(fetch_pokemon_matchups::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(fetch_pokemon_matchups::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(fetch_pokemon_matchups::Error::Unknown(e)) => rouille::Response::from(Problem::storage(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::entities::{Pokemon, PokemonForm, PokemonTypes, DEFAULT_NATIONAL_DEX_LIMIT};
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    use super::*;
+
+    #[test]
+    fn it_should_return_the_matchups_of_the_pokemon() {
+        // Arrange
+        let repo: Arc<dyn Repository> = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![Pokemon::pikachu()]).unwrap();
+        let req = rouille::Request::fake_http("GET", "/25/matchups", vec![], vec![]);
+
+        // Act
+        let res = crate::api::route(&repo, DEFAULT_NATIONAL_DEX_LIMIT, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 200);
+        let (mut reader, _) = res.data.into_reader_and_size();
+        let body: serde_json::Value = serde_json::from_reader(&mut reader).unwrap();
+        assert_eq!(body["number"], 25);
+        assert_eq!(body["types"], serde_json::json!(["Electric"]));
+        assert_eq!(
+            body["weaknesses"],
+            serde_json::json!([{ "type": "Ground", "multiplier": 2.0 }])
+        );
+        assert_eq!(body["resistances"].as_array().unwrap().len(), 3);
+        assert_eq!(body["immunities"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn it_should_return_not_found_when_the_pokemon_does_not_exist() {
+        // Arrange
+        let repo: Arc<dyn Repository> = Arc::new(InMemoryRepository::new());
+        let req = rouille::Request::fake_http("GET", "/25/matchups", vec![], vec![]);
+
+        // Act
+        let res = crate::api::route(&repo, DEFAULT_NATIONAL_DEX_LIMIT, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 404);
+    }
+    #[test]
+    fn it_should_return_the_matchups_of_the_form() {
+        // Arrange
+        let repo: Arc<dyn Repository> = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![
+            Pokemon::vulpix(),
+            Pokemon {
+                types: PokemonTypes::alolan_vulpix(),
+                ..Pokemon::vulpix()
+            }
+            .with_form(Some(PokemonForm::alola())),
+        ])
+        .unwrap();
+        let base = rouille::Request::fake_http("GET", "/37/matchups", vec![], vec![]);
+        let alola = rouille::Request::fake_http("GET", "/37/forms/alola/matchups", vec![], vec![]);
+
+        // Act
+        let base = crate::api::route(&repo, DEFAULT_NATIONAL_DEX_LIMIT, &base);
+        let alola = crate::api::route(&repo, DEFAULT_NATIONAL_DEX_LIMIT, &alola);
+
+        // Assert
+        assert_eq!(base.status_code, 200);
+        assert_eq!(alola.status_code, 200);
+        let body = |res: rouille::Response| {
+            let (mut reader, _) = res.data.into_reader_and_size();
+            serde_json::from_reader::<_, serde_json::Value>(&mut reader).unwrap()
+        };
+        let (base, alola) = (body(base), body(alola));
+        assert_eq!(base.get("form"), None);
+        assert_eq!(
+            base["resistances"][0],
+            serde_json::json!({ "type": "Fire", "multiplier": 0.5 })
+        );
+        assert_eq!(alola["form"], "alola");
+        assert_eq!(alola["types"], serde_json::json!(["Ice"]));
+        assert_eq!(
+            alola["weaknesses"][0],
+            serde_json::json!({ "type": "Fire", "multiplier": 2.0 })
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::domain::fetch_regional_dex;
+use crate::repositories::pokemon::Repository;
+
+use super::fetch_pokemon;
+use super::problem::Problem;
+use super::status_code::Status;
+
+/// A Pokemon of a regional dex, with its index in that dex.
+#[derive(Serialize)]
+pub struct Response {
+    index: u16,
+    #[serde(flatten)]
+    pokemon: fetch_pokemon::Response,
+}
+
+impl From<fetch_regional_dex::Response> for Response {
+    fn from(entry: fetch_regional_dex::Response) -> Self {
+        Self {
+            index: entry.index,
+            pokemon: fetch_pokemon::Response::from(entry.pokemon),
+        }
+    }
+}
+
+pub fn serve(repo: Arc<dyn Repository>, region: String) -> rouille::Response {
+    match fetch_regional_dex::execute(repo, fetch_regional_dex::Request::new(region)) {
+        Ok(entries) => rouille::Response::json(
+            &entries
+                .into_iter()
+                .map(Response::from)
+                .collect::<Vec<Response>>(),
+        ),
+        Err(fetch_regional_dex::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(fetch_regional_dex::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(fetch_regional_dex::Error::Unknown(e)) => rouille::Response::from(Problem::storage(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::entities::{
+        DexEntry, DexIndex, DexName, Pokemon, PokemonNumber, DEFAULT_NATIONAL_DEX_LIMIT,
+    };
+    #[cfg(feature = "airtable")]
+    use crate::repositories::airtable_pokemon::AirtableRepository;
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    use super::*;
+
+    #[test]
+    fn it_should_list_the_dex_in_order() {
+        // Arrange
+        let repo: Arc<dyn Repository> = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![Pokemon::pikachu(), Pokemon::vulpix()])
+            .unwrap();
+        let entry = |index, number| DexEntry::new(DexIndex::from(index), number);
+        repo.replace_regional_dex(
+            DexName::johto(),
+            vec![
+                entry(22, PokemonNumber::pikachu()),
+                entry(1, PokemonNumber::vulpix()),
+            ],
+        )
+        .unwrap();
+        let req = rouille::Request::fake_http("GET", "/dex/johto", vec![], vec![]);
+
+        // Act
+        let res = crate::api::route(&repo, DEFAULT_NATIONAL_DEX_LIMIT, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 200);
+        let (mut reader, _) = res.data.into_reader_and_size();
+        let body: serde_json::Value = serde_json::from_reader(&mut reader).unwrap();
+        assert_eq!(body[0]["index"], 1);
+        assert_eq!(body[0]["number"], 37);
+        assert_eq!(body[1]["index"], 22);
+        assert_eq!(body[1]["name"], "Pikachu");
+    }
+
+    #[test]
+    fn it_should_return_not_found_when_the_dex_does_not_exist() {
+        // Arrange
+        let repo: Arc<dyn Repository> = Arc::new(InMemoryRepository::new());
+        let req = rouille::Request::fake_http("GET", "/dex/paldea", vec![], vec![]);
+
+        // Act
+        let res = crate::api::route(&repo, DEFAULT_NATIONAL_DEX_LIMIT, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 404);
+    }
+
+    #[cfg(feature = "airtable")]
+    #[test]
+    fn it_should_answer_not_implemented_on_airtable() {
+        // Arrange
+        let repo: Arc<dyn Repository> =
+            Arc::new(AirtableRepository::new_test("http://localhost/test", "TEST-KEY"));
+        let req = rouille::Request::fake_http("GET", "/dex/johto", vec![], vec![]);
+
+        // Act
+        let res = crate::api::route(&repo, DEFAULT_NATIONAL_DEX_LIMIT, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 501);
+        let (mut reader, _) = res.data.into_reader_and_size();
+        let body: serde_json::Value = serde_json::from_reader(&mut reader).unwrap();
+        assert_eq!(body["code"], "unsupported");
+        assert_eq!(body["detail"], "regional dexes are not supported by this backend");
+    }
+}
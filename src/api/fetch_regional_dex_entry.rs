@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use crate::domain::fetch_regional_dex_entry;
+use crate::repositories::pokemon::Repository;
+
+use super::fetch_regional_dex::Response;
+use super::problem::Problem;
+use super::status_code::Status;
+
+pub fn serve(repo: Arc<dyn Repository>, region: String, index: u16) -> rouille::Response {
+    let req = fetch_regional_dex_entry::Request::new(region, index);
+    match fetch_regional_dex_entry::execute(repo, req) {
+        Ok(entry) => rouille::Response::json(&Response::from(entry)),
+        Err(fetch_regional_dex_entry::Error::BadRequest) => {
+            rouille::Response::from(Status::BadRequest)
+        }
+        Err(fetch_regional_dex_entry::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(fetch_regional_dex_entry::Error::Unknown(e)) => {
+            rouille::Response::from(Problem::storage(e))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::entities::{
+        DexEntry, DexIndex, DexName, Pokemon, PokemonNumber, DEFAULT_NATIONAL_DEX_LIMIT,
+    };
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    use super::*;
+
+    #[test]
+    fn it_should_serve_the_pokemon_at_the_index() {
+        // Arrange
+        let repo: Arc<dyn Repository> = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![Pokemon::pikachu()]).unwrap();
+        repo.replace_regional_dex(
+            DexName::johto(),
+            vec![DexEntry::new(
+                DexIndex::from(22),
+                PokemonNumber::pikachu(),
+            )],
+        )
+        .unwrap();
+        let req = rouille::Request::fake_http("GET", "/dex/johto/22", vec![], vec![]);
+
+        // Act
+        let res = crate::api::route(&repo, DEFAULT_NATIONAL_DEX_LIMIT, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 200);
+        let (mut reader, _) = res.data.into_reader_and_size();
+        let body: serde_json::Value = serde_json::from_reader(&mut reader).unwrap();
+        assert_eq!(body["index"], 22);
+        assert_eq!(body["number"], 25);
+        assert_eq!(body["generation"], 1);
+    }
+}
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::domain::fetch_trainer_dex;
+use crate::repositories::pokemon::Repository;
+
+use super::problem::Problem;
+use super::status_code::Status;
+
+/// What a trainer recorded about one Pokemon.
+#[derive(Serialize)]
+pub struct Response {
+    number: u16,
+    generation: Option<u8>,
+    name: String,
+    seen: bool,
+    caught: bool,
+    shiny: bool,
+}
+
+impl From<fetch_trainer_dex::Response> for Response {
+    fn from(entry: fetch_trainer_dex::Response) -> Self {
+        Self {
+            number: entry.number,
+            generation: entry.generation,
+            name: entry.name,
+            seen: entry.seen,
+            caught: entry.caught,
+            shiny: entry.shiny,
+        }
+    }
+}
+
+pub fn serve(repo: Arc<dyn Repository>, trainer: String) -> rouille::Response {
+    match fetch_trainer_dex::execute(repo, fetch_trainer_dex::Request::new(trainer)) {
+        Ok(entries) => rouille::Response::json(
+            &entries
+                .into_iter()
+                .map(Response::from)
+                .collect::<Vec<Response>>(),
+        ),
+        Err(fetch_trainer_dex::Error::BadRequest) => rouille::Response::from(Status::BadRequest),
+        Err(fetch_trainer_dex::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(fetch_trainer_dex::Error::Unknown(e)) => rouille::Response::from(Problem::storage(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::entities::{DexMark, DexStatus, Pokemon, Trainer, DEFAULT_NATIONAL_DEX_LIMIT};
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    use super::*;
+
+    #[test]
+    fn it_should_list_what_the_trainer_recorded() {
+        // Arrange
+        let repo: Arc<dyn Repository> = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![Pokemon::pikachu(), Pokemon::vulpix()])
+            .unwrap();
+        repo.insert_trainer(Trainer::red()).unwrap();
+        let seen = DexStatus::default().mark(DexMark::Seen);
+        repo.replace_dex_status(Trainer::red().id, Pokemon::vulpix().number, seen)
+            .unwrap();
+        let req = rouille::Request::fake_http("GET", "/trainers/red/dex", vec![], vec![]);
+
+        // Act
+        let res = crate::api::route(&repo, DEFAULT_NATIONAL_DEX_LIMIT, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 200);
+        let (mut reader, _) = res.data.into_reader_and_size();
+        let body: serde_json::Value = serde_json::from_reader(&mut reader).unwrap();
+        assert_eq!(body.as_array().unwrap().len(), 1);
+        assert_eq!(body[0]["number"], 37);
+        assert_eq!(body[0]["seen"], true);
+        assert_eq!(body[0]["caught"], false);
+    }
+
+    #[test]
+    fn it_should_return_not_found_when_the_trainer_does_not_exist() {
+        // Arrange
+        let repo: Arc<dyn Repository> = Arc::new(InMemoryRepository::new());
+        let req = rouille::Request::fake_http("GET", "/trainers/blue/dex", vec![], vec![]);
+
+        // Act
+        let res = crate::api::route(&repo, DEFAULT_NATIONAL_DEX_LIMIT, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 404);
+    }
+}
@@ -0,0 +1,163 @@
+use std::io::Read;
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::domain::import_pokemons;
+use crate::repositories::pokemon::Repository;
+
+use super::problem::Problem;
+use super::status_code::Status;
+
+#[derive(Serialize)]
+struct RowError {
+    line: usize,
+    name: String,
+    code: &'static str,
+    reason: String,
+}
+
+#[derive(Serialize)]
+struct Response {
+    created: usize,
+    updated: usize,
+    skipped: usize,
+    dry_run: bool,
+    errors: Vec<RowError>,
+}
+
+fn invalid_query(detail: &str) -> Problem {
+    Problem::new(Status::BadRequest)
+        .code("invalid_query")
+        .detail(detail)
+}
+
+/// The `format` query parameter wins over the `Content-Type` header.
+fn format(req: &rouille::Request) -> Option<String> {
+    req.get_param("format").or_else(|| {
+        let content_type = req.header("Content-Type")?;
+        let format = match content_type.split(';').next()?.trim() {
+            "text/csv" => "csv",
+            "application/json" => "json",
+            "application/x-ndjson" | "application/jsonl" => "jsonl",
+            _ => return None,
+        };
+        Some(String::from(format))
+    })
+}
+
+fn request(
+    req: &rouille::Request,
+    national_dex_limit: u16,
+) -> Result<import_pokemons::Request, Problem> {
+    let format = format(req).ok_or_else(|| {
+        invalid_query("query parameter format must be one of csv, json or jsonl")
+    })?;
+    let dry_run = match req.get_param("dry_run").as_deref() {
+        None | Some("false") => false,
+        Some("true") => true,
+        Some(_) => return Err(invalid_query("query parameter dry_run must be true or false")),
+    };
+
+    let mut content = String::new();
+    if let Some(mut body) = req.data() {
+        body.read_to_string(&mut content).map_err(|e| {
+            Problem::new(Status::BadRequest)
+                .code("invalid_body")
+                .detail(e.to_string())
+        })?;
+    }
+
+    Ok(import_pokemons::Request {
+        format,
+        content,
+        mode: req.get_param("mode"),
+        dry_run,
+        national_dex_limit,
+    })
+}
+
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    national_dex_limit: u16,
+    req: &rouille::Request,
+) -> rouille::Response {
+    let req = match request(req, national_dex_limit) {
+        Ok(req) => req,
+        Err(problem) => return rouille::Response::from(problem),
+    };
+
+    match import_pokemons::execute(repo, req) {
+        Ok(res) => rouille::Response::json(&Response {
+            created: res.created,
+            updated: res.updated,
+            skipped: res.skipped,
+            dry_run: res.dry_run,
+            errors: res
+                .errors
+                .into_iter()
+                .flat_map(|row| {
+                    row.errors.into_iter().map(move |e| RowError {
+                        line: row.line,
+                        name: e.field,
+                        code: e.code,
+                        reason: e.message,
+                    })
+                })
+                .collect(),
+        }),
+        Err(import_pokemons::Error::BadRequest) => rouille::Response::from(
+            invalid_query("the format, the mode or the file itself is invalid"),
+        ),
+        Err(import_pokemons::Error::Interrupted { written, source }) => {
+            let detail = format!("import stopped after {written} Pokemons: {source}");
+            rouille::Response::from(Problem::storage(source).detail(detail))
+        }
+        Err(import_pokemons::Error::Unknown(e)) => rouille::Response::from(Problem::storage(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::entities::DEFAULT_NATIONAL_DEX_LIMIT;
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    use super::*;
+
+    #[test]
+    fn it_should_import_a_csv_body() {
+        // Arrange
+        let repo = Arc::new(InMemoryRepository::new());
+        let body = "number,name,types,hp,attack,defense,special_attack,special_defense,speed
+25,Pikachu,Electric,35,55,40,50,50,90
+0,Vulpix,Fire,38,41,40,50,65,65
+";
+        let headers = vec![("Content-Type".to_owned(), "text/csv".to_owned())];
+        let req = rouille::Request::fake_http("POST", "/import", headers, body.into());
+
+        // Act
+        let res = serve(repo, DEFAULT_NATIONAL_DEX_LIMIT, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 200);
+        let mut body = String::new();
+        res.data.into_reader_and_size().0.read_to_string(&mut body).unwrap();
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(body["created"], 1);
+        assert_eq!(body["errors"][0]["line"], 3);
+        assert_eq!(body["errors"][0]["name"], "number");
+    }
+
+    #[test]
+    fn it_should_return_bad_request_when_format_is_missing() {
+        // Arrange
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = rouille::Request::fake_http("POST", "/import", vec![], vec![]);
+
+        // Act
+        let res = serve(repo, DEFAULT_NATIONAL_DEX_LIMIT, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 400);
+    }
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use crate::domain::mark_dex_entry;
+use crate::repositories::pokemon::Repository;
+
+use super::fetch_trainer_dex::Response;
+use super::problem::Problem;
+use super::status_code::Status;
+
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    trainer: String,
+    number: u16,
+    mark: String,
+) -> rouille::Response {
+    let req = mark_dex_entry::Request {
+        trainer,
+        number,
+        mark,
+    };
+
+    match mark_dex_entry::execute(repo, req) {
+        Ok(res) => rouille::Response::json(&Response::from(res)),
+        Err(mark_dex_entry::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation_failed(errors))
+        }
+        Err(mark_dex_entry::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(mark_dex_entry::Error::Unknown(e)) => rouille::Response::from(Problem::storage(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::entities::{Pokemon, Trainer, DEFAULT_NATIONAL_DEX_LIMIT};
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    use super::*;
+
+    #[test]
+    fn it_should_mark_a_catch() {
+        // Arrange
+        let repo: Arc<dyn Repository> = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![Pokemon::pikachu()]).unwrap();
+        repo.insert_trainer(Trainer::red()).unwrap();
+        let req = rouille::Request::fake_http("PUT", "/trainers/red/dex/25/caught", vec![], vec![]);
+
+        // Act
+        let res = crate::api::route(&repo, DEFAULT_NATIONAL_DEX_LIMIT, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 200);
+        let status = repo.fetch_trainer_dex(Trainer::red().id).unwrap()[0].1;
+        assert!(status.seen() && status.caught());
+    }
+
+    #[test]
+    fn it_should_return_bad_request_when_the_mark_is_unknown() {
+        // Arrange
+        let repo: Arc<dyn Repository> = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![Pokemon::pikachu()]).unwrap();
+        repo.insert_trainer(Trainer::red()).unwrap();
+        let req = rouille::Request::fake_http("PUT", "/trainers/red/dex/25/owned", vec![], vec![]);
+
+        // Act
+        let res = crate::api::route(&repo, DEFAULT_NATIONAL_DEX_LIMIT, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 400);
+    }
+}
@@ -1,36 +1,201 @@
+mod create_ability;
+mod create_evolution;
+mod create_move;
 mod create_pokemon;
+mod create_trainer;
+mod fetch_ability_holders;
+mod fetch_all_abilities;
+mod fetch_all_moves;
 mod fetch_all_pokemons;
+mod fetch_dex_completion;
+mod fetch_evolution_chain;
+mod fetch_learnset;
+mod fetch_move;
 mod fetch_pokemon;
+mod fetch_pokemon_abilities;
+mod fetch_pokemon_forms;
+mod fetch_pokemon_matchups;
+mod fetch_regional_dex;
+mod fetch_regional_dex_entry;
+mod fetch_trainer_dex;
+mod delete_move;
 mod delete_pokemon;
+mod export_pokemons;
 mod health;
+mod import_pokemons;
+mod mark_dex_entry;
+mod moves;
+mod problem;
+mod stats;
 mod status_code;
+mod unmark_dex_entry;
+mod update_learnset;
+mod update_move;
+mod update_pokemon;
+mod update_pokemon_abilities;
+mod update_regional_dex;
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use status_code::Status;
 
+use crate::config::LogLevel;
 use crate::repositories::pokemon::Repository;
 
-pub fn serve(addr: &str, repo: Arc<dyn Repository>) {
+fn log(log_level: LogLevel, req: &rouille::Request, res: &rouille::Response, elapsed: Duration) {
+    let threshold = match res.status_code {
+        500.. => LogLevel::Error,
+        400..=499 => LogLevel::Warn,
+        _ => LogLevel::Info,
+    };
+    if log_level >= threshold {
+        eprintln!(
+            "{} {} {} {:?}",
+            req.method(),
+            req.raw_url(),
+            res.status_code,
+            elapsed
+        );
+    }
+}
+
+pub fn serve(addr: &str, repo: Arc<dyn Repository>, log_level: LogLevel, national_dex_limit: u16) {
     rouille::start_server(addr, move |req| {
-        router!(req,
-        (GET) (/health) => {
-            health::serve()
-        },
-        (POST) (/) => {
-            create_pokemon::serve(repo.clone(), req)
-        },
-        (GET) (/{number: u16}) => {
-            fetch_pokemon::serve(repo.clone(), number)
-        },
-        (DELETE) (/{number: u16}) => {
-            delete_pokemon::serve(repo.clone(), number)
-        },
-        (GET) (/) => {
-            fetch_all_pokemons::serve(repo.clone())
-        },
-        _ => {
-            rouille::Response::from(Status::NotFound)
-        })
+        rouille::log_custom(
+            req,
+            |req, res, elapsed| log(log_level, req, res, elapsed),
+            |req, elapsed| {
+                if log_level >= LogLevel::Error {
+                    eprintln!("{} {} panicked after {:?}", req.method(), req.raw_url(), elapsed);
+                }
+            },
+            || route(&repo, national_dex_limit, req),
+        )
+    })
+}
+
+fn route(
+    repo: &Arc<dyn Repository>,
+    national_dex_limit: u16,
+    req: &rouille::Request,
+) -> rouille::Response {
+    router!(req,
+    (GET) (/health) => {
+        health::serve()
+    },
+    (GET) (/export) => {
+        export_pokemons::serve(repo.clone(), req)
+    },
+    (POST) (/) => {
+        create_pokemon::serve(repo.clone(), national_dex_limit, req)
+    },
+    (POST) (/import) => {
+        import_pokemons::serve(repo.clone(), national_dex_limit, req)
+    },
+    (GET) (/moves) => {
+        fetch_all_moves::serve(repo.clone())
+    },
+    (POST) (/moves) => {
+        create_move::serve(repo.clone(), req)
+    },
+    (GET) (/moves/{id: u16}) => {
+        fetch_move::serve(repo.clone(), id)
+    },
+    (PUT) (/moves/{id: u16}) => {
+        update_move::serve(repo.clone(), id, req)
+    },
+    (DELETE) (/moves/{id: u16}) => {
+        delete_move::serve(repo.clone(), id)
+    },
+    (GET) (/abilities) => {
+        fetch_all_abilities::serve(repo.clone())
+    },
+    (POST) (/abilities) => {
+        create_ability::serve(repo.clone(), req)
+    },
+    (GET) (/abilities/{name: String}/pokemons) => {
+        fetch_ability_holders::serve(repo.clone(), name)
+    },
+    (GET) (/dex/{region: String}) => {
+        fetch_regional_dex::serve(repo.clone(), region)
+    },
+    (PUT) (/dex/{region: String}) => {
+        update_regional_dex::serve(repo.clone(), national_dex_limit, region, req)
+    },
+    (GET) (/dex/{region: String}/{index: u16}) => {
+        fetch_regional_dex_entry::serve(repo.clone(), region, index)
+    },
+    (POST) (/trainers) => {
+        create_trainer::serve(repo.clone(), req)
+    },
+    (GET) (/trainers/{id: String}/dex) => {
+        fetch_trainer_dex::serve(repo.clone(), id)
+    },
+    (GET) (/trainers/{id: String}/dex/completion) => {
+        fetch_dex_completion::serve(repo.clone(), id)
+    },
+    (PUT) (/trainers/{id: String}/dex/{number: u16}/{mark: String}) => {
+        mark_dex_entry::serve(repo.clone(), id, number, mark)
+    },
+    (DELETE) (/trainers/{id: String}/dex/{number: u16}/{mark: String}) => {
+        unmark_dex_entry::serve(repo.clone(), id, number, mark)
+    },
+    (GET) (/{number: u16}) => {
+        fetch_pokemon::serve(repo.clone(), number, None)
+    },
+    (GET) (/{number: u16}/forms) => {
+        fetch_pokemon_forms::serve(repo.clone(), number)
+    },
+    (GET) (/{number: u16}/forms/{form: String}) => {
+        fetch_pokemon::serve(repo.clone(), number, Some(form))
+    },
+    (PUT) (/{number: u16}/forms/{form: String}) => {
+        update_pokemon::serve(repo.clone(), number, Some(form), req)
+    },
+    (PATCH) (/{number: u16}/forms/{form: String}) => {
+        update_pokemon::serve_partial(repo.clone(), number, Some(form), req)
+    },
+    (DELETE) (/{number: u16}/forms/{form: String}) => {
+        delete_pokemon::serve(repo.clone(), number, Some(form))
+    },
+    (GET) (/{number: u16}/forms/{form: String}/matchups) => {
+        fetch_pokemon_matchups::serve(repo.clone(), number, Some(form))
+    },
+    (GET) (/{number: u16}/matchups) => {
+        fetch_pokemon_matchups::serve(repo.clone(), number, None)
+    },
+    (GET) (/{number: u16}/evolutions) => {
+        fetch_evolution_chain::serve(repo.clone(), number)
+    },
+    (POST) (/{number: u16}/evolutions) => {
+        create_evolution::serve(repo.clone(), number, req)
+    },
+    (GET) (/{number: u16}/moves) => {
+        fetch_learnset::serve(repo.clone(), number)
+    },
+    (PUT) (/{number: u16}/moves) => {
+        update_learnset::serve(repo.clone(), number, req)
+    },
+    (GET) (/{number: u16}/abilities) => {
+        fetch_pokemon_abilities::serve(repo.clone(), number)
+    },
+    (PUT) (/{number: u16}/abilities) => {
+        update_pokemon_abilities::serve(repo.clone(), number, req)
+    },
+    (PUT) (/{number: u16}) => {
+        update_pokemon::serve(repo.clone(), number, None, req)
+    },
+    (PATCH) (/{number: u16}) => {
+        update_pokemon::serve_partial(repo.clone(), number, None, req)
+    },
+    (DELETE) (/{number: u16}) => {
+        delete_pokemon::serve(repo.clone(), number, None)
+    },
+    (GET) (/) => {
+        fetch_all_pokemons::serve(repo.clone(), req)
+    },
+    _ => {
+        rouille::Response::from(Status::NotFound)
     })
 }
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{create_move, fetch_learnset, fetch_move};
+
+/// The fields of a move, as written by `POST /moves` and `PUT /moves/{id}`.
+#[derive(Deserialize, Serialize)]
+pub struct MoveBody {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub tipe: String,
+    pub category: String,
+    #[serde(default)]
+    pub power: Option<u16>,
+    #[serde(default)]
+    pub accuracy: Option<u16>,
+    pub pp: u16,
+}
+
+impl MoveBody {
+    pub fn into_request(self, id: u16) -> create_move::Request {
+        create_move::Request {
+            id,
+            name: self.name,
+            tipe: self.tipe,
+            category: self.category,
+            power: self.power,
+            accuracy: self.accuracy,
+            pp: self.pp,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct MoveResponse {
+    id: u16,
+    name: String,
+    #[serde(rename = "type")]
+    tipe: String,
+    category: String,
+    power: Option<u16>,
+    accuracy: Option<u16>,
+    pp: u16,
+}
+
+impl From<fetch_move::Response> for MoveResponse {
+    fn from(res: fetch_move::Response) -> Self {
+        Self {
+            id: res.id,
+            name: res.name,
+            tipe: res.tipe,
+            category: res.category,
+            power: res.power,
+            accuracy: res.accuracy,
+            pp: res.pp,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct LearnedMoveResponse {
+    #[serde(flatten)]
+    details: MoveResponse,
+    method: String,
+}
+
+impl From<fetch_learnset::Response> for LearnedMoveResponse {
+    fn from(res: fetch_learnset::Response) -> Self {
+        Self {
+            details: MoveResponse {
+                id: res.id,
+                name: res.name,
+                tipe: res.tipe,
+                category: res.category,
+                power: res.power,
+                accuracy: res.accuracy,
+                pp: res.pp,
+            },
+            method: res.method,
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use serde::Serialize;
+
+use crate::domain::entities::FieldError;
+use crate::repositories::pokemon::StorageError;
+
+use super::status_code::Status;
+
+#[derive(Serialize)]
+struct InvalidParam {
+    name: String,
+    code: &'static str,
+    reason: String,
+}
+
+/// An RFC 7807 `application/problem+json` error body.
+#[derive(Serialize)]
+pub struct Problem {
+    #[serde(rename = "type")]
+    tipe: &'static str,
+    title: &'static str,
+    status: u16,
+    code: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<String>,
+    #[serde(rename = "invalid-params", skip_serializing_if = "Vec::is_empty")]
+    invalid_params: Vec<InvalidParam>,
+}
+
+impl Problem {
+    pub fn new(status: Status) -> Self {
+        Self {
+            tipe: "about:blank",
+            title: status.title(),
+            status: status.code(),
+            code: status.error_code(),
+            detail: None,
+            invalid_params: vec![],
+        }
+    }
+
+    pub fn code(self, code: &'static str) -> Self {
+        Self { code, ..self }
+    }
+
+    pub fn detail(self, detail: impl Into<String>) -> Self {
+        Self {
+            detail: Some(detail.into()),
+            ..self
+        }
+    }
+
+    pub fn invalid_params(self, errors: Vec<FieldError>) -> Self {
+        Self {
+            invalid_params: errors
+                .into_iter()
+                .map(|e| InvalidParam {
+                    name: e.field,
+                    code: e.code,
+                    reason: e.message,
+                })
+                .collect(),
+            ..self
+        }
+    }
+
+    pub fn invalid_body(e: rouille::input::json::JsonError) -> Self {
+        Self::new(Status::BadRequest)
+            .code("invalid_body")
+            .detail(e.to_string())
+    }
+
+    /// Storage failures are reported as unavailable when retrying later may succeed,
+    /// and as not implemented when the backend can't store that data at all.
+    pub fn storage(e: StorageError) -> Self {
+        let status = match e {
+            StorageError::Timeout | StorageError::HttpStatus { status: 429, .. } => {
+                Status::ServiceUnavailable
+            }
+            StorageError::Unsupported(_) => Status::NotImplemented,
+            _ => Status::InternalServerError,
+        };
+        Self::new(status).code(e.code()).detail(e.to_string())
+    }
+
+    pub fn validation_failed(errors: Vec<FieldError>) -> Self {
+        Self::new(Status::BadRequest)
+            .code("validation_failed")
+            .detail("one or more fields are invalid")
+            .invalid_params(errors)
+    }
+}
+
+impl From<Problem> for rouille::Response {
+    fn from(problem: Problem) -> Self {
+        rouille::Response::json(&problem)
+            .with_status_code(problem.status)
+            .with_unique_header("Content-Type", "application/problem+json")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_tell_a_broken_backend_from_a_throttled_one() {
+        // Arrange
+        let broken = StorageError::CorruptRow(String::from("bad type"));
+        let throttled = StorageError::HttpStatus {
+            status: 429,
+            body: String::new(),
+        };
+
+        // Act
+        let broken = Problem::storage(broken);
+        let throttled = Problem::storage(throttled);
+
+        // Assert
+        assert_eq!(broken.status, 500);
+        assert_eq!(broken.code, "corrupt_row");
+        assert_eq!(throttled.status, 503);
+        assert_eq!(throttled.code, "upstream_http_status");
+    }
+
+    #[test]
+    fn it_should_report_unsupported_data_as_not_implemented() {
+        // Arrange
+        let unsupported = StorageError::Unsupported("evolutions");
+
+        // Act
+        let problem = Problem::storage(unsupported);
+
+        // Assert
+        assert_eq!(problem.status, 501);
+        assert_eq!(problem.code, "unsupported");
+    }
+}
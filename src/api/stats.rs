@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+pub struct Stats {
+    pub hp: u16,
+    pub attack: u16,
+    pub defense: u16,
+    pub special_attack: u16,
+    pub special_defense: u16,
+    pub speed: u16,
+}
+
+impl From<Stats> for [u16; 6] {
+    fn from(stats: Stats) -> Self {
+        [
+            stats.hp,
+            stats.attack,
+            stats.defense,
+            stats.special_attack,
+            stats.special_defense,
+            stats.speed,
+        ]
+    }
+}
+
+#[derive(Serialize)]
+pub struct StatsResponse {
+    hp: u16,
+    attack: u16,
+    defense: u16,
+    special_attack: u16,
+    special_defense: u16,
+    speed: u16,
+    total: u16,
+}
+
+impl StatsResponse {
+    pub fn new(stats: [u16; 6], total: u16) -> Self {
+        let [hp, attack, defense, special_attack, special_defense, speed] = stats;
+        Self {
+            hp,
+            attack,
+            defense,
+            special_attack,
+            special_defense,
+            speed,
+            total,
+        }
+    }
+}
@@ -1,25 +1,64 @@
+use super::problem::Problem;
+
 pub enum Status {
     Ok,
     BadRequest,
     NotFound,
     Conflict,
     InternalServerError,
+    NotImplemented,
+    ServiceUnavailable,
 }
-impl From<Status> for rouille::Response {
-    fn from(status: Status) -> Self {
-        let status_code = match status {
+
+impl Status {
+    pub fn code(&self) -> u16 {
+        match self {
             Status::Ok => 200,
             Status::BadRequest => 400,
             Status::NotFound => 404,
             Status::Conflict => 409,
             Status::InternalServerError => 500,
-        };
+            Status::NotImplemented => 501,
+            Status::ServiceUnavailable => 503,
+        }
+    }
 
-        Self {
-            status_code,
-            headers: vec![],
-            data: rouille::ResponseBody::empty(),
-            upgrade: None,
+    pub fn title(&self) -> &'static str {
+        match self {
+            Status::Ok => "OK",
+            Status::BadRequest => "Bad Request",
+            Status::NotFound => "Not Found",
+            Status::Conflict => "Conflict",
+            Status::InternalServerError => "Internal Server Error",
+            Status::NotImplemented => "Not Implemented",
+            Status::ServiceUnavailable => "Service Unavailable",
+        }
+    }
+
+    /// Machine-readable error code used when nothing more specific applies.
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::BadRequest => "bad_request",
+            Status::NotFound => "not_found",
+            Status::Conflict => "conflict",
+            Status::InternalServerError => "internal_error",
+            Status::NotImplemented => "not_implemented",
+            Status::ServiceUnavailable => "unavailable",
+        }
+    }
+}
+
+impl From<Status> for rouille::Response {
+    fn from(status: Status) -> Self {
+        match status {
+            Status::Ok => Self {
+                status_code: status.code(),
+                headers: vec![],
+                data: rouille::ResponseBody::empty(),
+                upgrade: None,
+            },
+            status => rouille::Response::from(Problem::new(status)),
         }
     }
 }
@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use crate::domain::unmark_dex_entry;
+use crate::repositories::pokemon::Repository;
+
+use super::fetch_trainer_dex::Response;
+use super::problem::Problem;
+use super::status_code::Status;
+
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    trainer: String,
+    number: u16,
+    mark: String,
+) -> rouille::Response {
+    let req = unmark_dex_entry::Request {
+        trainer,
+        number,
+        mark,
+    };
+
+    match unmark_dex_entry::execute(repo, req) {
+        Ok(res) => rouille::Response::json(&Response::from(res)),
+        Err(unmark_dex_entry::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation_failed(errors))
+        }
+        Err(unmark_dex_entry::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(unmark_dex_entry::Error::Unknown(e)) => rouille::Response::from(Problem::storage(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::entities::{DexMark, DexStatus, Pokemon, Trainer, DEFAULT_NATIONAL_DEX_LIMIT};
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    use super::*;
+
+    #[test]
+    fn it_should_forget_a_pokemon_no_longer_seen() {
+        // Arrange
+        let repo: Arc<dyn Repository> = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![Pokemon::pikachu()]).unwrap();
+        repo.insert_trainer(Trainer::red()).unwrap();
+        let caught = DexStatus::default().mark(DexMark::Caught);
+        repo.replace_dex_status(Trainer::red().id, Pokemon::pikachu().number, caught)
+            .unwrap();
+        let req =
+            rouille::Request::fake_http("DELETE", "/trainers/red/dex/25/seen", vec![], vec![]);
+
+        // Act
+        let res = crate::api::route(&repo, DEFAULT_NATIONAL_DEX_LIMIT, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 200);
+        assert!(repo
+            .fetch_trainer_dex(Trainer::red().id)
+            .unwrap()
+            .is_empty());
+    }
+}
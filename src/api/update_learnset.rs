@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::update_learnset;
+use crate::repositories::pokemon::Repository;
+
+use super::moves::LearnedMoveResponse;
+use super::problem::Problem;
+use super::status_code::Status;
+
+#[derive(Deserialize, Serialize)]
+struct Entry {
+    id: u16,
+    method: String,
+}
+
+pub fn serve(repo: Arc<dyn Repository>, number: u16, req: &rouille::Request) -> rouille::Response {
+    let req = match rouille::input::json_input::<Vec<Entry>>(req) {
+        Ok(entries) => update_learnset::Request {
+            number,
+            moves: entries
+                .into_iter()
+                .map(|entry| update_learnset::Entry {
+                    id: entry.id,
+                    method: entry.method,
+                })
+                .collect(),
+        },
+        Err(e) => return rouille::Response::from(Problem::invalid_body(e)),
+    };
+
+    match update_learnset::execute(repo, req) {
+        Ok(moves) => rouille::Response::json(
+            &moves
+                .into_iter()
+                .map(LearnedMoveResponse::from)
+                .collect::<Vec<LearnedMoveResponse>>(),
+        ),
+        Err(update_learnset::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation_failed(errors))
+        }
+        Err(update_learnset::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(update_learnset::Error::Unknown(e)) => rouille::Response::from(Problem::storage(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::entities::{Move, Pokemon};
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    use super::*;
+
+    fn request(entries: Vec<Entry>) -> rouille::Request {
+        let data = serde_json::to_string(&entries).unwrap().into_bytes();
+        let headers = vec![("Content-Type".to_owned(), "application/json".to_owned())];
+        rouille::Request::fake_http("PUT", "/25/moves", headers, data)
+    }
+
+    #[test]
+    fn it_should_return_ok_when_every_move_exists() {
+        // Arrange
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![Pokemon::pikachu()]).unwrap();
+        repo.insert_move(Move::thunder_shock()).unwrap();
+        let req = request(vec![Entry {
+            id: 84,
+            method: "level:1".to_owned(),
+        }]);
+
+        // Act
+        let res = serve(repo.clone(), 25, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 200);
+        assert_eq!(repo.fetch_learnset(Pokemon::pikachu().number).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn it_should_return_bad_request_when_a_move_does_not_exist() {
+        // Arrange
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![Pokemon::pikachu()]).unwrap();
+        let req = request(vec![Entry {
+            id: 84,
+            method: "machine".to_owned(),
+        }]);
+
+        // Act
+        let res = serve(repo, 25, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 400);
+    }
+}
@@ -0,0 +1,24 @@
+use std::sync::Arc;
+
+use crate::domain::update_move;
+use crate::repositories::pokemon::Repository;
+
+use super::moves::{MoveBody, MoveResponse};
+use super::problem::Problem;
+use super::status_code::Status;
+
+pub fn serve(repo: Arc<dyn Repository>, id: u16, req: &rouille::Request) -> rouille::Response {
+    let req = match rouille::input::json_input::<MoveBody>(req) {
+        Ok(body) => body.into_request(id),
+        Err(e) => return rouille::Response::from(Problem::invalid_body(e)),
+    };
+
+    match update_move::execute(repo, req) {
+        Ok(res) => rouille::Response::json(&MoveResponse::from(res)),
+        Err(update_move::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation_failed(errors))
+        }
+        Err(update_move::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(update_move::Error::Unknown(e)) => rouille::Response::from(Problem::storage(e)),
+    }
+}
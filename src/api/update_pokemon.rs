@@ -0,0 +1,203 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::update_pokemon;
+use crate::repositories::pokemon::Repository;
+
+use super::problem::Problem;
+use super::stats::{Stats, StatsResponse};
+use super::status_code::Status;
+
+#[derive(Deserialize, Serialize)]
+struct Request {
+    name: String,
+    types: Vec<String>,
+    stats: Stats,
+}
+
+#[derive(Deserialize, Serialize)]
+struct PartialRequest {
+    name: Option<String>,
+    types: Option<Vec<String>>,
+    stats: Option<Stats>,
+}
+
+#[derive(Serialize)]
+struct Response {
+    number: u16,
+    generation: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    form: Option<String>,
+    name: String,
+    types: Vec<String>,
+    stats: StatsResponse,
+}
+
+/// Without a form, replaces the base form of the number.
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    number: u16,
+    form: Option<String>,
+    req: &rouille::Request,
+) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => update_pokemon::Request {
+            number,
+            form,
+            name: Some(req.name),
+            types: Some(req.types),
+            stats: Some(<[u16; 6]>::from(req.stats)),
+        },
+        Err(e) => return rouille::Response::from(Problem::invalid_body(e)),
+    };
+
+    respond(update_pokemon::execute(repo, req))
+}
+
+pub fn serve_partial(
+    repo: Arc<dyn Repository>,
+    number: u16,
+    form: Option<String>,
+    req: &rouille::Request,
+) -> rouille::Response {
+    let req = match rouille::input::json_input::<PartialRequest>(req) {
+        Ok(req) => update_pokemon::Request {
+            number,
+            form,
+            name: req.name,
+            types: req.types,
+            stats: req.stats.map(<[u16; 6]>::from),
+        },
+        Err(e) => return rouille::Response::from(Problem::invalid_body(e)),
+    };
+
+    respond(update_pokemon::execute(repo, req))
+}
+
+fn respond(res: Result<update_pokemon::Response, update_pokemon::Error>) -> rouille::Response {
+    match res {
+        Ok(res) => rouille::Response::json(&Response {
+            number: res.number,
+            generation: res.generation,
+            form: res.form,
+            name: res.name,
+            types: res.types,
+            stats: StatsResponse::new(res.stats, res.total),
+        }),
+        Err(update_pokemon::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation_failed(errors))
+        }
+        Err(update_pokemon::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(update_pokemon::Error::Unknown(e)) => rouille::Response::from(Problem::storage(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::entities::{PokemonName, PokemonNumber, PokemonStats, PokemonTypes};
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    use super::*;
+
+    fn stats() -> Stats {
+        Stats {
+            hp: 35,
+            attack: 55,
+            defense: 40,
+            special_attack: 50,
+            special_defense: 50,
+            speed: 90,
+        }
+    }
+
+    fn request<T: Serialize>(method: &str, body: Option<T>) -> rouille::Request {
+        match body {
+            Some(body) => {
+                let data = serde_json::to_string(&body).unwrap().into_bytes();
+                let headers = vec![("Content-Type".to_owned(), "application/json".to_owned())];
+                rouille::Request::fake_http(method, "/25", headers, data)
+            }
+            None => rouille::Request::fake_http(method, "/25", vec![], vec![]),
+        }
+    }
+
+    fn repo_with_pikachu() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu().into(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .expect("error inserting pikachu");
+        repo
+    }
+
+    #[test]
+    fn it_should_return_bad_request_when_body_is_empty() {
+        // Arrange
+        let req = request::<Request>("PUT", None);
+        let repo = repo_with_pikachu();
+
+        // Act
+        let res = serve(repo, 25, None, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 400);
+    }
+
+    #[test]
+    fn it_should_return_not_found_when_pokemon_does_not_exist() {
+        // Arrange
+        let req = Request {
+            name: "Pikachu".to_owned(),
+            types: vec!["Electric".to_owned()],
+            stats: stats(),
+        };
+        let req = request("PUT", Some(req));
+        let repo = Arc::new(InMemoryRepository::new());
+
+        // Act
+        let res = serve(repo, 25, None, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 404);
+    }
+
+    #[test]
+    fn it_should_return_ok_when_body_is_valid() {
+        // Arrange
+        let req = Request {
+            name: "Pikachu".to_owned(),
+            types: vec!["Electric".to_owned()],
+            stats: stats(),
+        };
+        let req = request("PUT", Some(req));
+        let repo = repo_with_pikachu();
+
+        // Act
+        let res = serve(repo, 25, None, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 200);
+    }
+
+    #[test]
+    fn it_should_return_ok_when_partial_body_is_valid() {
+        // Arrange
+        let req = PartialRequest {
+            name: Some("Pikachoo".to_owned()),
+            types: None,
+            stats: None,
+        };
+        let req = request("PATCH", Some(req));
+        let repo = repo_with_pikachu();
+
+        // Act
+        let res = serve_partial(repo, 25, None, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 200);
+    }
+}
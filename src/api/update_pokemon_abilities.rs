@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::update_pokemon_abilities;
+use crate::repositories::pokemon::Repository;
+
+use super::fetch_pokemon_abilities::Response;
+use super::problem::Problem;
+use super::status_code::Status;
+
+#[derive(Deserialize, Serialize)]
+struct Request {
+    #[serde(default)]
+    regular: Vec<String>,
+    #[serde(default)]
+    hidden: Option<String>,
+}
+
+pub fn serve(repo: Arc<dyn Repository>, number: u16, req: &rouille::Request) -> rouille::Response {
+    let req = match rouille::input::json_input::<Request>(req) {
+        Ok(req) => update_pokemon_abilities::Request {
+            number,
+            regular: req.regular,
+            hidden: req.hidden,
+        },
+        Err(e) => return rouille::Response::from(Problem::invalid_body(e)),
+    };
+
+    match update_pokemon_abilities::execute(repo, req) {
+        Ok(res) => rouille::Response::json(&Response {
+            regular: res.regular,
+            hidden: res.hidden,
+        }),
+        Err(update_pokemon_abilities::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation_failed(errors))
+        }
+        Err(update_pokemon_abilities::Error::NotFound) => rouille::Response::from(Status::NotFound),
+        Err(update_pokemon_abilities::Error::Unknown(e)) => {
+            rouille::Response::from(Problem::storage(e))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::entities::{Ability, Pokemon};
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    use super::*;
+
+    fn request(body: Request) -> rouille::Request {
+        let data = serde_json::to_string(&body).unwrap().into_bytes();
+        let headers = vec![("Content-Type".to_owned(), "application/json".to_owned())];
+        rouille::Request::fake_http("PUT", "/25/abilities", headers, data)
+    }
+
+    #[test]
+    fn it_should_return_ok_when_every_ability_exists() {
+        // Arrange
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![Pokemon::pikachu()]).unwrap();
+        repo.insert_ability(Ability::static_()).unwrap();
+        let req = request(Request {
+            regular: vec!["Static".to_owned()],
+            hidden: None,
+        });
+
+        // Act
+        let res = serve(repo, 25, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 200);
+    }
+
+    #[test]
+    fn it_should_return_bad_request_when_there_are_too_many_abilities() {
+        // Arrange
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![Pokemon::pikachu()]).unwrap();
+        let req = request(Request {
+            regular: vec!["Static".to_owned(), "Run Away".to_owned(), "Guts".to_owned()],
+            hidden: None,
+        });
+
+        // Act
+        let res = serve(repo, 25, &req);
+
+        // Assert
+        assert_eq!(res.status_code, 400);
+    }
+}
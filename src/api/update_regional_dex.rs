@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::update_regional_dex;
+use crate::repositories::pokemon::Repository;
+
+use super::fetch_regional_dex::Response;
+use super::problem::Problem;
+
+#[derive(Deserialize, Serialize)]
+struct Entry {
+    index: u16,
+    number: u16,
+}
+
+pub fn serve(
+    repo: Arc<dyn Repository>,
+    national_dex_limit: u16,
+    region: String,
+    req: &rouille::Request,
+) -> rouille::Response {
+    let req = match rouille::input::json_input::<Vec<Entry>>(req) {
+        Ok(entries) => update_regional_dex::Request {
+            region,
+            entries: entries
+                .into_iter()
+                .map(|entry| update_regional_dex::Entry {
+                    index: entry.index,
+                    number: entry.number,
+                })
+                .collect(),
+            national_dex_limit,
+        },
+        Err(e) => return rouille::Response::from(Problem::invalid_body(e)),
+    };
+
+    match update_regional_dex::execute(repo, req) {
+        Ok(entries) => rouille::Response::json(
+            &entries
+                .into_iter()
+                .map(Response::from)
+                .collect::<Vec<Response>>(),
+        ),
+        Err(update_regional_dex::Error::BadRequest(errors)) => {
+            rouille::Response::from(Problem::validation_failed(errors))
+        }
+        Err(update_regional_dex::Error::Unknown(e)) => rouille::Response::from(Problem::storage(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::entities::{DexName, Pokemon, DEFAULT_NATIONAL_DEX_LIMIT};
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    use super::*;
+
+    fn request(entries: Vec<Entry>) -> rouille::Request {
+        let data = serde_json::to_string(&entries).unwrap().into_bytes();
+        let headers = vec![("Content-Type".to_owned(), "application/json".to_owned())];
+        rouille::Request::fake_http("PUT", "/dex/johto", headers, data)
+    }
+
+    #[test]
+    fn it_should_return_ok_when_every_pokemon_exists() {
+        // Arrange
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![Pokemon::pikachu()]).unwrap();
+        let req = request(vec![Entry {
+            index: 22,
+            number: 25,
+        }]);
+
+        // Act
+        let res = serve(repo.clone(), DEFAULT_NATIONAL_DEX_LIMIT, String::from("johto"), &req);
+
+        // Assert
+        assert_eq!(res.status_code, 200);
+        assert_eq!(repo.fetch_regional_dex(DexName::johto()).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn it_should_return_bad_request_when_a_pokemon_does_not_exist() {
+        // Arrange
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = request(vec![Entry {
+            index: 1,
+            number: 152,
+        }]);
+
+        // Act
+        let res = serve(repo, DEFAULT_NATIONAL_DEX_LIMIT, String::from("johto"), &req);
+
+        // Assert
+        assert_eq!(res.status_code, 400);
+    }
+}
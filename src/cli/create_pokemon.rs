@@ -3,20 +3,26 @@ use std::sync::Arc;
 use crate::{repositories::pokemon::Repository};
 use crate::domain::create_pokemon;
 
-use super::{prompt_number, prompt_name, prompt_types};
+use super::exit_code::ExitCode;
+use super::output::{self, Format};
+use super::{prompt_number, prompt_name, prompt_types, prompt_stats};
 
 
-pub fn run(repo: Arc<dyn Repository>) {
+pub fn run(repo: Arc<dyn Repository>, national_dex_limit: u16) {
     let number = prompt_number();
     let name = prompt_name();
     let types = prompt_types();
+    let stats = prompt_stats();
 
 
-    let req = match (number, name, types) {
-        (Ok(number), Ok(name), Ok(types)) => create_pokemon::Request {
+    let req = match (number, name, types, stats) {
+        (Ok(number), Ok(name), Ok(types), Ok(stats)) => create_pokemon::Request {
             number,
+            form: None,
             name,
             types,
+            stats,
+            national_dex_limit,
         },
         _ => {
             println!("An error occurred during the prompt");
@@ -25,12 +31,71 @@ pub fn run(repo: Arc<dyn Repository>) {
     };
 
     match create_pokemon::execute(repo, req) {
-        Ok(res) => println!(
-            "{:?}",
-            res
+        Ok(p) => output::print_one(
+            Format::Table,
+            &output::Pokemon::new(p.number, p.name, p.types, p.stats, p.total).with_form(p.form),
         ),
-        Err(create_pokemon::Error::BadRequest) => println!("The request is invalid"),
+        Err(create_pokemon::Error::BadRequest(errors)) => {
+            println!("The request is invalid");
+            for error in errors {
+                println!("  {}: {}", error.field, error.message);
+            }
+        }
         Err(create_pokemon::Error::Conflict) => println!("The Pokemon already exists"),
-        Err(create_pokemon::Error::Unknown) => println!("An unknown error occurred"),
+        Err(create_pokemon::Error::Unknown(e)) => println!("An unknown error occurred: {e}"),
     };
-}
\ No newline at end of file
+}
+pub fn exec(repo: Arc<dyn Repository>, req: create_pokemon::Request, format: Format) -> ExitCode {
+    match create_pokemon::execute(repo, req) {
+        Ok(p) => {
+            output::print_one(
+                format,
+                &output::Pokemon::new(p.number, p.name, p.types, p.stats, p.total).with_form(p.form),
+            );
+            ExitCode::Success
+        }
+        Err(create_pokemon::Error::BadRequest(errors)) => {
+            eprintln!("The request is invalid");
+            for error in errors {
+                eprintln!("  {}: {}", error.field, error.message);
+            }
+            ExitCode::BadRequest
+        }
+        Err(create_pokemon::Error::Conflict) => {
+            eprintln!("The Pokemon already exists");
+            ExitCode::Conflict
+        }
+        Err(create_pokemon::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {e}");
+            ExitCode::Unknown
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::DEFAULT_NATIONAL_DEX_LIMIT;
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    fn request() -> create_pokemon::Request {
+        create_pokemon::Request {
+            number: 25,
+            form: None,
+            name: String::from("Pikachu"),
+            types: vec![String::from("Electric")],
+            stats: [35, 55, 40, 50, 50, 90],
+            national_dex_limit: DEFAULT_NATIONAL_DEX_LIMIT,
+        }
+    }
+
+    #[test]
+    fn it_should_exit_with_conflict_when_pokemon_already_exists() {
+        let repo = Arc::new(InMemoryRepository::new());
+        assert_eq!(exec(repo.clone(), request(), Format::Json), ExitCode::Success);
+
+        let code = exec(repo, request(), Format::Json);
+
+        assert_eq!(code.code(), 4);
+    }
+}
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use crate::domain::create_trainer;
+use crate::repositories::pokemon::Repository;
+
+use super::exit_code::ExitCode;
+use super::output::{self, Format};
+
+pub fn exec(repo: Arc<dyn Repository>, req: create_trainer::Request, format: Format) -> ExitCode {
+    match create_trainer::execute(repo, req) {
+        Ok(trainer) => {
+            output::print_one(format, &output::Trainer::new(trainer.id, trainer.name));
+            ExitCode::Success
+        }
+        Err(create_trainer::Error::BadRequest(errors)) => {
+            eprintln!("The request is invalid");
+            for error in errors {
+                eprintln!("  {}: {}", error.field, error.message);
+            }
+            ExitCode::BadRequest
+        }
+        Err(create_trainer::Error::Conflict) => {
+            eprintln!("The trainer already exists");
+            ExitCode::Conflict
+        }
+        Err(create_trainer::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {e}");
+            ExitCode::Unknown
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    fn request() -> create_trainer::Request {
+        create_trainer::Request {
+            id: String::from("red"),
+            name: String::from("Red"),
+        }
+    }
+
+    #[test]
+    fn it_should_exit_with_conflict_when_the_trainer_already_exists() {
+        let repo = Arc::new(InMemoryRepository::new());
+        assert_eq!(
+            exec(repo.clone(), request(), Format::Json),
+            ExitCode::Success
+        );
+
+        let code = exec(repo, request(), Format::Json);
+
+        assert_eq!(code, ExitCode::Conflict);
+    }
+}
@@ -3,11 +3,14 @@ use crate::domain::delete_pokemon;
 use crate::repositories::pokemon::Repository;
 use std::sync::Arc;
 
+use super::exit_code::ExitCode;
+use super::output::Format;
+
 pub fn run(repo: Arc<dyn Repository>) {
     let number = prompt_number();
 
     let req = match number {
-        Ok(number) => delete_pokemon::Request { number },
+        Ok(number) => delete_pokemon::Request { number, form: None },
         _ => {
             println!("An error occurred during the prompt");
             return;
@@ -18,6 +21,33 @@ pub fn run(repo: Arc<dyn Repository>) {
         Ok(()) => println!("The Pokemon has been deleted"),
         Err(delete_pokemon::Error::BadRequest) => println!("The request is invalid"),
         Err(delete_pokemon::Error::NotFound) => println!("The Pokemon does not exist"),
-        Err(delete_pokemon::Error::Unknown) => println!("An unknown error occurred"),
+        Err(delete_pokemon::Error::Unknown(e)) => println!("An unknown error occurred: {e}"),
+    }
+}
+pub fn exec(
+    repo: Arc<dyn Repository>,
+    number: u16,
+    form: Option<String>,
+    format: Format,
+) -> ExitCode {
+    match delete_pokemon::execute(repo, delete_pokemon::Request { number, form }) {
+        Ok(()) => {
+            if format == Format::Table {
+                println!("The Pokemon has been deleted");
+            }
+            ExitCode::Success
+        }
+        Err(delete_pokemon::Error::BadRequest) => {
+            eprintln!("The request is invalid");
+            ExitCode::BadRequest
+        }
+        Err(delete_pokemon::Error::NotFound) => {
+            eprintln!("The Pokemon does not exist");
+            ExitCode::NotFound
+        }
+        Err(delete_pokemon::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {e}");
+            ExitCode::Unknown
+        }
     }
-}
\ No newline at end of file
+}
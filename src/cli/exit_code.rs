@@ -0,0 +1,21 @@
+/// Process exit status of a scripted command, one per domain error variant.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExitCode {
+    Success,
+    Unknown,
+    BadRequest,
+    NotFound,
+    Conflict,
+}
+
+impl ExitCode {
+    pub fn code(&self) -> i32 {
+        match self {
+            ExitCode::Success => 0,
+            ExitCode::Unknown => 1,
+            ExitCode::BadRequest => 2,
+            ExitCode::NotFound => 3,
+            ExitCode::Conflict => 4,
+        }
+    }
+}
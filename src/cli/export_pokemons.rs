@@ -0,0 +1,43 @@
+use std::io::Write;
+use std::sync::Arc;
+
+use crate::domain::export_pokemons;
+use crate::repositories::pokemon::Repository;
+
+use super::exit_code::ExitCode;
+
+/// Writes the export to `output`, chunk by chunk.
+pub fn exec(repo: Arc<dyn Repository>, req: export_pokemons::Request, output: &mut dyn Write) -> ExitCode {
+    let export = match export_pokemons::execute(repo, req) {
+        Ok(export) => export,
+        Err(export_pokemons::Error::BadRequest) => {
+            eprintln!("The format is invalid");
+            return ExitCode::BadRequest;
+        }
+        Err(export_pokemons::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {e}");
+            return ExitCode::Unknown;
+        }
+    };
+
+    for chunk in export {
+        let written = match chunk {
+            Ok(chunk) => output.write_all(chunk.as_bytes()),
+            Err(e) => {
+                eprintln!("The export stopped: {e}");
+                return ExitCode::Unknown;
+            }
+        };
+        if let Err(e) = written {
+            eprintln!("An error occurred while writing the export: {e}");
+            return ExitCode::Unknown;
+        }
+    }
+    match output.flush() {
+        Ok(()) => ExitCode::Success,
+        Err(e) => {
+            eprintln!("An error occurred while writing the export: {e}");
+            ExitCode::Unknown
+        }
+    }
+}
@@ -3,15 +3,32 @@ use std::sync::Arc;
 use crate::{repositories::pokemon::Repository};
 use crate::domain::fetch_all_pokemons;
 
+use super::exit_code::ExitCode;
+use super::output::{self, Format};
+
 
 pub fn run(repo: Arc<dyn Repository>) {
-    match fetch_all_pokemons::execute(repo) {
-        Ok(res) => res.into_iter().for_each(|p| {
-            println!(
-                "{:?}",
-                p
-            );
-        }),
-        Err(fetch_all_pokemons::Error::Unknown) => println!("An unknown error occurred"),
-    };
-}
\ No newline at end of file
+    exec(repo, fetch_all_pokemons::Request::default(), Format::Table);
+}
+
+pub fn exec(repo: Arc<dyn Repository>, req: fetch_all_pokemons::Request, format: Format) -> ExitCode {
+    match fetch_all_pokemons::execute(repo, req) {
+        Ok(page) => {
+            let pokemons = page
+                .pokemons
+                .into_iter()
+                .map(|p| output::Pokemon::new(p.number, p.name, p.types, p.stats, p.total).with_form(p.form))
+                .collect::<Vec<output::Pokemon>>();
+            output::print(format, &pokemons);
+            ExitCode::Success
+        }
+        Err(fetch_all_pokemons::Error::BadRequest) => {
+            eprintln!("The request is invalid");
+            ExitCode::BadRequest
+        }
+        Err(fetch_all_pokemons::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {e}");
+            ExitCode::Unknown
+        }
+    }
+}
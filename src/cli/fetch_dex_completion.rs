@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use crate::domain::fetch_dex_completion;
+use crate::repositories::pokemon::Repository;
+
+use super::exit_code::ExitCode;
+use super::output::{self, Format};
+
+fn row(generation: Option<u8>, c: fetch_dex_completion::Completion) -> output::Completion {
+    output::Completion::new(
+        generation,
+        [c.total, c.seen, c.caught, c.shiny],
+        c.percentage,
+    )
+}
+
+/// Prints the overall completion first, then the completion of each generation.
+pub fn exec(repo: Arc<dyn Repository>, trainer: String, format: Format) -> ExitCode {
+    match fetch_dex_completion::execute(repo, fetch_dex_completion::Request::new(trainer)) {
+        Ok(res) => {
+            let mut rows = vec![row(None, res.overall)];
+            rows.extend(
+                res.generations
+                    .into_iter()
+                    .map(|(generation, completion)| row(Some(generation), completion)),
+            );
+            output::print(format, &rows);
+            ExitCode::Success
+        }
+        Err(fetch_dex_completion::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {e}");
+            ExitCode::Unknown
+        }
+        Err(fetch_dex_completion::Error::BadRequest) => {
+            eprintln!("Invalid trainer");
+            ExitCode::BadRequest
+        }
+        Err(fetch_dex_completion::Error::NotFound) => {
+            eprintln!("Trainer not found");
+            ExitCode::NotFound
+        }
+    }
+}
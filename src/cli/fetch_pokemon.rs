@@ -3,6 +3,8 @@ use std::sync::Arc;
 use crate::domain::fetch_pokemon;
 use crate::repositories::pokemon::Repository;
 
+use super::exit_code::ExitCode;
+use super::output::{self, Format};
 use super::prompt_number;
 
 pub fn run(repo: Arc<dyn Repository>) {
@@ -16,9 +18,70 @@ pub fn run(repo: Arc<dyn Repository>) {
 
     let req = fetch_pokemon::Request::new(number);
     match fetch_pokemon::execute(repo, req) {
-        Ok(res) => println!("{:?}", res),
-        Err(fetch_pokemon::Error::Unknown) => println!("An unknown error uccurred"),
+        Ok(p) => output::print_one(
+            Format::Table,
+            &output::Pokemon::new(p.number, p.name, p.types, p.stats, p.total).with_form(p.form),
+        ),
+        Err(fetch_pokemon::Error::Unknown(e)) => println!("An unknown error occurred: {e}"),
         Err(fetch_pokemon::Error::BadRequest) => println!("Invalid request"),
         Err(fetch_pokemon::Error::NotFound) => println!("Pokemon not found"),
     }
 }
+
+pub fn exec(
+    repo: Arc<dyn Repository>,
+    number: u16,
+    form: Option<String>,
+    format: Format,
+) -> ExitCode {
+    let mut req = fetch_pokemon::Request::new(number);
+    if let Some(form) = form {
+        req = req.with_form(form);
+    }
+    match fetch_pokemon::execute(repo, req) {
+        Ok(p) => {
+            output::print_one(
+                format,
+                &output::Pokemon::new(p.number, p.name, p.types, p.stats, p.total).with_form(p.form),
+            );
+            ExitCode::Success
+        }
+        Err(fetch_pokemon::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {e}");
+            ExitCode::Unknown
+        }
+        Err(fetch_pokemon::Error::BadRequest) => {
+            eprintln!("Invalid request");
+            ExitCode::BadRequest
+        }
+        Err(fetch_pokemon::Error::NotFound) => {
+            eprintln!("Pokemon not found");
+            ExitCode::NotFound
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_exit_with_not_found_when_repo_does_not_contain_pokemon() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        let code = exec(repo, 25, None, Format::Json);
+
+        assert_eq!(code, ExitCode::NotFound);
+        assert_eq!(code.code(), 3);
+    }
+
+    #[test]
+    fn it_should_exit_with_bad_request_when_number_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        let code = exec(repo, 0, None, Format::Json);
+
+        assert_eq!(code.code(), 2);
+    }
+}
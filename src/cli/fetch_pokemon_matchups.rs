@@ -0,0 +1,113 @@
+use std::sync::Arc;
+
+use crate::domain::fetch_pokemon_matchups;
+use crate::repositories::pokemon::Repository;
+
+use super::exit_code::ExitCode;
+use super::output::{self, Format};
+use super::prompt_number;
+
+fn matchups(matchups: Vec<fetch_pokemon_matchups::Matchup>) -> Vec<output::Matchup> {
+    matchups
+        .into_iter()
+        .map(|m| output::Matchup::new(m.tipe, m.multiplier))
+        .collect()
+}
+
+fn render(res: fetch_pokemon_matchups::Response) -> output::Matchups {
+    output::Matchups::new(
+        res.number,
+        res.name,
+        res.types,
+        [
+            matchups(res.weaknesses),
+            matchups(res.resistances),
+            matchups(res.immunities),
+        ],
+    )
+    .with_form(res.form)
+}
+
+pub fn run(repo: Arc<dyn Repository>) {
+    let number = match prompt_number() {
+        Ok(n) => n,
+        Err(_) => {
+            println!("An error occurred during prompt!");
+            return;
+        }
+    };
+
+    let req = fetch_pokemon_matchups::Request::new(number);
+    match fetch_pokemon_matchups::execute(repo, req) {
+        Ok(res) => output::print_one(Format::Table, &render(res)),
+        Err(fetch_pokemon_matchups::Error::Unknown(e)) => println!("An unknown error occurred: {e}"),
+        Err(fetch_pokemon_matchups::Error::BadRequest) => println!("Invalid request"),
+        Err(fetch_pokemon_matchups::Error::NotFound) => println!("Pokemon not found"),
+    }
+}
+
+pub fn exec(
+    repo: Arc<dyn Repository>,
+    number: u16,
+    form: Option<String>,
+    format: Format,
+) -> ExitCode {
+    let mut req = fetch_pokemon_matchups::Request::new(number);
+    if let Some(form) = form {
+        req = req.with_form(form);
+    }
+    match fetch_pokemon_matchups::execute(repo, req) {
+        Ok(res) => {
+            output::print_one(format, &render(res));
+            ExitCode::Success
+        }
+        Err(fetch_pokemon_matchups::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {e}");
+            ExitCode::Unknown
+        }
+        Err(fetch_pokemon_matchups::Error::BadRequest) => {
+            eprintln!("Invalid request");
+            ExitCode::BadRequest
+        }
+        Err(fetch_pokemon_matchups::Error::NotFound) => {
+            eprintln!("Pokemon not found");
+            ExitCode::NotFound
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::Pokemon;
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_exit_with_success_when_the_pokemon_exists() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![Pokemon::pikachu()]).unwrap();
+
+        let code = exec(repo, 25, None, Format::Json);
+
+        assert_eq!(code, ExitCode::Success);
+    }
+
+    #[test]
+    fn it_should_exit_with_not_found_when_repo_does_not_contain_pokemon() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        let code = exec(repo, 25, Some(String::from("alola")), Format::Json);
+
+        assert_eq!(code, ExitCode::NotFound);
+        assert_eq!(code.code(), 3);
+    }
+
+    #[test]
+    fn it_should_exit_with_bad_request_when_number_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        let code = exec(repo, 0, None, Format::Json);
+
+        assert_eq!(code.code(), 2);
+    }
+}
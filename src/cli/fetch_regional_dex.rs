@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use dialoguer::Input;
+
+use crate::domain::{fetch_regional_dex, fetch_regional_dex_entry};
+use crate::repositories::pokemon::Repository;
+
+use super::exit_code::ExitCode;
+use super::output::{self, Format};
+
+fn entry(entry: fetch_regional_dex::Response) -> output::DexEntry {
+    let p = entry.pokemon;
+    output::DexEntry::new(
+        entry.index,
+        output::Pokemon::new(p.number, p.name, p.types, p.stats, p.total),
+    )
+}
+
+pub fn run(repo: Arc<dyn Repository>) {
+    let region = match Input::<String>::new()
+        .with_prompt("Regional dex")
+        .interact_text()
+    {
+        Ok(region) => region,
+        Err(_) => {
+            println!("An error occurred during prompt!");
+            return;
+        }
+    };
+
+    exec(repo, region, None, Format::Table);
+}
+
+/// Prints the whole dex, or only the Pokemon at `index`.
+pub fn exec(
+    repo: Arc<dyn Repository>,
+    region: String,
+    index: Option<u16>,
+    format: Format,
+) -> ExitCode {
+    if let Some(index) = index {
+        return exec_entry(repo, region, index, format);
+    }
+    match fetch_regional_dex::execute(repo, fetch_regional_dex::Request::new(region)) {
+        Ok(entries) => {
+            let entries = entries
+                .into_iter()
+                .map(entry)
+                .collect::<Vec<output::DexEntry>>();
+            output::print(format, &entries);
+            ExitCode::Success
+        }
+        Err(fetch_regional_dex::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {e}");
+            ExitCode::Unknown
+        }
+        Err(fetch_regional_dex::Error::BadRequest) => {
+            eprintln!("Invalid request");
+            ExitCode::BadRequest
+        }
+        Err(fetch_regional_dex::Error::NotFound) => {
+            eprintln!("Regional dex not found");
+            ExitCode::NotFound
+        }
+    }
+}
+
+fn exec_entry(repo: Arc<dyn Repository>, region: String, index: u16, format: Format) -> ExitCode {
+    let req = fetch_regional_dex_entry::Request::new(region, index);
+    match fetch_regional_dex_entry::execute(repo, req) {
+        Ok(found) => {
+            output::print_one(format, &entry(found));
+            ExitCode::Success
+        }
+        Err(fetch_regional_dex_entry::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {e}");
+            ExitCode::Unknown
+        }
+        Err(fetch_regional_dex_entry::Error::BadRequest) => {
+            eprintln!("Invalid request");
+            ExitCode::BadRequest
+        }
+        Err(fetch_regional_dex_entry::Error::NotFound) => {
+            eprintln!("Nothing at this index of the regional dex");
+            ExitCode::NotFound
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{DexEntry, DexIndex, DexName, Pokemon, PokemonNumber};
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_exit_with_not_found_outside_of_the_dex() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![Pokemon::pikachu()]).unwrap();
+        repo.replace_regional_dex(
+            DexName::johto(),
+            vec![DexEntry::new(
+                DexIndex::from(22),
+                PokemonNumber::pikachu(),
+            )],
+        )
+        .unwrap();
+
+        assert_eq!(
+            exec(repo.clone(), String::from("johto"), Some(22), Format::Json),
+            ExitCode::Success
+        );
+        assert_eq!(
+            exec(repo.clone(), String::from("johto"), Some(1), Format::Json),
+            ExitCode::NotFound
+        );
+        assert_eq!(
+            exec(repo, String::from("kanto"), None, Format::Json),
+            ExitCode::NotFound
+        );
+    }
+}
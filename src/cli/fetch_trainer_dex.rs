@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use crate::domain::fetch_trainer_dex;
+use crate::repositories::pokemon::Repository;
+
+use super::exit_code::ExitCode;
+use super::output::{self, Format};
+
+pub(super) fn entry(entry: fetch_trainer_dex::Response) -> output::TrainerDexEntry {
+    output::TrainerDexEntry::new(
+        entry.number,
+        entry.name,
+        [entry.seen, entry.caught, entry.shiny],
+    )
+}
+
+pub fn exec(repo: Arc<dyn Repository>, trainer: String, format: Format) -> ExitCode {
+    match fetch_trainer_dex::execute(repo, fetch_trainer_dex::Request::new(trainer)) {
+        Ok(entries) => {
+            let entries = entries
+                .into_iter()
+                .map(entry)
+                .collect::<Vec<output::TrainerDexEntry>>();
+            output::print(format, &entries);
+            ExitCode::Success
+        }
+        Err(fetch_trainer_dex::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {e}");
+            ExitCode::Unknown
+        }
+        Err(fetch_trainer_dex::Error::BadRequest) => {
+            eprintln!("Invalid trainer");
+            ExitCode::BadRequest
+        }
+        Err(fetch_trainer_dex::Error::NotFound) => {
+            eprintln!("Trainer not found");
+            ExitCode::NotFound
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::domain::import_pokemons;
+use crate::repositories::pokemon::Repository;
+
+use super::exit_code::ExitCode;
+use super::output::{self, Cell, Format, Tabular};
+
+#[derive(Serialize)]
+struct Summary {
+    created: usize,
+    updated: usize,
+    skipped: usize,
+    failed: usize,
+    dry_run: bool,
+}
+
+impl Tabular for Summary {
+    fn headers() -> Vec<&'static str> {
+        vec!["created", "updated", "skipped", "failed", "dry_run"]
+    }
+
+    fn cells(&self) -> Vec<Cell> {
+        vec![
+            Cell::Number(self.created as u64),
+            Cell::Number(self.updated as u64),
+            Cell::Number(self.skipped as u64),
+            Cell::Number(self.failed as u64),
+            Cell::Text(self.dry_run.to_string()),
+        ]
+    }
+}
+
+/// Invalid rows are reported on stderr and make the command exit with a bad request,
+/// even though the valid ones have been imported.
+pub fn exec(repo: Arc<dyn Repository>, req: import_pokemons::Request, format: Format) -> ExitCode {
+    match import_pokemons::execute(repo, req) {
+        Ok(res) => {
+            for row in &res.errors {
+                for error in &row.errors {
+                    eprintln!("line {}: {}: {}", row.line, error.field, error.message);
+                }
+            }
+            output::print_one(
+                format,
+                &Summary {
+                    created: res.created,
+                    updated: res.updated,
+                    skipped: res.skipped,
+                    failed: res.errors.len(),
+                    dry_run: res.dry_run,
+                },
+            );
+            match res.errors.is_empty() {
+                true => ExitCode::Success,
+                false => ExitCode::BadRequest,
+            }
+        }
+        Err(import_pokemons::Error::BadRequest) => {
+            eprintln!("The format, the mode or the file itself is invalid");
+            ExitCode::BadRequest
+        }
+        Err(import_pokemons::Error::Interrupted { written, source }) => {
+            eprintln!("The import stopped after {written} Pokemons: {source}");
+            ExitCode::Unknown
+        }
+        Err(import_pokemons::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {e}");
+            ExitCode::Unknown
+        }
+    }
+}
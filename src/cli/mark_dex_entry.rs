@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use dialoguer::Input;
+
+use crate::domain::mark_dex_entry;
+use crate::repositories::pokemon::Repository;
+
+use super::exit_code::ExitCode;
+use super::output::{self, Format};
+use super::{fetch_trainer_dex, prompt_number};
+
+pub fn run(repo: Arc<dyn Repository>) {
+    let trainer = Input::<String>::new()
+        .with_prompt("Trainer")
+        .interact_text();
+    let number = prompt_number();
+
+    let req = match (trainer, number) {
+        (Ok(trainer), Ok(number)) => mark_dex_entry::Request {
+            trainer,
+            number,
+            mark: String::from("caught"),
+        },
+        _ => {
+            println!("An error occurred during the prompt");
+            return;
+        }
+    };
+
+    exec(repo, req, Format::Table);
+}
+
+pub fn exec(repo: Arc<dyn Repository>, req: mark_dex_entry::Request, format: Format) -> ExitCode {
+    match mark_dex_entry::execute(repo, req) {
+        Ok(res) => {
+            output::print_one(format, &fetch_trainer_dex::entry(res));
+            ExitCode::Success
+        }
+        Err(mark_dex_entry::Error::BadRequest(errors)) => {
+            eprintln!("The request is invalid");
+            for error in errors {
+                eprintln!("  {}: {}", error.field, error.message);
+            }
+            ExitCode::BadRequest
+        }
+        Err(mark_dex_entry::Error::NotFound) => {
+            eprintln!("The trainer or the Pokemon does not exist");
+            ExitCode::NotFound
+        }
+        Err(mark_dex_entry::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {e}");
+            ExitCode::Unknown
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Pokemon, Trainer};
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    fn request(trainer: &str) -> mark_dex_entry::Request {
+        mark_dex_entry::Request {
+            trainer: String::from(trainer),
+            number: 25,
+            mark: String::from("caught"),
+        }
+    }
+
+    #[test]
+    fn it_should_exit_with_not_found_for_unknown_trainers() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![Pokemon::pikachu()]).unwrap();
+        repo.insert_trainer(Trainer::red()).unwrap();
+
+        assert_eq!(
+            exec(repo.clone(), request("red"), Format::Json),
+            ExitCode::Success
+        );
+        assert_eq!(
+            exec(repo, request("blue"), Format::Json),
+            ExitCode::NotFound
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::domain::migrate_pokemons::{self, Mismatch, Progress};
+use crate::repositories::pokemon::Repository;
+
+use super::exit_code::ExitCode;
+use super::output::{self, Cell, Format, Tabular};
+
+#[derive(Serialize)]
+struct Summary {
+    created: usize,
+    updated: usize,
+    skipped: usize,
+    /// Only set when the migration was verified.
+    mismatches: Option<usize>,
+}
+
+impl Tabular for Summary {
+    fn headers() -> Vec<&'static str> {
+        vec!["created", "updated", "skipped", "mismatches"]
+    }
+
+    fn cells(&self) -> Vec<Cell> {
+        vec![
+            Cell::Number(self.created as u64),
+            Cell::Number(self.updated as u64),
+            Cell::Number(self.skipped as u64),
+            match self.mismatches {
+                Some(mismatches) => Cell::Number(mismatches as u64),
+                None => Cell::Text(String::from("-")),
+            },
+        ]
+    }
+}
+
+fn report_progress(progress: Progress) {
+    eprintln!("Migrated {}/{} Pokemons", progress.written, progress.total);
+}
+
+/// Conflicts and verification mismatches are reported on stderr.
+pub fn exec(
+    from: Arc<dyn Repository>,
+    to: Arc<dyn Repository>,
+    req: migrate_pokemons::Request,
+    format: Format,
+) -> ExitCode {
+    match migrate_pokemons::execute(from, to, req, &report_progress) {
+        Ok(res) => {
+            let mismatches = res.mismatches.as_deref().unwrap_or_default();
+            for mismatch in mismatches {
+                match mismatch {
+                    Mismatch::Missing(key) => eprintln!("Pokemon {key} is missing"),
+                    Mismatch::Different(key) => eprintln!("Pokemon {key} differs"),
+                }
+            }
+            output::print_one(
+                format,
+                &Summary {
+                    created: res.created,
+                    updated: res.updated,
+                    skipped: res.skipped,
+                    mismatches: res.mismatches.as_ref().map(Vec::len),
+                },
+            );
+            match mismatches.is_empty() {
+                true => ExitCode::Success,
+                false => ExitCode::Unknown,
+            }
+        }
+        Err(migrate_pokemons::Error::BadRequest) => {
+            eprintln!("The mode is invalid");
+            ExitCode::BadRequest
+        }
+        Err(migrate_pokemons::Error::Conflict(keys)) => {
+            let keys = keys.iter().map(ToString::to_string).collect::<Vec<String>>();
+            eprintln!("These Pokemons already exist in the target: {}", keys.join(", "));
+            ExitCode::Conflict
+        }
+        Err(migrate_pokemons::Error::Source(e)) => {
+            eprintln!("An error occurred while reading the source: {e}");
+            ExitCode::Unknown
+        }
+        Err(migrate_pokemons::Error::Target(e)) => {
+            eprintln!("An error occurred while writing the target: {e}");
+            ExitCode::Unknown
+        }
+        Err(migrate_pokemons::Error::Interrupted { written, source }) => {
+            eprintln!("The migration stopped after {written} Pokemons: {source}");
+            ExitCode::Unknown
+        }
+    }
+}
@@ -1,19 +1,52 @@
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
 use dialoguer::{theme::ColorfulTheme, Select, Input, MultiSelect};
+use std::str::FromStr;
 use std::sync::Arc;
 
-use crate::repositories::pokemon::Repository;
+use crate::config::{AirtableLayer, Config, ConfigError, Layer, SqliteLayer};
+use crate::domain::entities::PokemonType;
+use crate::domain::{
+    create_pokemon as create, create_trainer as new_trainer, export_pokemons as export,
+    fetch_all_pokemons as fetch_all, import_pokemons as import, mark_dex_entry as mark,
+    migrate_pokemons as migrate, unmark_dex_entry as unmark,
+};
+use crate::repositories::{self, pokemon::Repository};
 
 mod create_pokemon;
+mod create_trainer;
+pub mod exit_code;
+mod export_pokemons;
 mod fetch_all_pokemons;
+mod fetch_dex_completion;
 mod fetch_pokemon;
+mod fetch_pokemon_matchups;
+mod fetch_regional_dex;
+mod fetch_trainer_dex;
+mod import_pokemons;
+mod mark_dex_entry;
+mod migrate_pokemons;
 mod delete_pokemon;
+mod output;
+mod unmark_dex_entry;
+mod update_pokemon;
 
-pub fn run(repo: Arc<dyn Repository>) {
+use exit_code::ExitCode;
+use output::Format;
+
+/// Stats given to a Pokemon created without `--stats`, the lowest accepted,
+/// until the real ones are set with `PATCH /{number}`.
+const DEFAULT_STATS: [u16; 6] = [1; 6];
+
+pub fn run(repo: Arc<dyn Repository>, national_dex_limit: u16) {
     let choices = [
         "Fetch all Pokemons",
         "Fetch a Pokemon",
+        "Fetch a Pokemon's matchups",
+        "Browse a regional dex",
         "Create a Pokemon",
+        "Update a Pokemon",
         "Delete a Pokemon",
+        "Mark a catch",
         "Exit",
     ];
     loop {
@@ -31,31 +64,35 @@ pub fn run(repo: Arc<dyn Repository>) {
         match index {
             0 => fetch_all_pokemons::run(repo.clone()),
             1 => fetch_pokemon::run(repo.clone()),
-            2 => create_pokemon::run(repo.clone()),
-            3 => delete_pokemon::run(repo.clone()),
-            4 => break,
+            2 => fetch_pokemon_matchups::run(repo.clone()),
+            3 => fetch_regional_dex::run(repo.clone()),
+            4 => create_pokemon::run(repo.clone(), national_dex_limit),
+            5 => update_pokemon::run(repo.clone()),
+            6 => delete_pokemon::run(repo.clone()),
+            7 => mark_dex_entry::run(repo.clone()),
+            8 => break,
             _ => continue,
         }
     }
 }
 
 
-pub fn prompt_number() -> Result<u16, ()> {
+pub(crate) fn prompt_number() -> Result<u16, ()> {
     match Input::new().with_prompt("Pokemon number").interact_text() {
         Ok(number) => Ok(number),
         _ => Err(()),
     }
 }
 
-pub fn prompt_name() -> Result<String, ()> {
+pub(crate) fn prompt_name() -> Result<String, ()> {
     match Input::new().with_prompt("Pokemon name").interact_text() {
         Ok(name) => Ok(name),
         _ => Err(()),
     }
 }
 
-pub fn prompt_types() -> Result<Vec<String>, ()> {
-    let types = ["Electric", "Fire"];
+pub(crate) fn prompt_types() -> Result<Vec<String>, ()> {
+    let types = PokemonType::names();
     match MultiSelect::new()
         .with_prompt("Pokemon types")
         .items(&types)
@@ -68,3 +105,606 @@ pub fn prompt_types() -> Result<Vec<String>, ()> {
         _ => Err(()),
     }
 }
+
+pub(crate) fn prompt_stats() -> Result<[u16; 6], ()> {
+    let labels = ["HP", "Attack", "Defense", "Sp. Atk", "Sp. Def", "Speed"];
+    let mut stats = [0; 6];
+    for (stat, label) in stats.iter_mut().zip(labels) {
+        match Input::new().with_prompt(label).interact_text() {
+            Ok(value) => *stat = value,
+            _ => return Err(()),
+        }
+    }
+    Ok(stats)
+}
+
+fn format_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("format")
+        .long("format")
+        .takes_value(true)
+        .possible_values(&Format::NAMES)
+        .default_value("table")
+        .help("Output format")
+}
+
+fn number_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("number").value_name("NUMBER").required(true)
+}
+
+fn trainer_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("trainer").value_name("TRAINER").required(true)
+}
+
+fn mark_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("mark")
+        .value_name("MARK")
+        .possible_values(&["seen", "caught", "shiny"])
+        .default_value("caught")
+}
+
+fn form_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("form")
+        .long("form")
+        .takes_value(true)
+        .help("A regional form or alternate forme, such as alola, the base form if unset")
+}
+
+/// The command line of the `pokedex` binary: global settings and every subcommand.
+pub fn app<'a, 'b>() -> App<'a, 'b> {
+    let mut app = App::new(crate_name!())
+        .version(crate_version!())
+        .author(crate_authors!())
+        .arg(
+            Arg::with_name("config")
+                .long("config")
+                .value_name("FILE")
+                .help("TOML configuration file, pokedex.toml by default"),
+        )
+        .arg(
+            Arg::with_name("backend")
+                .long("backend")
+                .takes_value(true)
+                .possible_values(repositories::BACKENDS),
+        )
+        .arg(Arg::with_name("listen").long("listen").value_name("ADDRESS"))
+        .arg(
+            Arg::with_name("log_level")
+                .long("log-level")
+                .takes_value(true)
+                .possible_values(&["off", "error", "warn", "info", "debug"]),
+        )
+        .arg(
+            Arg::with_name("timeout_secs")
+                .long("timeout-secs")
+                .value_name("SECONDS"),
+        )
+        .arg(
+            Arg::with_name("national_dex_limit")
+                .long("national-dex-limit")
+                .value_name("NUMBER")
+                .help("Highest national number accepted, 1025 by default"),
+        )
+        .subcommands(subcommands());
+    if cfg!(feature = "sqlite") {
+        app = app.arg(Arg::with_name("sqlite").long("sqlite").value_name("PATH"));
+    }
+    if cfg!(feature = "airtable") {
+        app = app
+            .arg(
+                Arg::with_name("airtable")
+                    .long("airtable")
+                    .value_names(&["API_KEY", "WORKSPACE_ID"])
+                    .help("Prefer --airtable-api-key-file, the key is visible to other users here"),
+            )
+            .arg(
+                Arg::with_name("airtable_api_key_file")
+                    .long("airtable-api-key-file")
+                    .value_name("FILE"),
+            )
+            .arg(
+                Arg::with_name("airtable_workspace_id")
+                    .long("airtable-workspace-id")
+                    .value_name("WORKSPACE_ID"),
+            );
+    }
+    app
+}
+
+/// The settings given as flags, the last layer of the configuration.
+pub fn flags(matches: &ArgMatches) -> Result<Layer, ConfigError> {
+    let timeout_secs = match matches.value_of("timeout_secs") {
+        Some(value) => Some(value.parse().map_err(|_| ConfigError::Invalid {
+            key: "--timeout-secs",
+            value: value.to_owned(),
+        })?),
+        None => None,
+    };
+    let national_dex_limit = match matches.value_of("national_dex_limit") {
+        Some(value) => Some(value.parse().map_err(|_| ConfigError::Invalid {
+            key: "--national-dex-limit",
+            value: value.to_owned(),
+        })?),
+        None => None,
+    };
+    let airtable = matches
+        .values_of("airtable")
+        .map(|values| values.map(str::to_owned).collect::<Vec<String>>());
+    let (backend, api_key, workspace_id) = match (matches.value_of("sqlite"), airtable) {
+        (Some(_), _) => (Some("sqlite"), None, None),
+        (None, Some(values)) => (Some("airtable"), values.first().cloned(), values.get(1).cloned()),
+        (None, None) => (matches.value_of("backend"), None, None),
+    };
+    Ok(Layer {
+        backend: backend.map(str::to_owned),
+        listen: matches.value_of("listen").map(str::to_owned),
+        log_level: matches.value_of("log_level").map(str::to_owned),
+        timeout_secs,
+        national_dex_limit,
+        sqlite: SqliteLayer {
+            path: matches.value_of("sqlite").map(str::to_owned),
+        },
+        airtable: AirtableLayer {
+            api_key,
+            api_key_file: matches.value_of("airtable_api_key_file").map(str::to_owned),
+            workspace_id: workspace_id
+                .or_else(|| matches.value_of("airtable_workspace_id").map(str::to_owned)),
+        },
+    })
+}
+
+/// Non-interactive subcommands, meant to be used from scripts.
+pub fn subcommands<'a, 'b>() -> Vec<App<'a, 'b>> {
+    vec![
+        SubCommand::with_name("interactive").about("Runs the interactive menu"),
+        SubCommand::with_name("list")
+            .about("Lists the Pokemons")
+            .arg(Arg::with_name("type").long("type").takes_value(true))
+            .arg(Arg::with_name("name").long("name").takes_value(true))
+            .arg(Arg::with_name("min_number").long("min-number").takes_value(true))
+            .arg(Arg::with_name("max_number").long("max-number").takes_value(true))
+            .arg(Arg::with_name("generation").long("generation").takes_value(true))
+            .arg(
+                Arg::with_name("sort")
+                    .long("sort")
+                    .takes_value(true)
+                    .possible_values(&["number", "name"]),
+            )
+            .arg(
+                Arg::with_name("direction")
+                    .long("direction")
+                    .takes_value(true)
+                    .possible_values(&["asc", "desc"]),
+            )
+            .arg(Arg::with_name("limit").long("limit").takes_value(true))
+            .arg(Arg::with_name("offset").long("offset").takes_value(true))
+            .arg(format_arg()),
+        SubCommand::with_name("get")
+            .about("Fetches a Pokemon")
+            .arg(number_arg())
+            .arg(form_arg())
+            .arg(format_arg()),
+        SubCommand::with_name("matchups")
+            .about("Fetches the weaknesses, resistances and immunities of a Pokemon")
+            .arg(number_arg())
+            .arg(form_arg())
+            .arg(format_arg()),
+        SubCommand::with_name("dex")
+            .about("Browses a regional dex, or fetches the Pokemon at one of its indices")
+            .arg(Arg::with_name("region").value_name("REGION").required(true))
+            .arg(Arg::with_name("index").value_name("INDEX"))
+            .arg(format_arg()),
+        SubCommand::with_name("create")
+            .about("Creates a Pokemon")
+            .arg(
+                Arg::with_name("number")
+                    .long("number")
+                    .takes_value(true)
+                    .required(true),
+            )
+            .arg(form_arg())
+            .arg(Arg::with_name("name").long("name").takes_value(true).required(true))
+            .arg(
+                Arg::with_name("type")
+                    .long("type")
+                    .takes_value(true)
+                    .multiple(true)
+                    .number_of_values(1)
+                    .required(true),
+            )
+            .arg(
+                Arg::with_name("stats")
+                    .long("stats")
+                    .value_names(&["HP", "ATK", "DEF", "SPA", "SPD", "SPE"])
+                    .help("Base stats, 1 for each by default"),
+            )
+            .arg(format_arg()),
+        SubCommand::with_name("delete")
+            .about("Deletes a Pokemon, and every other form with the base form")
+            .arg(number_arg())
+            .arg(form_arg())
+            .arg(format_arg()),
+        SubCommand::with_name("import")
+            .about("Imports Pokemons from a CSV, JSON or JSON Lines file")
+            .arg(Arg::with_name("file").value_name("FILE").required(true))
+            .arg(
+                Arg::with_name("input_format")
+                    .long("input-format")
+                    .takes_value(true)
+                    .possible_values(&["csv", "json", "jsonl"])
+                    .help("Format of the file, guessed from its extension by default"),
+            )
+            .arg(
+                Arg::with_name("mode")
+                    .long("mode")
+                    .takes_value(true)
+                    .possible_values(&["fail", "skip", "overwrite"])
+                    .default_value("fail")
+                    .help("What to do with Pokemons that already exist"),
+            )
+            .arg(
+                Arg::with_name("dry_run")
+                    .long("dry-run")
+                    .help("Validates the file without writing anything"),
+            )
+            .arg(format_arg()),
+        SubCommand::with_name("export")
+            .about("Exports every Pokemon to a CSV, JSON or JSON Lines file")
+            .arg(
+                Arg::with_name("format")
+                    .long("format")
+                    .takes_value(true)
+                    .possible_values(&["csv", "json", "jsonl"])
+                    .default_value("json"),
+            )
+            .arg(
+                Arg::with_name("output")
+                    .long("output")
+                    .value_name("FILE")
+                    .help("Writes to FILE instead of stdout"),
+            ),
+        SubCommand::with_name("migrate")
+            .about("Copies every Pokemon from one backend to another")
+            .arg(
+                Arg::with_name("from")
+                    .long("from")
+                    .value_name("BACKEND")
+                    .required(true)
+                    .help("memory, sqlite:PATH or airtable:API_KEY,WORKSPACE_ID"),
+            )
+            .arg(
+                Arg::with_name("to")
+                    .long("to")
+                    .value_name("BACKEND")
+                    .required(true)
+                    .help("memory, sqlite:PATH or airtable:API_KEY,WORKSPACE_ID"),
+            )
+            .arg(
+                Arg::with_name("mode")
+                    .long("mode")
+                    .takes_value(true)
+                    .possible_values(&["fail", "skip", "overwrite"])
+                    .default_value("fail")
+                    .help("What to do with Pokemons that already exist in the target"),
+            )
+            .arg(
+                Arg::with_name("verify")
+                    .long("verify")
+                    .help("Compares both backends once the migration is over"),
+            )
+            .arg(format_arg()),
+        SubCommand::with_name("trainer")
+            .about("Manages trainers and what they saw and caught")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(
+                SubCommand::with_name("create")
+                    .about("Creates a trainer")
+                    .arg(trainer_arg())
+                    .arg(Arg::with_name("name").long("name").takes_value(true).required(true))
+                    .arg(format_arg()),
+            )
+            .subcommand(
+                SubCommand::with_name("dex")
+                    .about("Lists every Pokemon the trainer has seen")
+                    .arg(trainer_arg())
+                    .arg(format_arg()),
+            )
+            .subcommand(
+                SubCommand::with_name("completion")
+                    .about("Prints the share of the dex caught, overall and per generation")
+                    .arg(trainer_arg())
+                    .arg(format_arg()),
+            )
+            .subcommand(
+                SubCommand::with_name("mark")
+                    .about("Marks a Pokemon as seen, caught or shiny, along with what it implies")
+                    .arg(trainer_arg())
+                    .arg(number_arg())
+                    .arg(mark_arg())
+                    .arg(format_arg()),
+            )
+            .subcommand(
+                SubCommand::with_name("unmark")
+                    .about("Clears a mark, along with the marks implying it")
+                    .arg(trainer_arg())
+                    .arg(number_arg())
+                    .arg(mark_arg())
+                    .arg(format_arg()),
+            ),
+        SubCommand::with_name("config")
+            .about("Inspects the configuration")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(
+                SubCommand::with_name("check")
+                    .about("Prints the effective configuration, with secrets redacted"),
+            ),
+    ]
+}
+
+/// Runs the `config` subcommand. An invalid configuration never gets this far.
+pub fn config(config: &Config, matches: &ArgMatches) -> ExitCode {
+    match matches.subcommand() {
+        ("check", _) => {
+            print!("{}", config.to_redacted_toml());
+            ExitCode::Success
+        }
+        _ => ExitCode::BadRequest,
+    }
+}
+
+/// Runs the `migrate` subcommand. It opens its own `--from` and `--to` backends,
+/// so it works even when the configured one can't be opened.
+pub fn migrate(config: &Config, matches: &ArgMatches) -> ExitCode {
+    exec_migrate(config, matches).unwrap_or_else(|code| code)
+}
+
+fn value<T: FromStr>(matches: &ArgMatches, name: &str) -> Result<Option<T>, ExitCode> {
+    match matches.value_of(name).map(str::parse::<T>) {
+        Some(Ok(value)) => Ok(Some(value)),
+        Some(Err(_)) => {
+            eprintln!("The value of {name} is invalid");
+            Err(ExitCode::BadRequest)
+        }
+        None => Ok(None),
+    }
+}
+
+fn values<T: FromStr>(matches: &ArgMatches, name: &str) -> Result<Vec<T>, ExitCode> {
+    matches
+        .values_of(name)
+        .into_iter()
+        .flatten()
+        .map(|value| {
+            value.parse::<T>().map_err(|_| {
+                eprintln!("The value of {name} is invalid");
+                ExitCode::BadRequest
+            })
+        })
+        .collect()
+}
+
+/// Runs the scripted subcommand `name` and returns how the process should exit.
+pub fn exec(repo: Arc<dyn Repository>, config: &Config, name: &str, matches: &ArgMatches) -> ExitCode {
+    let res = match name {
+        "list" => exec_list(repo, matches),
+        "get" => exec_get(repo, matches),
+        "matchups" => exec_matchups(repo, matches),
+        "dex" => exec_dex(repo, matches),
+        "create" => exec_create(repo, config, matches),
+        "delete" => exec_delete(repo, matches),
+        "import" => exec_import(repo, config, matches),
+        "export" => exec_export(repo, matches),
+        "trainer" => exec_trainer(repo, matches),
+        _ => Err(ExitCode::BadRequest),
+    };
+    res.unwrap_or_else(|code| code)
+}
+
+fn format(matches: &ArgMatches) -> Format {
+    matches
+        .value_of("format")
+        .and_then(|format| Format::try_from(format).ok())
+        .unwrap_or(Format::Table)
+}
+
+fn exec_list(repo: Arc<dyn Repository>, matches: &ArgMatches) -> Result<ExitCode, ExitCode> {
+    let req = fetch_all::Request {
+        tipe: value(matches, "type")?,
+        name: value(matches, "name")?,
+        min_number: value(matches, "min_number")?,
+        max_number: value(matches, "max_number")?,
+        generation: value(matches, "generation")?,
+        sort: value(matches, "sort")?,
+        direction: value(matches, "direction")?,
+        limit: value(matches, "limit")?,
+        offset: value(matches, "offset")?,
+    };
+    Ok(fetch_all_pokemons::exec(repo, req, format(matches)))
+}
+
+fn exec_get(repo: Arc<dyn Repository>, matches: &ArgMatches) -> Result<ExitCode, ExitCode> {
+    let number = value(matches, "number")?.ok_or(ExitCode::BadRequest)?;
+    Ok(fetch_pokemon::exec(repo, number, value(matches, "form")?, format(matches)))
+}
+
+fn exec_matchups(repo: Arc<dyn Repository>, matches: &ArgMatches) -> Result<ExitCode, ExitCode> {
+    let number = value(matches, "number")?.ok_or(ExitCode::BadRequest)?;
+    Ok(fetch_pokemon_matchups::exec(repo, number, value(matches, "form")?, format(matches)))
+}
+
+fn exec_dex(repo: Arc<dyn Repository>, matches: &ArgMatches) -> Result<ExitCode, ExitCode> {
+    let region = value(matches, "region")?.ok_or(ExitCode::BadRequest)?;
+    Ok(fetch_regional_dex::exec(repo, region, value(matches, "index")?, format(matches)))
+}
+
+fn exec_create(repo: Arc<dyn Repository>, config: &Config, matches: &ArgMatches) -> Result<ExitCode, ExitCode> {
+    let stats = match values::<u16>(matches, "stats")? {
+        stats if stats.is_empty() => DEFAULT_STATS,
+        stats => stats.try_into().map_err(|_| ExitCode::BadRequest)?,
+    };
+    let req = create::Request {
+        number: value(matches, "number")?.ok_or(ExitCode::BadRequest)?,
+        form: value(matches, "form")?,
+        name: value(matches, "name")?.ok_or(ExitCode::BadRequest)?,
+        types: values(matches, "type")?,
+        stats,
+        national_dex_limit: config.national_dex_limit,
+    };
+    Ok(create_pokemon::exec(repo, req, format(matches)))
+}
+
+fn exec_delete(repo: Arc<dyn Repository>, matches: &ArgMatches) -> Result<ExitCode, ExitCode> {
+    let number = value(matches, "number")?.ok_or(ExitCode::BadRequest)?;
+    Ok(delete_pokemon::exec(repo, number, value(matches, "form")?, format(matches)))
+}
+
+fn exec_import(repo: Arc<dyn Repository>, config: &Config, matches: &ArgMatches) -> Result<ExitCode, ExitCode> {
+    let path = matches.value_of("file").ok_or(ExitCode::BadRequest)?;
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        eprintln!("Could not read {path}: {e}");
+        ExitCode::BadRequest
+    })?;
+    let extension = std::path::Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| match extension {
+            "ndjson" => "jsonl",
+            extension => extension,
+        });
+    let input_format = match matches.value_of("input_format").or(extension) {
+        Some(input_format) => input_format.to_owned(),
+        None => {
+            eprintln!("The format of {path} can't be guessed, use --input-format");
+            return Err(ExitCode::BadRequest);
+        }
+    };
+    let req = import::Request {
+        format: input_format,
+        content,
+        mode: value(matches, "mode")?,
+        dry_run: matches.is_present("dry_run"),
+        national_dex_limit: config.national_dex_limit,
+    };
+    Ok(import_pokemons::exec(repo, req, format(matches)))
+}
+
+fn exec_export(repo: Arc<dyn Repository>, matches: &ArgMatches) -> Result<ExitCode, ExitCode> {
+    let req = export::Request {
+        format: value(matches, "format")?.ok_or(ExitCode::BadRequest)?,
+    };
+    let code = match matches.value_of("output") {
+        Some(path) => {
+            let mut file = std::fs::File::create(path).map_err(|e| {
+                eprintln!("Could not create {path}: {e}");
+                ExitCode::Unknown
+            })?;
+            export_pokemons::exec(repo, req, &mut file)
+        }
+        None => export_pokemons::exec(repo, req, &mut std::io::stdout().lock()),
+    };
+    Ok(code)
+}
+
+fn open_repo(
+    config: &Config,
+    matches: &ArgMatches,
+    name: &str,
+) -> Result<Arc<dyn Repository>, ExitCode> {
+    let spec = matches.value_of(name).ok_or(ExitCode::BadRequest)?;
+    repositories::open(spec, config.timeout()).map_err(|e| {
+        eprintln!("{e}");
+        match e {
+            repositories::OpenError::InvalidSpec(_) | repositories::OpenError::Disabled(_) => {
+                ExitCode::BadRequest
+            }
+            repositories::OpenError::Storage(_) => ExitCode::Unknown,
+        }
+    })
+}
+
+fn exec_migrate(config: &Config, matches: &ArgMatches) -> Result<ExitCode, ExitCode> {
+    let from = open_repo(config, matches, "from")?;
+    let to = open_repo(config, matches, "to")?;
+    let req = migrate::Request {
+        mode: value(matches, "mode")?,
+        verify: matches.is_present("verify"),
+    };
+    Ok(migrate_pokemons::exec(from, to, req, format(matches)))
+}
+
+fn exec_trainer(repo: Arc<dyn Repository>, matches: &ArgMatches) -> Result<ExitCode, ExitCode> {
+    let (name, matches) = match matches.subcommand() {
+        (name, Some(matches)) => (name, matches),
+        _ => return Err(ExitCode::BadRequest),
+    };
+    let trainer = value(matches, "trainer")?.ok_or(ExitCode::BadRequest)?;
+    let code = match name {
+        "create" => {
+            let req = new_trainer::Request {
+                id: trainer,
+                name: value(matches, "name")?.ok_or(ExitCode::BadRequest)?,
+            };
+            create_trainer::exec(repo, req, format(matches))
+        }
+        "dex" => fetch_trainer_dex::exec(repo, trainer, format(matches)),
+        "completion" => fetch_dex_completion::exec(repo, trainer, format(matches)),
+        "mark" => {
+            let req = mark::Request {
+                trainer,
+                number: value(matches, "number")?.ok_or(ExitCode::BadRequest)?,
+                mark: value(matches, "mark")?.ok_or(ExitCode::BadRequest)?,
+            };
+            mark_dex_entry::exec(repo, req, format(matches))
+        }
+        "unmark" => {
+            let req = unmark::Request {
+                trainer,
+                number: value(matches, "number")?.ok_or(ExitCode::BadRequest)?,
+                mark: value(matches, "mark")?.ok_or(ExitCode::BadRequest)?,
+            };
+            unmark_dex_entry::exec(repo, req, format(matches))
+        }
+        _ => return Err(ExitCode::BadRequest),
+    };
+    Ok(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{PokemonKey, PokemonNumber, PokemonStats};
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_migrate_without_the_configured_backend() {
+        let flags = Layer {
+            sqlite: SqliteLayer {
+                path: Some(String::from("/nonexistent/pokedex.db")),
+            },
+            ..Layer::default()
+        };
+        let config = crate::config::load(None, &|_| None, flags).unwrap();
+        let args = "pokedex migrate --from memory --to memory";
+        let matches = app().get_matches_from_safe(args.split(' ')).unwrap();
+        let (_, sub_matches) = matches.subcommand();
+
+        let code = migrate(&config, sub_matches.unwrap());
+
+        assert!(config.backend.open(config.timeout()).is_err());
+        assert_eq!(code, ExitCode::Success);
+    }
+
+    #[test]
+    fn it_should_create_a_pokemon_without_stats() {
+        let repo: Arc<dyn Repository> = Arc::new(InMemoryRepository::new());
+        let config = crate::config::load(None, &|_| None, Layer::default()).unwrap();
+        let args = "pokedex create --number 25 --name Pikachu --type Electric";
+        let matches = app().get_matches_from_safe(args.split(' ')).unwrap();
+        let (name, sub_matches) = matches.subcommand();
+
+        let code = exec(repo.clone(), &config, name, sub_matches.unwrap());
+
+        assert_eq!(code, ExitCode::Success);
+        let pikachu = repo.fetch_one(PokemonKey::from(PokemonNumber::pikachu())).unwrap();
+        assert_eq!(pikachu.stats, PokemonStats::try_from(DEFAULT_STATS).unwrap());
+    }
+}
@@ -0,0 +1,600 @@
+use console::{measure_text_width, style, Color};
+use serde::Serialize;
+
+/// How command results are written to stdout.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Table,
+    Json,
+    JsonLines,
+    Csv,
+    Yaml,
+}
+
+impl Format {
+    pub const NAMES: [&'static str; 5] = ["table", "json", "jsonl", "csv", "yaml"];
+}
+
+impl TryFrom<&str> for Format {
+    type Error = ();
+
+    fn try_from(format: &str) -> Result<Self, Self::Error> {
+        match format {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            "jsonl" => Ok(Format::JsonLines),
+            "csv" => Ok(Format::Csv),
+            "yaml" => Ok(Format::Yaml),
+            _ => Err(()),
+        }
+    }
+}
+
+pub enum Cell {
+    Text(String),
+    Number(u64),
+    /// Pokemon types, colored in tables.
+    Types(Vec<String>),
+}
+
+impl Cell {
+    fn plain(&self) -> String {
+        match self {
+            Cell::Text(text) => text.clone(),
+            Cell::Number(number) => number.to_string(),
+            Cell::Types(types) => types.join("/"),
+        }
+    }
+
+    fn styled(&self) -> String {
+        match self {
+            Cell::Types(types) => types
+                .iter()
+                .map(|tipe| style(tipe).fg(type_color(tipe)).to_string())
+                .collect::<Vec<String>>()
+                .join("/"),
+            cell => cell.plain(),
+        }
+    }
+}
+
+/// A record that can be laid out in columns, for tables and CSV.
+/// JSON, JSON Lines and YAML use its `Serialize` implementation instead.
+pub trait Tabular: Serialize {
+    fn headers() -> Vec<&'static str>;
+    fn cells(&self) -> Vec<Cell>;
+}
+
+fn type_color(tipe: &str) -> Color {
+    match tipe {
+        "Normal" => Color::Color256(250),
+        "Fire" => Color::Color256(202),
+        "Water" => Color::Color256(33),
+        "Electric" => Color::Color256(220),
+        "Grass" => Color::Color256(40),
+        "Ice" => Color::Color256(87),
+        "Fighting" => Color::Color256(160),
+        "Poison" => Color::Color256(128),
+        "Ground" => Color::Color256(178),
+        "Flying" => Color::Color256(111),
+        "Psychic" => Color::Color256(205),
+        "Bug" => Color::Color256(106),
+        "Rock" => Color::Color256(137),
+        "Ghost" => Color::Color256(61),
+        "Dragon" => Color::Color256(63),
+        "Dark" => Color::Color256(95),
+        "Steel" => Color::Color256(146),
+        "Fairy" => Color::Color256(218),
+        _ => Color::White,
+    }
+}
+
+fn table<T: Tabular>(values: &[T]) -> String {
+    let headers = T::headers();
+    let rows = values.iter().map(T::cells).collect::<Vec<Vec<Cell>>>();
+    let mut widths = headers
+        .iter()
+        .map(|header| header.len())
+        .collect::<Vec<usize>>();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(measure_text_width(&cell.plain()));
+        }
+    }
+
+    let mut lines = vec![headers
+        .iter()
+        .zip(&widths)
+        .map(|(header, width)| {
+            let padding = " ".repeat(width - header.len());
+            format!("{}{padding}", style(header.to_uppercase()).bold())
+        })
+        .collect::<Vec<String>>()];
+    for row in rows {
+        lines.push(
+            row.iter()
+                .zip(&widths)
+                .map(|(cell, width)| {
+                    let padding = " ".repeat(width - measure_text_width(&cell.plain()));
+                    match cell {
+                        Cell::Number(_) => format!("{padding}{}", cell.styled()),
+                        _ => format!("{}{padding}", cell.styled()),
+                    }
+                })
+                .collect(),
+        );
+    }
+    lines
+        .into_iter()
+        .map(|line| format!("{}\n", line.join("  ").trim_end()))
+        .collect()
+}
+
+fn csv<T: Tabular>(values: &[T]) -> Result<String, String> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+    writer.write_record(T::headers()).map_err(|e| e.to_string())?;
+    for value in values {
+        writer
+            .write_record(value.cells().iter().map(Cell::plain))
+            .map_err(|e| e.to_string())?;
+    }
+    let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
+
+/// Renders a list of records. An empty list still renders headers, `[]` or nothing,
+/// depending on the format.
+pub fn render<T: Tabular>(format: Format, values: &[T]) -> Result<String, String> {
+    match format {
+        Format::Table => Ok(table(values)),
+        Format::Csv => csv(values),
+        Format::Json => serde_json::to_string_pretty(values)
+            .map(|json| json + "\n")
+            .map_err(|e| e.to_string()),
+        Format::JsonLines => values
+            .iter()
+            .map(|value| serde_json::to_string(value).map(|json| json + "\n"))
+            .collect::<Result<String, _>>()
+            .map_err(|e| e.to_string()),
+        Format::Yaml => serde_yaml::to_string(values).map_err(|e| e.to_string()),
+    }
+}
+
+/// Renders a single record. JSON and YAML render it as an object rather than a list.
+pub fn render_one<T: Tabular>(format: Format, value: &T) -> Result<String, String> {
+    match format {
+        Format::Json => serde_json::to_string_pretty(value)
+            .map(|json| json + "\n")
+            .map_err(|e| e.to_string()),
+        Format::Yaml => serde_yaml::to_string(value).map_err(|e| e.to_string()),
+        format => render(format, std::slice::from_ref(value)),
+    }
+}
+
+pub fn print<T: Tabular>(format: Format, values: &[T]) {
+    match render(format, values) {
+        Ok(output) => print!("{output}"),
+        Err(e) => eprintln!("An error occurred while rendering the output: {e}"),
+    }
+}
+
+pub fn print_one<T: Tabular>(format: Format, value: &T) {
+    match render_one(format, value) {
+        Ok(output) => print!("{output}"),
+        Err(e) => eprintln!("An error occurred while rendering the output: {e}"),
+    }
+}
+
+#[derive(Serialize)]
+pub struct Stats {
+    hp: u16,
+    attack: u16,
+    defense: u16,
+    special_attack: u16,
+    special_defense: u16,
+    speed: u16,
+    total: u16,
+}
+
+#[derive(Serialize)]
+pub struct Pokemon {
+    number: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    form: Option<String>,
+    name: String,
+    types: Vec<String>,
+    stats: Stats,
+}
+
+impl Pokemon {
+    pub fn new(number: u16, name: String, types: Vec<String>, stats: [u16; 6], total: u16) -> Self {
+        let [hp, attack, defense, special_attack, special_defense, speed] = stats;
+        Self {
+            number,
+            form: None,
+            name,
+            types,
+            stats: Stats {
+                hp,
+                attack,
+                defense,
+                special_attack,
+                special_defense,
+                speed,
+                total,
+            },
+        }
+    }
+}
+
+impl Pokemon {
+    pub fn with_form(self, form: Option<String>) -> Self {
+        Self { form, ..self }
+    }
+}
+
+impl Tabular for Pokemon {
+    fn headers() -> Vec<&'static str> {
+        vec![
+            "number",
+            "name",
+            "types",
+            "hp",
+            "attack",
+            "defense",
+            "special_attack",
+            "special_defense",
+            "speed",
+            "total",
+        ]
+    }
+
+    fn cells(&self) -> Vec<Cell> {
+        let stats = &self.stats;
+        vec![
+            Cell::Number(self.number.into()),
+            Cell::Text(match &self.form {
+                Some(form) => format!("{} ({form})", self.name),
+                None => self.name.clone(),
+            }),
+            Cell::Types(self.types.clone()),
+            Cell::Number(stats.hp.into()),
+            Cell::Number(stats.attack.into()),
+            Cell::Number(stats.defense.into()),
+            Cell::Number(stats.special_attack.into()),
+            Cell::Number(stats.special_defense.into()),
+            Cell::Number(stats.speed.into()),
+            Cell::Number(stats.total.into()),
+        ]
+    }
+}
+
+#[derive(Serialize)]
+pub struct Matchup {
+    #[serde(rename = "type")]
+    tipe: String,
+    multiplier: f32,
+}
+
+impl Matchup {
+    pub fn new(tipe: String, multiplier: f32) -> Self {
+        Self { tipe, multiplier }
+    }
+}
+
+/// The attacking types a Pokemon is weak to, resists or is immune to.
+#[derive(Serialize)]
+pub struct Matchups {
+    number: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    form: Option<String>,
+    name: String,
+    types: Vec<String>,
+    weaknesses: Vec<Matchup>,
+    resistances: Vec<Matchup>,
+    immunities: Vec<Matchup>,
+}
+
+impl Matchups {
+    pub fn new(
+        number: u16,
+        name: String,
+        types: Vec<String>,
+        [weaknesses, resistances, immunities]: [Vec<Matchup>; 3],
+    ) -> Self {
+        Self {
+            number,
+            form: None,
+            name,
+            types,
+            weaknesses,
+            resistances,
+            immunities,
+        }
+    }
+
+    pub fn with_form(self, form: Option<String>) -> Self {
+        Self { form, ..self }
+    }
+}
+
+/// `Ground x2, Rock x4`, or `-` when there is none.
+fn multipliers(matchups: &[Matchup]) -> Cell {
+    match matchups.is_empty() {
+        true => Cell::Text(String::from("-")),
+        false => Cell::Text(
+            matchups
+                .iter()
+                .map(|m| format!("{} x{}", m.tipe, m.multiplier))
+                .collect::<Vec<String>>()
+                .join(", "),
+        ),
+    }
+}
+
+impl Tabular for Matchups {
+    fn headers() -> Vec<&'static str> {
+        vec!["number", "name", "types", "weaknesses", "resistances", "immunities"]
+    }
+
+    fn cells(&self) -> Vec<Cell> {
+        vec![
+            Cell::Number(self.number.into()),
+            Cell::Text(match &self.form {
+                Some(form) => format!("{} ({form})", self.name),
+                None => self.name.clone(),
+            }),
+            Cell::Types(self.types.clone()),
+            multipliers(&self.weaknesses),
+            multipliers(&self.resistances),
+            multipliers(&self.immunities),
+        ]
+    }
+}
+
+/// A Pokemon of a regional dex, with its index in that dex.
+#[derive(Serialize)]
+pub struct DexEntry {
+    index: u16,
+    #[serde(flatten)]
+    pokemon: Pokemon,
+}
+
+impl DexEntry {
+    pub fn new(index: u16, pokemon: Pokemon) -> Self {
+        Self { index, pokemon }
+    }
+}
+
+impl Tabular for DexEntry {
+    fn headers() -> Vec<&'static str> {
+        let mut headers = vec!["index"];
+        headers.extend(Pokemon::headers());
+        headers
+    }
+
+    fn cells(&self) -> Vec<Cell> {
+        let mut cells = vec![Cell::Number(self.index.into())];
+        cells.extend(self.pokemon.cells());
+        cells
+    }
+}
+
+#[derive(Serialize)]
+pub struct Trainer {
+    id: String,
+    name: String,
+}
+
+impl Trainer {
+    pub fn new(id: String, name: String) -> Self {
+        Self { id, name }
+    }
+}
+
+impl Tabular for Trainer {
+    fn headers() -> Vec<&'static str> {
+        vec!["id", "name"]
+    }
+
+    fn cells(&self) -> Vec<Cell> {
+        vec![Cell::Text(self.id.clone()), Cell::Text(self.name.clone())]
+    }
+}
+
+/// What a trainer recorded about one Pokemon.
+#[derive(Serialize)]
+pub struct TrainerDexEntry {
+    number: u16,
+    name: String,
+    seen: bool,
+    caught: bool,
+    shiny: bool,
+}
+
+impl TrainerDexEntry {
+    pub fn new(number: u16, name: String, [seen, caught, shiny]: [bool; 3]) -> Self {
+        Self {
+            number,
+            name,
+            seen,
+            caught,
+            shiny,
+        }
+    }
+}
+
+fn flag(flag: bool) -> Cell {
+    Cell::Text(String::from(if flag { "yes" } else { "no" }))
+}
+
+impl Tabular for TrainerDexEntry {
+    fn headers() -> Vec<&'static str> {
+        vec!["number", "name", "seen", "caught", "shiny"]
+    }
+
+    fn cells(&self) -> Vec<Cell> {
+        vec![
+            Cell::Number(self.number.into()),
+            Cell::Text(self.name.clone()),
+            flag(self.seen),
+            flag(self.caught),
+            flag(self.shiny),
+        ]
+    }
+}
+
+/// How much of the dex a trainer caught, overall when `generation` is unset.
+#[derive(Serialize)]
+pub struct Completion {
+    generation: Option<u8>,
+    total: usize,
+    seen: usize,
+    caught: usize,
+    shiny: usize,
+    percentage: f64,
+}
+
+impl Completion {
+    pub fn new(generation: Option<u8>, counts: [usize; 4], percentage: f64) -> Self {
+        let [total, seen, caught, shiny] = counts;
+        Self {
+            generation,
+            total,
+            seen,
+            caught,
+            shiny,
+            percentage,
+        }
+    }
+}
+
+impl Tabular for Completion {
+    fn headers() -> Vec<&'static str> {
+        vec!["generation", "total", "seen", "caught", "shiny", "percentage"]
+    }
+
+    fn cells(&self) -> Vec<Cell> {
+        vec![
+            Cell::Text(match self.generation {
+                Some(generation) => generation.to_string(),
+                None => String::from("all"),
+            }),
+            Cell::Number(self.total as u64),
+            Cell::Number(self.seen as u64),
+            Cell::Number(self.caught as u64),
+            Cell::Number(self.shiny as u64),
+            Cell::Text(format!("{:.1}%", self.percentage)),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pokemons() -> Vec<Pokemon> {
+        vec![
+            Pokemon::new(
+                25,
+                String::from("Pikachu"),
+                vec![String::from("Electric")],
+                [35, 55, 40, 50, 50, 90],
+                320,
+            ),
+            Pokemon::new(
+                37,
+                String::from("Vulpix, the fox"),
+                vec![String::from("Fire")],
+                [38, 41, 40, 50, 65, 65],
+                299,
+            ),
+        ]
+    }
+
+    #[test]
+    fn it_should_align_table_columns() {
+        let output = console::strip_ansi_codes(&render(Format::Table, &pokemons()).unwrap())
+            .to_string();
+
+        let lines = output.lines().collect::<Vec<&str>>();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("NUMBER  NAME             TYPES     HP"));
+        assert!(lines[1].starts_with("    25  Pikachu          Electric  35"));
+        assert!(lines[2].starts_with("    37  Vulpix, the fox  Fire      38"));
+    }
+
+    #[test]
+    fn it_should_quote_csv_fields() {
+        let output = render(Format::Csv, &pokemons()).unwrap();
+
+        let lines = output.lines().collect::<Vec<&str>>();
+        assert_eq!(
+            lines[0],
+            "number,name,types,hp,attack,defense,special_attack,special_defense,speed,total"
+        );
+        assert_eq!(lines[2], "37,\"Vulpix, the fox\",Fire,38,41,40,50,65,65,299");
+    }
+
+    #[test]
+    fn it_should_write_one_json_document_per_line() {
+        let output = render(Format::JsonLines, &pokemons()).unwrap();
+
+        let lines = output.lines().collect::<Vec<&str>>();
+        assert_eq!(lines.len(), 2);
+        let value: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(value["stats"]["total"], 320);
+    }
+
+    #[test]
+    fn it_should_render_a_single_record_as_an_object() {
+        let output = render_one(Format::Yaml, &pokemons().remove(0)).unwrap();
+
+        assert!(output.starts_with("number: 25\nname: Pikachu\n"));
+    }
+
+    #[test]
+    fn it_should_render_matchups_as_one_row() {
+        let matchups = Matchups::new(
+            37,
+            String::from("Vulpix"),
+            vec![String::from("Ice")],
+            [
+                vec![
+                    Matchup::new(String::from("Fire"), 2.0),
+                    Matchup::new(String::from("Rock"), 2.0),
+                ],
+                vec![Matchup::new(String::from("Ice"), 0.5)],
+                vec![],
+            ],
+        )
+        .with_form(Some(String::from("alola")));
+
+        let csv = render_one(Format::Csv, &matchups).unwrap();
+        let json = render_one(Format::Json, &matchups).unwrap();
+
+        let lines = csv.lines().collect::<Vec<&str>>();
+        assert_eq!(lines[0], "number,name,types,weaknesses,resistances,immunities");
+        assert_eq!(lines[1], "37,Vulpix (alola),Ice,\"Fire x2, Rock x2\",Ice x0.5,-");
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            value["weaknesses"][0],
+            serde_json::json!({ "type": "Fire", "multiplier": 2.0 })
+        );
+        assert_eq!(value["immunities"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn it_should_render_completion_percentages() {
+        let rows = vec![
+            Completion::new(None, [3, 2, 1, 0], 33.3),
+            Completion::new(Some(1), [2, 2, 1, 0], 50.0),
+        ];
+
+        let output = render(Format::Csv, &rows).unwrap();
+
+        let lines = output.lines().collect::<Vec<&str>>();
+        assert_eq!(lines[1], "all,3,2,1,0,33.3%");
+        assert_eq!(lines[2], "1,2,2,1,0,50.0%");
+    }
+}
@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use crate::domain::unmark_dex_entry;
+use crate::repositories::pokemon::Repository;
+
+use super::exit_code::ExitCode;
+use super::fetch_trainer_dex;
+use super::output::{self, Format};
+
+pub fn exec(repo: Arc<dyn Repository>, req: unmark_dex_entry::Request, format: Format) -> ExitCode {
+    match unmark_dex_entry::execute(repo, req) {
+        Ok(res) => {
+            output::print_one(format, &fetch_trainer_dex::entry(res));
+            ExitCode::Success
+        }
+        Err(unmark_dex_entry::Error::BadRequest(errors)) => {
+            eprintln!("The request is invalid");
+            for error in errors {
+                eprintln!("  {}: {}", error.field, error.message);
+            }
+            ExitCode::BadRequest
+        }
+        Err(unmark_dex_entry::Error::NotFound) => {
+            eprintln!("The trainer or the Pokemon does not exist");
+            ExitCode::NotFound
+        }
+        Err(unmark_dex_entry::Error::Unknown(e)) => {
+            eprintln!("An unknown error occurred: {e}");
+            ExitCode::Unknown
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use crate::domain::update_pokemon;
+use crate::repositories::pokemon::Repository;
+
+use super::output::{self, Format};
+use super::{prompt_name, prompt_number, prompt_types, prompt_stats};
+
+pub fn run(repo: Arc<dyn Repository>) {
+    let number = prompt_number();
+    let name = prompt_name();
+    let types = prompt_types();
+    let stats = prompt_stats();
+
+    let req = match (number, name, types, stats) {
+        (Ok(number), Ok(name), Ok(types), Ok(stats)) => update_pokemon::Request {
+            number,
+            form: None,
+            name: Some(name),
+            types: Some(types),
+            stats: Some(stats),
+        },
+        _ => {
+            println!("An error occurred during the prompt");
+            return;
+        }
+    };
+
+    match update_pokemon::execute(repo, req) {
+        Ok(p) => output::print_one(
+            Format::Table,
+            &output::Pokemon::new(p.number, p.name, p.types, p.stats, p.total).with_form(p.form),
+        ),
+        Err(update_pokemon::Error::BadRequest(errors)) => {
+            println!("The request is invalid");
+            for error in errors {
+                println!("  {}: {}", error.field, error.message);
+            }
+        }
+        Err(update_pokemon::Error::NotFound) => println!("The Pokemon does not exist"),
+        Err(update_pokemon::Error::Unknown(e)) => println!("An unknown error occurred: {e}"),
+    }
+}
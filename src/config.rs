@@ -0,0 +1,433 @@
+use std::fmt;
+use std::path::Path;
+use std::time::Duration;
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::domain::entities::DEFAULT_NATIONAL_DEX_LIMIT;
+use crate::repositories::inmemory_pokemon::InMemoryRepository;
+use crate::repositories::pokemon::Repository;
+use crate::repositories::{self, OpenError};
+
+pub const DEFAULT_FILE: &str = "pokedex.toml";
+pub const DEFAULT_LISTEN: &str = "localhost:8000";
+pub const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// A credential. It never shows up in `Debug` output or in a serialized config.
+#[derive(Clone, PartialEq)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\"***\"")
+    }
+}
+
+impl Serialize for Secret {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str("***")
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Off,
+    /// Server errors only.
+    Error,
+    /// Client and server errors.
+    Warn,
+    /// Every request.
+    Info,
+    Debug,
+}
+
+impl TryFrom<&str> for LogLevel {
+    type Error = ();
+
+    fn try_from(level: &str) -> Result<Self, ()> {
+        match level.to_lowercase().as_str() {
+            "off" => Ok(LogLevel::Off),
+            "error" => Ok(LogLevel::Error),
+            "warn" => Ok(LogLevel::Warn),
+            "info" => Ok(LogLevel::Info),
+            "debug" => Ok(LogLevel::Debug),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum Backend {
+    Memory,
+    Sqlite { path: String },
+    Airtable {
+        api_key: Secret,
+        workspace_id: String,
+    },
+}
+
+impl Backend {
+    /// Backends left out of the build are still accepted by the configuration, so
+    /// asking for one fails here with `OpenError::Disabled` instead of a parse error.
+    pub fn open(&self, timeout: Duration) -> Result<Arc<dyn Repository>, OpenError> {
+        match self {
+            Backend::Memory => Ok(Arc::new(InMemoryRepository::new())),
+            Backend::Sqlite { path } => repositories::open_sqlite(path, timeout),
+            Backend::Airtable {
+                api_key,
+                workspace_id,
+            } => repositories::open_airtable(api_key.expose(), workspace_id, timeout),
+        }
+    }
+}
+
+/// The effective configuration, once every layer has been applied.
+#[derive(Debug, Serialize)]
+pub struct Config {
+    pub listen: String,
+    pub log_level: LogLevel,
+    pub timeout_secs: u64,
+    /// Highest national number accepted, raised as new generations come out.
+    pub national_dex_limit: u16,
+    pub backend: Backend,
+}
+
+impl Config {
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs)
+    }
+
+    /// The configuration as TOML, with secrets redacted.
+    pub fn to_redacted_toml(&self) -> String {
+        toml::to_string(self).unwrap_or_else(|e| format!("# can't be displayed: {e}\n"))
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Read { path: String, message: String },
+    Parse { path: String, message: String },
+    Invalid { key: &'static str, value: String },
+    Missing(&'static str),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Read { path, message } => write!(f, "can't read {path}: {message}"),
+            ConfigError::Parse { path, message } => write!(f, "can't parse {path}: {message}"),
+            ConfigError::Invalid { key, value } => write!(f, "invalid value {value:?} for {key}"),
+            ConfigError::Missing(key) => write!(f, "{key} is required"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SqliteLayer {
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AirtableLayer {
+    pub api_key: Option<String>,
+    /// File holding the API key, so that it stays out of the config and the command line.
+    pub api_key_file: Option<String>,
+    pub workspace_id: Option<String>,
+}
+
+/// Settings from one source. Each layer overrides the fields set by the previous ones.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Layer {
+    /// Either `memory`, `sqlite` or `airtable`. Guessed from the other settings when missing.
+    pub backend: Option<String>,
+    pub listen: Option<String>,
+    pub log_level: Option<String>,
+    pub timeout_secs: Option<u64>,
+    pub national_dex_limit: Option<u16>,
+    #[serde(default)]
+    pub sqlite: SqliteLayer,
+    #[serde(default)]
+    pub airtable: AirtableLayer,
+}
+
+impl Layer {
+    fn merge(self, over: Layer) -> Layer {
+        // A key and a key file from different layers don't mix: the last layer
+        // that sets either of them wins.
+        let (api_key, api_key_file) =
+            match over.airtable.api_key.is_some() || over.airtable.api_key_file.is_some() {
+                true => (over.airtable.api_key, over.airtable.api_key_file),
+                false => (self.airtable.api_key, self.airtable.api_key_file),
+            };
+        Layer {
+            backend: over.backend.or(self.backend),
+            listen: over.listen.or(self.listen),
+            log_level: over.log_level.or(self.log_level),
+            timeout_secs: over.timeout_secs.or(self.timeout_secs),
+            national_dex_limit: over.national_dex_limit.or(self.national_dex_limit),
+            sqlite: SqliteLayer {
+                path: over.sqlite.path.or(self.sqlite.path),
+            },
+            airtable: AirtableLayer {
+                api_key,
+                api_key_file,
+                workspace_id: over.airtable.workspace_id.or(self.airtable.workspace_id),
+            },
+        }
+    }
+
+    fn from_file(path: &str) -> Result<Layer, ConfigError> {
+        let content = std::fs::read_to_string(path).map_err(|e| ConfigError::Read {
+            path: path.to_owned(),
+            message: e.to_string(),
+        })?;
+        toml::from_str(&content).map_err(|e| ConfigError::Parse {
+            path: path.to_owned(),
+            message: e.to_string(),
+        })
+    }
+
+    /// Reads the `POKEDEX_*` variables through `env`.
+    pub fn from_env(env: &dyn Fn(&str) -> Option<String>) -> Result<Layer, ConfigError> {
+        let timeout_secs = match env("POKEDEX_TIMEOUT_SECS") {
+            Some(value) => Some(value.parse().map_err(|_| ConfigError::Invalid {
+                key: "POKEDEX_TIMEOUT_SECS",
+                value,
+            })?),
+            None => None,
+        };
+        let national_dex_limit = match env("POKEDEX_NATIONAL_DEX_LIMIT") {
+            Some(value) => Some(value.parse().map_err(|_| ConfigError::Invalid {
+                key: "POKEDEX_NATIONAL_DEX_LIMIT",
+                value,
+            })?),
+            None => None,
+        };
+        Ok(Layer {
+            backend: env("POKEDEX_BACKEND"),
+            listen: env("POKEDEX_LISTEN"),
+            log_level: env("POKEDEX_LOG_LEVEL"),
+            timeout_secs,
+            national_dex_limit,
+            sqlite: SqliteLayer {
+                path: env("POKEDEX_SQLITE_PATH"),
+            },
+            airtable: AirtableLayer {
+                api_key: env("POKEDEX_AIRTABLE_API_KEY"),
+                api_key_file: env("POKEDEX_AIRTABLE_API_KEY_FILE"),
+                workspace_id: env("POKEDEX_AIRTABLE_WORKSPACE_ID"),
+            },
+        })
+    }
+}
+
+fn read_secret(path: &str) -> Result<Secret, ConfigError> {
+    match std::fs::read_to_string(path) {
+        Ok(secret) => Ok(Secret(secret.trim_end_matches(['\r', '\n']).to_owned())),
+        Err(e) => Err(ConfigError::Read {
+            path: path.to_owned(),
+            message: e.to_string(),
+        }),
+    }
+}
+
+fn backend(layer: Layer) -> Result<Backend, ConfigError> {
+    let name = match layer.backend {
+        Some(name) => name,
+        None if layer.sqlite.path.is_some() => String::from("sqlite"),
+        None if layer.airtable.workspace_id.is_some() => String::from("airtable"),
+        None => String::from("memory"),
+    };
+    match name.as_str() {
+        "memory" => Ok(Backend::Memory),
+        "sqlite" => Ok(Backend::Sqlite {
+            path: layer.sqlite.path.ok_or(ConfigError::Missing("sqlite.path"))?,
+        }),
+        "airtable" => {
+            let api_key = match (layer.airtable.api_key_file, layer.airtable.api_key) {
+                (Some(path), _) => read_secret(&path)?,
+                (None, Some(api_key)) => Secret(api_key),
+                (None, None) => return Err(ConfigError::Missing("airtable.api_key")),
+            };
+            Ok(Backend::Airtable {
+                api_key,
+                workspace_id: layer
+                    .airtable
+                    .workspace_id
+                    .ok_or(ConfigError::Missing("airtable.workspace_id"))?,
+            })
+        }
+        _ => Err(ConfigError::Invalid {
+            key: "backend",
+            value: name,
+        }),
+    }
+}
+
+/// Builds the configuration from the TOML `file`, then the environment, then `flags`.
+/// Without an explicit file, `POKEDEX_CONFIG` or else `pokedex.toml` is read if it exists.
+pub fn load(
+    file: Option<&str>,
+    env: &dyn Fn(&str) -> Option<String>,
+    flags: Layer,
+) -> Result<Config, ConfigError> {
+    let file = match file.map(str::to_owned).or_else(|| env("POKEDEX_CONFIG")) {
+        Some(path) => Layer::from_file(&path)?,
+        None if Path::new(DEFAULT_FILE).exists() => Layer::from_file(DEFAULT_FILE)?,
+        None => Layer::default(),
+    };
+    let layer = file.merge(Layer::from_env(env)?).merge(flags);
+
+    let log_level = match layer.log_level.as_deref().map(LogLevel::try_from) {
+        Some(Ok(level)) => level,
+        Some(Err(())) => {
+            return Err(ConfigError::Invalid {
+                key: "log_level",
+                value: layer.log_level.unwrap_or_default(),
+            })
+        }
+        None => LogLevel::Info,
+    };
+    let national_dex_limit = match layer.national_dex_limit {
+        Some(0) => {
+            return Err(ConfigError::Invalid {
+                key: "national_dex_limit",
+                value: String::from("0"),
+            })
+        }
+        Some(limit) => limit,
+        None => DEFAULT_NATIONAL_DEX_LIMIT,
+    };
+    Ok(Config {
+        listen: layer
+            .listen
+            .clone()
+            .unwrap_or_else(|| DEFAULT_LISTEN.to_owned()),
+        log_level,
+        timeout_secs: layer.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS),
+        national_dex_limit,
+        backend: backend(layer)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn temp_file(name: &str, content: &str) -> String {
+        let path = std::env::temp_dir().join(format!("pokedex-{}-{name}", std::process::id()));
+        std::fs::write(&path, content).unwrap();
+        path.to_str().unwrap().to_owned()
+    }
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars = vars
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect::<HashMap<String, String>>();
+        move |key| vars.get(key).cloned()
+    }
+
+    #[test]
+    fn it_should_apply_file_then_env_then_flags() {
+        let file = temp_file(
+            "layers.toml",
+            "listen = \"0.0.0.0:80\"\nlog_level = \"error\"\ntimeout_secs = 5\n\n[sqlite]\npath = \"file.db\"\n",
+        );
+        let env = env(&[("POKEDEX_LOG_LEVEL", "warn"), ("POKEDEX_SQLITE_PATH", "env.db")]);
+        let flags = Layer {
+            sqlite: SqliteLayer {
+                path: Some(String::from("flag.db")),
+            },
+            ..Layer::default()
+        };
+
+        let config = load(Some(&file), &env, flags).unwrap();
+
+        assert_eq!(config.listen, "0.0.0.0:80");
+        assert_eq!(config.log_level, LogLevel::Warn);
+        assert_eq!(config.timeout(), Duration::from_secs(5));
+        assert_eq!(
+            config.backend,
+            Backend::Sqlite {
+                path: String::from("flag.db")
+            }
+        );
+    }
+
+    #[test]
+    fn it_should_raise_or_lower_the_national_dex_limit() {
+        let default = load(None, &env(&[]), Layer::default()).unwrap();
+        let env = env(&[("POKEDEX_NATIONAL_DEX_LIMIT", "898")]);
+        let lowered = load(None, &env, Layer::default()).unwrap();
+        let zero = Layer {
+            national_dex_limit: Some(0),
+            ..Layer::default()
+        };
+
+        assert_eq!(default.national_dex_limit, DEFAULT_NATIONAL_DEX_LIMIT);
+        assert_eq!(lowered.national_dex_limit, 898);
+        assert!(matches!(
+            load(None, &env, zero),
+            Err(ConfigError::Invalid {
+                key: "national_dex_limit",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn it_should_read_secrets_from_files_and_redact_them() {
+        let secret = temp_file("api_key", "keyABC\n");
+        let env = env(&[
+            ("POKEDEX_BACKEND", "airtable"),
+            ("POKEDEX_AIRTABLE_API_KEY_FILE", &secret),
+            ("POKEDEX_AIRTABLE_WORKSPACE_ID", "app123"),
+        ]);
+
+        let config = load(None, &env, Layer::default()).unwrap();
+
+        match &config.backend {
+            Backend::Airtable { api_key, .. } => assert_eq!(api_key.expose(), "keyABC"),
+            _ => unreachable!(),
+        }
+        let redacted = config.to_redacted_toml();
+        assert!(!redacted.contains("keyABC"));
+        assert!(redacted.contains("api_key = \"***\""));
+        assert!(!format!("{:?}", config).contains("keyABC"));
+    }
+
+    #[test]
+    fn it_should_report_missing_and_invalid_settings() {
+        let missing = load(None, &env(&[("POKEDEX_BACKEND", "sqlite")]), Layer::default());
+        let invalid = load(None, &env(&[("POKEDEX_TIMEOUT_SECS", "soon")]), Layer::default());
+
+        match (missing, invalid) {
+            (
+                Err(ConfigError::Missing("sqlite.path")),
+                Err(ConfigError::Invalid {
+                    key: "POKEDEX_TIMEOUT_SECS",
+                    ..
+                }),
+            ) => {}
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{Ability, FieldError, PokemonName};
+use crate::repositories::pokemon::{InsertAbilityError, Repository, StorageError};
+
+pub use super::fetch_all_abilities::Response;
+
+pub struct Request {
+    pub name: String,
+    pub description: String,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    BadRequest(Vec<FieldError>),
+    Conflict,
+    Unknown(StorageError),
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let mut errors = vec![];
+    let name = PokemonName::try_from(req.name).map_err(|e| errors.push(FieldError::new("name", &e)));
+    let description = req.description.trim().to_owned();
+    if description.is_empty() {
+        errors.push(FieldError {
+            field: String::from("description"),
+            code: "empty",
+            message: String::from("description must not be empty"),
+        });
+    }
+    let ability = match name {
+        Ok(name) if errors.is_empty() => Ability::new(name, description),
+        _ => return Err(Error::BadRequest(errors)),
+    };
+
+    match repo.insert_ability(ability) {
+        Ok(ability) => Ok(Response::from(ability)),
+        Err(InsertAbilityError::Conflict) => Err(Error::Conflict),
+        Err(InsertAbilityError::Storage(e)) => Err(Error::Unknown(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    fn request(description: &str) -> Request {
+        Request {
+            name: String::from("Static"),
+            description: String::from(description),
+        }
+    }
+
+    #[test]
+    fn it_should_return_the_ability_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        let res = execute(repo.clone(), request(" May paralyze on contact. ")).unwrap();
+
+        assert_eq!(res.name, "Static");
+        assert_eq!(res.description, "May paralyze on contact.");
+        match execute(repo, request("Again.")) {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_description_is_empty() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo, request(" ")) {
+            Err(Error::BadRequest(errors)) => assert_eq!(errors[0].field, "description"),
+            _ => unreachable!(),
+        };
+    }
+}
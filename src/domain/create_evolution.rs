@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::domain::entities::{Evolution, EvolutionTrigger, FieldError, PokemonNumber};
+use crate::repositories::pokemon::{FetchOneError, InsertEvolutionError, Repository, StorageError};
+
+pub struct Request {
+    pub from: u16,
+    pub to: u16,
+    /// `level:N`, `stone:NAME`, `trade` or `friendship`.
+    pub trigger: String,
+    pub conditions: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub from: u16,
+    pub to: u16,
+    pub trigger: String,
+    pub conditions: Vec<String>,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    BadRequest(Vec<FieldError>),
+    /// One end of the evolution is not a known Pokemon.
+    NotFound(u16),
+    /// The target already evolves from another Pokemon.
+    Conflict,
+    /// The evolution would make a Pokemon evolve, directly or not, into itself.
+    Cycle,
+    Unknown(StorageError),
+}
+
+fn validate(req: Request) -> Result<Evolution, Vec<FieldError>> {
+    let mut errors = vec![];
+    let from = PokemonNumber::try_from(req.from).map_err(|e| errors.push(FieldError::new("from", &e)));
+    let to = PokemonNumber::try_from(req.to).map_err(|e| errors.push(FieldError::new("to", &e)));
+    let trigger = EvolutionTrigger::try_from(req.trigger)
+        .map_err(|e| errors.push(FieldError::new("trigger", &e)));
+    let conditions = req
+        .conditions
+        .into_iter()
+        .map(|condition| condition.trim().to_owned())
+        .collect::<Vec<String>>();
+    if conditions.iter().any(String::is_empty) {
+        errors.push(FieldError {
+            field: String::from("conditions"),
+            code: "empty",
+            message: String::from("conditions must not be empty"),
+        });
+    }
+
+    match (from, to, trigger) {
+        (Ok(from), Ok(to), Ok(trigger)) if errors.is_empty() => {
+            Ok(Evolution::new(from, to, trigger, conditions))
+        }
+        _ => Err(errors),
+    }
+}
+
+/// Whether `to` is `from` itself or one of the stages `from` evolved from.
+fn is_ancestor(evolutions: &[Evolution], from: &PokemonNumber, to: &PokemonNumber) -> bool {
+    let parents = evolutions
+        .iter()
+        .map(|evolution| (&evolution.to, &evolution.from))
+        .collect::<HashMap<&PokemonNumber, &PokemonNumber>>();
+    let mut current = from;
+    // Stored evolutions have no cycle, so the walk ends within `parents.len()` steps.
+    for _ in 0..=parents.len() {
+        if current == to {
+            return true;
+        }
+        match parents.get(current) {
+            Some(parent) => current = parent,
+            None => return false,
+        }
+    }
+    false
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let evolution = validate(req).map_err(Error::BadRequest)?;
+
+    for number in [&evolution.from, &evolution.to] {
+        match repo.fetch_one(number.clone().into()) {
+            Ok(_) => {}
+            Err(FetchOneError::NotFound) => return Err(Error::NotFound(u16::from(number.clone()))),
+            Err(FetchOneError::Storage(e)) => return Err(Error::Unknown(e)),
+        }
+    }
+
+    let evolutions = repo.fetch_evolutions().map_err(Error::Unknown)?;
+    if is_ancestor(&evolutions, &evolution.from, &evolution.to) {
+        return Err(Error::Cycle);
+    }
+
+    match repo.insert_evolution(evolution) {
+        Ok(evolution) => Ok(Response {
+            from: u16::from(evolution.from),
+            to: u16::from(evolution.to),
+            trigger: String::from(evolution.trigger),
+            conditions: evolution.conditions,
+        }),
+        Err(InsertEvolutionError::Conflict) => Err(Error::Conflict),
+        Err(InsertEvolutionError::Storage(e)) => Err(Error::Unknown(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Pokemon, PokemonName, PokemonStats, PokemonTypes};
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    fn repo(numbers: &[u16]) -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        let pokemons = numbers
+            .iter()
+            .map(|number| {
+                Pokemon::new(
+                    PokemonNumber::try_from(*number).unwrap(),
+                    PokemonName::pikachu(),
+                    PokemonTypes::pikachu(),
+                    PokemonStats::pikachu(),
+                )
+            })
+            .collect();
+        repo.upsert_all(pokemons).unwrap();
+        repo
+    }
+
+    fn request(from: u16, to: u16) -> Request {
+        Request {
+            from,
+            to,
+            trigger: String::from("stone:Thunder Stone"),
+            conditions: vec![],
+        }
+    }
+
+    #[test]
+    fn it_should_link_two_existing_pokemons() {
+        let repo = repo(&[25, 26]);
+
+        let res = execute(repo.clone(), request(25, 26)).unwrap();
+
+        assert_eq!((res.from, res.to), (25, 26));
+        assert_eq!(res.trigger, "stone:Thunder Stone");
+        assert_eq!(repo.fetch_evolutions().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn it_should_reject_missing_pokemons_and_invalid_fields() {
+        let repo = repo(&[25]);
+
+        match execute(repo.clone(), request(25, 26)) {
+            Err(Error::NotFound(26)) => {}
+            _ => unreachable!(),
+        };
+        let req = Request {
+            trigger: String::from("level:101"),
+            conditions: vec![String::from(" ")],
+            ..request(25, 26)
+        };
+        match execute(repo, req) {
+            Err(Error::BadRequest(errors)) => {
+                let fields = errors.iter().map(|e| e.field.as_str()).collect::<Vec<&str>>();
+                assert_eq!(fields, vec!["trigger", "conditions"]);
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_refuse_cycles_and_second_pre_evolutions() {
+        let repo = repo(&[172, 25, 26, 37]);
+        execute(repo.clone(), request(172, 25)).unwrap();
+        execute(repo.clone(), request(25, 26)).unwrap();
+
+        for (from, to) in [(26, 172), (25, 25)] {
+            match execute(repo.clone(), request(from, to)) {
+                Err(Error::Cycle) => {}
+                _ => unreachable!(),
+            };
+        }
+        match execute(repo, request(37, 26)) {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        };
+    }
+}
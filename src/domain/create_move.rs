@@ -0,0 +1,133 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{
+    FieldError, Move, MoveAccuracy, MoveCategory, MoveId, MovePower, MovePp, PokemonName,
+    PokemonType,
+};
+use crate::repositories::pokemon::{InsertMoveError, Repository, StorageError};
+
+pub use super::fetch_move::Response;
+
+pub struct Request {
+    pub id: u16,
+    pub name: String,
+    pub tipe: String,
+    /// `physical`, `special` or `status`.
+    pub category: String,
+    pub power: Option<u16>,
+    pub accuracy: Option<u16>,
+    pub pp: u16,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    BadRequest(Vec<FieldError>),
+    Conflict,
+    Unknown(StorageError),
+}
+
+/// Shared with `update_move`, which replaces a move with the same fields.
+pub(super) fn validate(req: Request) -> Result<Move, Vec<FieldError>> {
+    let mut errors = vec![];
+    let id = MoveId::try_from(req.id).map_err(|e| errors.push(FieldError::new("id", &e)));
+    let name = PokemonName::try_from(req.name).map_err(|e| errors.push(FieldError::new("name", &e)));
+    let tipe = PokemonType::try_from(req.tipe).map_err(|e| errors.push(FieldError::new("type", &e)));
+    let category = MoveCategory::try_from(req.category)
+        .map_err(|e| errors.push(FieldError::new("category", &e)));
+    let power = req
+        .power
+        .map(MovePower::try_from)
+        .transpose()
+        .map_err(|e| errors.push(FieldError::new("power", &e)));
+    let accuracy = req
+        .accuracy
+        .map(MoveAccuracy::try_from)
+        .transpose()
+        .map_err(|e| errors.push(FieldError::new("accuracy", &e)));
+    let pp = MovePp::try_from(req.pp).map_err(|e| errors.push(FieldError::new("pp", &e)));
+    if let (Ok(MoveCategory::Status), Ok(Some(_))) = (&category, &power) {
+        errors.push(FieldError {
+            field: String::from("power"),
+            code: "unexpected",
+            message: String::from("status moves have no power"),
+        });
+    }
+
+    match (id, name, tipe, category, power, accuracy, pp) {
+        (Ok(id), Ok(name), Ok(tipe), Ok(category), Ok(power), Ok(accuracy), Ok(pp))
+            if errors.is_empty() =>
+        {
+            Ok(Move::new(id, name, tipe, category, power, accuracy, pp))
+        }
+        _ => Err(errors),
+    }
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let new_move = validate(req).map_err(Error::BadRequest)?;
+
+    match repo.insert_move(new_move) {
+        Ok(created) => Ok(Response::from(created)),
+        Err(InsertMoveError::Conflict) => Err(Error::Conflict),
+        Err(InsertMoveError::Storage(e)) => Err(Error::Unknown(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    fn request() -> Request {
+        Request {
+            id: 84,
+            name: String::from("Thunder Shock"),
+            tipe: String::from("electric"),
+            category: String::from("Special"),
+            power: Some(40),
+            accuracy: Some(100),
+            pp: 30,
+        }
+    }
+
+    #[test]
+    fn it_should_return_the_move_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        let res = execute(repo, request()).unwrap();
+
+        assert_eq!((res.id, res.name.as_str()), (84, "Thunder Shock"));
+        assert_eq!((res.tipe.as_str(), res.category.as_str()), ("Electric", "special"));
+        assert_eq!((res.power, res.accuracy, res.pp), (Some(40), Some(100), 30));
+    }
+
+    #[test]
+    fn it_should_return_a_conflict_error_when_the_move_already_exists() {
+        let repo = Arc::new(InMemoryRepository::new());
+        execute(repo.clone(), request()).unwrap();
+
+        match execute(repo, request()) {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            category: String::from("status"),
+            accuracy: Some(101),
+            pp: 0,
+            ..request()
+        };
+
+        match execute(repo, req) {
+            Err(Error::BadRequest(errors)) => {
+                let fields = errors.iter().map(|e| e.field.as_str()).collect::<Vec<&str>>();
+                assert_eq!(fields, vec!["accuracy", "pp", "power"]);
+            }
+            _ => unreachable!(),
+        };
+    }
+}
@@ -1,51 +1,91 @@
 use std::sync::Arc;
 
-use crate::domain::entities::{PokemonName, PokemonNumber, PokemonTypes};
-use crate::repositories::pokemon::{InsertError, Repository};
+use crate::domain::entities::{
+    FieldError, PokemonForm, PokemonKey, PokemonName, PokemonNumber, PokemonStats, PokemonTypes,
+};
+use crate::repositories::pokemon::{InsertError, Repository, StorageError};
 
 pub struct Request {
     pub number: u16,
+    /// Unset for the base form.
+    pub form: Option<String>,
     pub name: String,
     pub types: Vec<String>,
+    /// HP, Attack, Defense, Sp. Atk, Sp. Def and Speed.
+    pub stats: [u16; 6],
+    /// Highest national number accepted.
+    pub national_dex_limit: u16,
 }
 
 #[derive(Debug)]
 pub struct Response {
     pub number: u16,
+    pub generation: Option<u8>,
+    pub form: Option<String>,
     pub name: String,
     pub types: Vec<String>,
+    pub stats: [u16; 6],
+    pub total: u16,
 }
 
+#[derive(Debug)]
 pub enum Error {
-    BadRequest,
+    BadRequest(Vec<FieldError>),
     Conflict,
-    Unknown,
+    Unknown(StorageError),
 }
 
 pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
     let pokemon = match (
-        PokemonNumber::try_from(req.number),
+        PokemonNumber::within(req.number, req.national_dex_limit),
+        req.form.map(PokemonForm::try_from).transpose(),
         PokemonName::try_from(req.name),
         PokemonTypes::try_from(req.types),
+        PokemonStats::try_from(req.stats),
     ) {
-        (Ok(number), Ok(name), Ok(types)) => repo.insert(number, name.clone(), types.clone()),
-        _ => return Err(Error::BadRequest),
+        (Ok(number), Ok(form), Ok(name), Ok(types), Ok(stats)) => {
+            repo.insert(PokemonKey::new(number, form), name, types, stats)
+        }
+        (number, form, name, types, stats) => {
+            let mut errors = vec![];
+            if let Err(e) = number {
+                errors.push(FieldError::new("number", &e));
+            }
+            if let Err(e) = form {
+                errors.push(FieldError::new("form", &e));
+            }
+            if let Err(e) = name {
+                errors.push(FieldError::new("name", &e));
+            }
+            if let Err(e) = types {
+                errors.push(FieldError::new("types", &e));
+            }
+            if let Err(e) = stats {
+                errors.push(FieldError::new("stats", &e));
+            }
+            return Err(Error::BadRequest(errors));
+        }
     };
 
     match pokemon {
         Ok(pokemon) => Ok(Response {
+            generation: pokemon.number.generation().map(u8::from),
             number: u16::from(pokemon.number),
+            form: pokemon.form.map(String::from),
             name: String::from(pokemon.name),
             types: Vec::<String>::from(pokemon.types),
+            total: pokemon.stats.total(),
+            stats: <[u16; 6]>::from(pokemon.stats),
         }),
         Err(InsertError::Conflict) => Err(Error::Conflict),
-        _ => Err(Error::Unknown),
+        Err(InsertError::Storage(e)) => Err(Error::Unknown(e)),
     }
 }
 
 #[cfg(test)]
 mod tests {
 
+    use crate::domain::entities::DEFAULT_NATIONAL_DEX_LIMIT;
     use crate::repositories::inmemory_pokemon::InMemoryRepository;
 
     use super::*;
@@ -55,8 +95,11 @@ mod tests {
         let repo = Arc::new(InMemoryRepository::new());
         let req = Request {
             number: 25,
+            form: None,
             name: String::from("Pikachu"),
             types: vec![String::from("Electric")],
+            stats: [35, 55, 40, 50, 50, 90],
+            national_dex_limit: DEFAULT_NATIONAL_DEX_LIMIT,
         };
 
         let res = execute(repo, req);
@@ -66,40 +109,137 @@ mod tests {
                 assert_eq!(res.number, 25);
                 assert_eq!(res.name, "Pikachu".to_owned());
                 assert_eq!(res.types, vec!["Electric".to_owned()]);
+                assert_eq!(res.stats, [35, 55, 40, 50, 50, 90]);
+                assert_eq!(res.total, 320);
             }
             _ => unreachable!("execute returned an error"),
         }
     }
 
+    #[test]
+    fn it_should_accept_types_outside_of_the_original_three() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            number: 7,
+            form: None,
+            name: String::from("Squirtle"),
+            types: vec![String::from("water")],
+            stats: [44, 48, 65, 50, 64, 43],
+            national_dex_limit: DEFAULT_NATIONAL_DEX_LIMIT,
+        };
+
+        let res = execute(repo, req);
+
+        match res {
+            Ok(res) => assert_eq!(res.types, vec!["Water".to_owned()]),
+            _ => unreachable!("execute returned an error"),
+        }
+    }
+
     #[test]
     fn it_should_return_a_bad_request_error_when_request_is_invalid() {
         let repo = Arc::new(InMemoryRepository::new());
         let req = Request {
             number: 25,
+            form: None,
             name: String::from(""),
             types: vec![String::from("Electric")],
+            stats: [35, 55, 40, 50, 50, 90],
+            national_dex_limit: DEFAULT_NATIONAL_DEX_LIMIT,
+        };
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => {
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].field, "name");
+                assert_eq!(errors[0].code, "empty");
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_number_is_above_the_limit() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            number: 906,
+            form: None,
+            name: String::from("Sprigatito"),
+            types: vec![String::from("Grass")],
+            stats: [40, 61, 54, 45, 45, 65],
+            national_dex_limit: 898,
         };
 
         let res = execute(repo, req);
 
         match res {
-            Err(Error::BadRequest) => {}
+            Err(Error::BadRequest(errors)) => {
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].field, "number");
+                assert_eq!(errors[0].code, "out_of_range");
+            }
             _ => unreachable!(),
         };
     }
 
+    #[test]
+    fn it_should_return_a_bad_request_error_when_stats_are_out_of_bounds() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            number: 25,
+            form: None,
+            name: String::from("Pikachu"),
+            types: vec![String::from("Electric")],
+            stats: [35, 55, 40, 50, 50, 0],
+            national_dex_limit: DEFAULT_NATIONAL_DEX_LIMIT,
+        };
+
+        let res = execute(repo, req);
+
+        assert!(matches!(res, Err(Error::BadRequest(errors)) if errors[0].field == "stats"));
+    }
+
+    #[test]
+    fn it_should_report_every_invalid_field() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            number: 0,
+            form: None,
+            name: String::from(""),
+            types: vec![String::from("Shadow")],
+            stats: [0, 55, 40, 50, 50, 90],
+            national_dex_limit: DEFAULT_NATIONAL_DEX_LIMIT,
+        };
+
+        let res = execute(repo, req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => {
+                let fields = errors.iter().map(|e| e.field.as_str()).collect::<Vec<&str>>();
+                assert_eq!(fields, vec!["number", "name", "types", "stats"]);
+            }
+            _ => unreachable!(),
+        }
+    }
+
     #[test]
     fn it_should_return_a_conflict_error_when_pokemon_already_exists() {
         let repo = Arc::new(InMemoryRepository::new());
         let number = PokemonNumber::pikachu();
         let name = PokemonName::pikachu();
         let types = PokemonTypes::pikachu();
-        repo.insert(number, name, types).ok();
+        let stats = PokemonStats::pikachu();
+        repo.insert(number.into(), name, types, stats).ok();
 
         let req = Request {
             number: 25,
+            form: None,
             name: String::from("Charmander"),
             types: vec![String::from("Fire")],
+            stats: [35, 55, 40, 50, 50, 90],
+            national_dex_limit: DEFAULT_NATIONAL_DEX_LIMIT,
         };
         let res = execute(repo, req);
 
@@ -115,14 +255,17 @@ mod tests {
         let number = 25;
         let req = Request {
             number,
+            form: None,
             name: String::from("Pikachu"),
             types: vec![String::from("Electric")],
+            stats: [35, 55, 40, 50, 50, 90],
+            national_dex_limit: DEFAULT_NATIONAL_DEX_LIMIT,
         };
 
         let res = execute(repo, req);
 
         match res {
-            Err(Error::Unknown) => {}
+            Err(Error::Unknown(_)) => {}
             _ => unreachable!(),
         }
     }
@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{FieldError, PokemonName, Trainer, TrainerId};
+use crate::repositories::pokemon::{InsertTrainerError, Repository, StorageError};
+
+pub struct Request {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub id: String,
+    pub name: String,
+}
+
+impl From<Trainer> for Response {
+    fn from(trainer: Trainer) -> Self {
+        Self {
+            id: String::from(trainer.id),
+            name: String::from(trainer.name),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    BadRequest(Vec<FieldError>),
+    Conflict,
+    Unknown(StorageError),
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let mut errors = vec![];
+    let id = TrainerId::try_from(req.id).map_err(|e| errors.push(FieldError::new("id", &e)));
+    let name =
+        PokemonName::try_from(req.name).map_err(|e| errors.push(FieldError::new("name", &e)));
+    let trainer = match (id, name) {
+        (Ok(id), Ok(name)) => Trainer::new(id, name),
+        _ => return Err(Error::BadRequest(errors)),
+    };
+
+    match repo.insert_trainer(trainer) {
+        Ok(trainer) => Ok(Response::from(trainer)),
+        Err(InsertTrainerError::Conflict) => Err(Error::Conflict),
+        Err(InsertTrainerError::Storage(e)) => Err(Error::Unknown(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    fn request(id: &str) -> Request {
+        Request {
+            id: String::from(id),
+            name: String::from("Red"),
+        }
+    }
+
+    #[test]
+    fn it_should_store_the_id_in_lowercase_and_detect_conflicts() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        let res = execute(repo.clone(), request("Red")).unwrap();
+
+        assert_eq!(res.id, "red");
+        match execute(repo, request("red")) {
+            Err(Error::Conflict) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_a_bad_request_error_when_the_id_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo, request("red/blue")) {
+            Err(Error::BadRequest(errors)) => assert_eq!(errors[0].code, "invalid_trainer_id"),
+            _ => unreachable!(),
+        };
+    }
+}
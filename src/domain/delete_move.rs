@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use crate::repositories::pokemon::{DeleteMoveError, Repository, StorageError};
+
+use super::entities::MoveId;
+
+#[derive(Debug)]
+pub enum Error {
+    Unknown(StorageError),
+    BadRequest,
+    NotFound,
+}
+
+pub struct Request {
+    pub id: u16,
+}
+
+/// Also removes the move from every learnset.
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<(), Error> {
+    match MoveId::try_from(req.id) {
+        Ok(id) => match repo.delete_move(id) {
+            Ok(_) => Ok(()),
+            Err(DeleteMoveError::NotFound) => Err(Error::NotFound),
+            Err(DeleteMoveError::Storage(e)) => Err(Error::Unknown(e)),
+        },
+        Err(_) => Err(Error::BadRequest),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::Move;
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_delete_the_move_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert_move(Move::swift()).unwrap();
+
+        execute(repo.clone(), Request { id: 129 }).unwrap();
+
+        assert!(repo.fetch_moves().unwrap().is_empty());
+        match execute(repo, Request { id: 129 }) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+}
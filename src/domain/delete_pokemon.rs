@@ -1,27 +1,30 @@
-use crate::repositories::pokemon::{DeleteError, Repository};
+use crate::repositories::pokemon::{DeleteError, Repository, StorageError};
 use std::sync::Arc;
 
-use super::entities::PokemonNumber;
+use super::entities::{PokemonForm, PokemonKey, PokemonNumber};
 
 #[derive(Debug)]
 pub enum Error {
-    Unknown,
+    Unknown(StorageError),
     BadRequest,
     NotFound,
 }
 
 pub struct Request {
     pub number: u16,
+    /// Unset to delete the base form, and with it every other form.
+    pub form: Option<String>,
 }
 
 pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<(), Error> {
-    match PokemonNumber::try_from(req.number) {
-        Ok(number) => match repo.delete(number) {
+    let form = req.form.map(PokemonForm::try_from).transpose();
+    match (PokemonNumber::try_from(req.number), form) {
+        (Ok(number), Ok(form)) => match repo.delete(PokemonKey::new(number, form)) {
             Ok(_) => Ok(()),
             Err(DeleteError::NotFound) => Err(Error::NotFound),
-            Err(DeleteError::Unknown) => Err(Error::Unknown),
+            Err(DeleteError::Storage(e)) => Err(Error::Unknown(e)),
         },
-        Err(_) => Err(Error::BadRequest),
+        _ => Err(Error::BadRequest),
     }
 }
 
@@ -29,24 +32,31 @@ pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<(), Error> {
 mod tests {
 
     use super::*;
-    use crate::domain::entities::{PokemonName, PokemonTypes};
+    use crate::domain::entities::{PokemonName, PokemonStats, PokemonTypes};
     use crate::repositories::inmemory_pokemon::InMemoryRepository;
+    use crate::repositories::pokemon::FetchAllQuery;
 
     #[test]
     fn it_should_return_unknown_error_when_unexpected_error_happens() {
         let repo = Arc::new(InMemoryRepository::new().with_error());
 
-        let req = Request { number: 25 };
+        let req = Request {
+            number: 25,
+            form: None,
+        };
         let res = execute(repo, req);
 
-        assert!(matches!(res, Err(Error::Unknown)))
+        assert!(matches!(res, Err(Error::Unknown(_))))
     }
 
     #[test]
     fn it_should_return_bad_request_when_number_is_invalid() {
         let repo = Arc::new(InMemoryRepository::new());
 
-        let req = Request { number: 0 };
+        let req = Request {
+            number: 0,
+            form: None,
+        };
         let res = execute(repo, req);
 
         assert!(matches!(res, Err(Error::BadRequest)));
@@ -56,7 +66,10 @@ mod tests {
     fn it_should_return_not_found_when_repo_does_not_find_pokemon() {
         let repo = Arc::new(InMemoryRepository::new());
 
-        let req = Request { number: 1 };
+        let req = Request {
+            number: 1,
+            form: None,
+        };
         let res = execute(repo, req);
 
         assert!(matches!(res, Err(Error::NotFound)));
@@ -66,24 +79,28 @@ mod tests {
     fn it_should_return_ok_when_pokemon_is_deleted() {
         let repo = Arc::new(InMemoryRepository::new());
         repo.insert(
-            PokemonNumber::vulpix(),
+            PokemonNumber::vulpix().into(),
             PokemonName::vulpix(),
             PokemonTypes::vulpix(),
+            PokemonStats::vulpix(),
         )
         .expect("error inserting vulpix");
         repo.insert(
-            PokemonNumber::pikachu(),
+            PokemonNumber::pikachu().into(),
             PokemonName::pikachu(),
             PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
         )
         .expect("error inserting pikachu");
 
-        let req = Request { number: 25 };
-        let res = execute(repo.clone(), req).expect("error while deleting pikachu");
+        let req = Request {
+            number: 25,
+            form: None,
+        };
+        execute(repo.clone(), req).expect("error while deleting pikachu");
 
-        let pokemons = repo.fetch_all().expect("error on fetch all pokemons");
+        let pokemons = repo.fetch_all(&FetchAllQuery::default()).expect("error on fetch all pokemons");
 
-        assert!(matches!(res, ()));
         assert_eq!(pokemons.len(), 1);
         assert_eq!(pokemons[0].number, PokemonNumber::vulpix());
     }
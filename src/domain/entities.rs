@@ -1,20 +1,64 @@
 use std::cmp::{PartialEq, PartialOrd};
+use std::fmt;
 
-#[derive(Clone, Debug)]
+/// An entity validation failure that can be reported back to whoever sent the value.
+pub trait ValidationError: fmt::Display {
+    /// Stable, machine-readable identifier of the failure.
+    fn code(&self) -> &'static str;
+}
+
+/// A validation failure tied to the request field that caused it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldError {
+    pub field: String,
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: &str, error: &dyn ValidationError) -> Self {
+        Self {
+            field: field.to_owned(),
+            code: error.code(),
+            message: error.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct Pokemon {
     pub number: PokemonNumber,
+    /// Unset for the base form of the national number.
+    pub form: Option<PokemonForm>,
     pub name: PokemonName,
     pub types: PokemonTypes,
+    pub stats: PokemonStats,
 }
 
 impl Pokemon {
-    pub fn new(number: PokemonNumber, name: PokemonName, types: PokemonTypes) -> Self {
+    /// Builds the base form of the national number.
+    pub fn new(
+        number: PokemonNumber,
+        name: PokemonName,
+        types: PokemonTypes,
+        stats: PokemonStats,
+    ) -> Self {
         Self {
             number,
+            form: None,
             name,
             types,
+            stats,
         }
     }
+
+    pub fn with_form(self, form: Option<PokemonForm>) -> Self {
+        Self { form, ..self }
+    }
+
+    pub fn key(&self) -> PokemonKey {
+        PokemonKey::new(self.number.clone(), self.form.clone())
+    }
 }
 
 #[cfg(test)]
@@ -22,21 +66,25 @@ impl Pokemon {
     pub fn pikachu() -> Self {
         Self {
             number: PokemonNumber::pikachu(),
+            form: None,
             name: PokemonName::pikachu(),
             types: PokemonTypes::pikachu(),
+            stats: PokemonStats::pikachu(),
         }
     }
 
     pub fn vulpix() -> Self {
         Self {
             number: PokemonNumber::vulpix(),
+            form: None,
             name: PokemonName::vulpix(),
             types: PokemonTypes::vulpix(),
+            stats: PokemonStats::vulpix(),
         }
     }
 }
 
-#[derive(PartialEq, Clone, PartialOrd, Eq, Ord, Debug)]
+#[derive(PartialEq, Clone, PartialOrd, Eq, Ord, Hash, Debug)]
 pub struct PokemonNumber(u16);
 
 #[cfg(test)]
@@ -55,15 +103,60 @@ impl PokemonNumber {
     }
 }
 
+/// Highest national number accepted when none is configured, the last Paldea Pokemon.
+pub const DEFAULT_NATIONAL_DEX_LIMIT: u16 = 1025;
+
+impl PokemonNumber {
+    /// Validates a national number given by a user against the configured
+    /// national dex limit. Numbers read back from storage go through
+    /// `try_from` instead, so lowering the limit never hides stored Pokemons.
+    pub fn within(n: u16, max: u16) -> Result<Self, PokemonNumberError> {
+        match n {
+            1.. if n <= max => Ok(Self(n)),
+            _ => Err(PokemonNumberError::OutOfRange { value: n, max }),
+        }
+    }
+
+    /// The region that introduced the number, if it's a known one.
+    pub fn region(&self) -> Option<Region> {
+        Region::ALL
+            .into_iter()
+            .find(|region| (region.first()..=region.last()).contains(&self.0))
+    }
+
+    pub fn generation(&self) -> Option<Generation> {
+        self.region().map(Region::generation)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PokemonNumberError {
+    OutOfRange { value: u16, max: u16 },
+}
+
+impl fmt::Display for PokemonNumberError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PokemonNumberError::OutOfRange { value, max } => {
+                write!(f, "number {value} is not between 1 and {max}")
+            }
+        }
+    }
+}
+
+impl ValidationError for PokemonNumberError {
+    fn code(&self) -> &'static str {
+        match self {
+            PokemonNumberError::OutOfRange { .. } => "out_of_range",
+        }
+    }
+}
+
 impl TryFrom<u16> for PokemonNumber {
-    type Error = ();
+    type Error = PokemonNumberError;
 
     fn try_from(n: u16) -> Result<Self, Self::Error> {
-        if n > 0 && n < 899 {
-            Ok(Self(n))
-        } else {
-            Err(())
-        }
+        Self::within(n, u16::MAX)
     }
 }
 
@@ -73,9 +166,269 @@ impl From<PokemonNumber> for u16 {
     }
 }
 
-#[derive(Clone, Debug)]
+/// The region a national number was introduced in. Hisui shares the eighth
+/// generation with Galar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Region {
+    Kanto,
+    Johto,
+    Hoenn,
+    Sinnoh,
+    Unova,
+    Kalos,
+    Alola,
+    Galar,
+    Hisui,
+    Paldea,
+}
+
+impl Region {
+    pub const ALL: [Region; 10] = [
+        Region::Kanto,
+        Region::Johto,
+        Region::Hoenn,
+        Region::Sinnoh,
+        Region::Unova,
+        Region::Kalos,
+        Region::Alola,
+        Region::Galar,
+        Region::Hisui,
+        Region::Paldea,
+    ];
+
+    /// First and last national numbers of the region.
+    fn range(self) -> (u16, u16) {
+        match self {
+            Region::Kanto => (1, 151),
+            Region::Johto => (152, 251),
+            Region::Hoenn => (252, 386),
+            Region::Sinnoh => (387, 493),
+            Region::Unova => (494, 649),
+            Region::Kalos => (650, 721),
+            Region::Alola => (722, 809),
+            Region::Galar => (810, 898),
+            Region::Hisui => (899, 905),
+            Region::Paldea => (906, 1025),
+        }
+    }
+
+    fn first(self) -> u16 {
+        self.range().0
+    }
+
+    fn last(self) -> u16 {
+        self.range().1
+    }
+
+    pub fn generation(self) -> Generation {
+        match self {
+            Region::Kanto => Generation(1),
+            Region::Johto => Generation(2),
+            Region::Hoenn => Generation(3),
+            Region::Sinnoh => Generation(4),
+            Region::Unova => Generation(5),
+            Region::Kalos => Generation(6),
+            Region::Alola => Generation(7),
+            Region::Galar | Region::Hisui => Generation(8),
+            Region::Paldea => Generation(9),
+        }
+    }
+}
+
+impl From<Region> for String {
+    fn from(region: Region) -> Self {
+        String::from(match region {
+            Region::Kanto => "Kanto",
+            Region::Johto => "Johto",
+            Region::Hoenn => "Hoenn",
+            Region::Sinnoh => "Sinnoh",
+            Region::Unova => "Unova",
+            Region::Kalos => "Kalos",
+            Region::Alola => "Alola",
+            Region::Galar => "Galar",
+            Region::Hisui => "Hisui",
+            Region::Paldea => "Paldea",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Generation(u8);
+
+impl Generation {
+    fn regions(self) -> impl Iterator<Item = Region> {
+        Region::ALL
+            .into_iter()
+            .filter(move |region| region.generation() == self)
+    }
+
+    /// First national number of the generation.
+    pub fn first(self) -> PokemonNumber {
+        PokemonNumber(self.regions().map(Region::first).min().unwrap_or_default())
+    }
+
+    /// Last national number of the generation.
+    pub fn last(self) -> PokemonNumber {
+        PokemonNumber(self.regions().map(Region::last).max().unwrap_or_default())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GenerationError {
+    Unknown(u8),
+}
+
+impl fmt::Display for GenerationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GenerationError::Unknown(n) => write!(f, "generation {n} is not between 1 and 9"),
+        }
+    }
+}
+
+impl ValidationError for GenerationError {
+    fn code(&self) -> &'static str {
+        match self {
+            GenerationError::Unknown(_) => "unknown_generation",
+        }
+    }
+}
+
+impl TryFrom<u8> for Generation {
+    type Error = GenerationError;
+
+    fn try_from(n: u8) -> Result<Self, Self::Error> {
+        match n {
+            1..=9 => Ok(Self(n)),
+            _ => Err(GenerationError::Unknown(n)),
+        }
+    }
+}
+
+impl From<Generation> for u8 {
+    fn from(generation: Generation) -> Self {
+        generation.0
+    }
+}
+
+/// Up to 32 lowercase letters, digits and dashes, safe to put in a path.
+fn is_slug(slug: &str) -> bool {
+    slug.len() <= 32
+        && slug
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+/// A regional form or alternate forme, such as `alola` or `wash`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PokemonForm(String);
+
+impl PokemonForm {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+impl PokemonForm {
+    pub fn alola() -> Self {
+        Self(String::from("alola"))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PokemonFormError {
+    Empty,
+    Invalid(String),
+}
+
+impl fmt::Display for PokemonFormError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PokemonFormError::Empty => write!(f, "form must not be empty"),
+            PokemonFormError::Invalid(form) => write!(
+                f,
+                "form {form:?} must only hold up to 32 letters, digits and dashes"
+            ),
+        }
+    }
+}
+
+impl ValidationError for PokemonFormError {
+    fn code(&self) -> &'static str {
+        match self {
+            PokemonFormError::Empty => "empty",
+            PokemonFormError::Invalid(_) => "invalid_form",
+        }
+    }
+}
+
+/// Forms are case-insensitive, and stored in lowercase.
+impl TryFrom<String> for PokemonForm {
+    type Error = PokemonFormError;
+
+    fn try_from(form: String) -> Result<Self, Self::Error> {
+        let slug = form.trim().to_ascii_lowercase();
+        if slug.is_empty() {
+            Err(PokemonFormError::Empty)
+        } else if !is_slug(&slug) {
+            Err(PokemonFormError::Invalid(form))
+        } else {
+            Ok(Self(slug))
+        }
+    }
+}
+
+impl From<PokemonForm> for String {
+    fn from(form: PokemonForm) -> Self {
+        form.0
+    }
+}
+
+/// What identifies a stored Pokemon: its national number and its form.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PokemonKey {
+    pub number: PokemonNumber,
+    /// Unset for the base form.
+    pub form: Option<PokemonForm>,
+}
+
+impl PokemonKey {
+    pub fn new(number: PokemonNumber, form: Option<PokemonForm>) -> Self {
+        Self { number, form }
+    }
+
+    pub fn is_base(&self) -> bool {
+        self.form.is_none()
+    }
+}
+
+/// `37` for the base form, `37 (alola)` for another form.
+impl fmt::Display for PokemonKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.form {
+            Some(form) => write!(f, "{} ({})", self.number.0, form.as_str()),
+            None => write!(f, "{}", self.number.0),
+        }
+    }
+}
+
+/// The base form of the national number.
+impl From<PokemonNumber> for PokemonKey {
+    fn from(number: PokemonNumber) -> Self {
+        Self::new(number, None)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct PokemonName(String);
 
+impl PokemonName {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
 #[cfg(test)]
 impl PokemonName {
     pub fn pikachu() -> Self {
@@ -91,12 +444,33 @@ impl PokemonName {
     }
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub enum PokemonNameError {
+    Empty,
+}
+
+impl fmt::Display for PokemonNameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PokemonNameError::Empty => write!(f, "name must not be empty"),
+        }
+    }
+}
+
+impl ValidationError for PokemonNameError {
+    fn code(&self) -> &'static str {
+        match self {
+            PokemonNameError::Empty => "empty",
+        }
+    }
+}
+
 impl TryFrom<String> for PokemonName {
-    type Error = ();
+    type Error = PokemonNameError;
 
     fn try_from(name: String) -> Result<Self, Self::Error> {
         if name.is_empty() {
-            Err(())
+            Err(PokemonNameError::Empty)
         } else {
             Ok(Self(name))
         }
@@ -109,39 +483,126 @@ impl From<PokemonName> for String {
     }
 }
 
-#[derive(Clone, Debug)]
-enum PokemonType {
-    Electric,
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PokemonType {
+    Normal,
     Fire,
+    Water,
+    Electric,
     Grass,
+    Ice,
+    Fighting,
+    Poison,
+    Ground,
+    Flying,
+    Psychic,
+    Bug,
+    Rock,
+    Ghost,
+    Dragon,
+    Dark,
+    Steel,
+    Fairy,
+}
+
+impl PokemonType {
+    pub const ALL: [PokemonType; 18] = [
+        PokemonType::Normal,
+        PokemonType::Fire,
+        PokemonType::Water,
+        PokemonType::Electric,
+        PokemonType::Grass,
+        PokemonType::Ice,
+        PokemonType::Fighting,
+        PokemonType::Poison,
+        PokemonType::Ground,
+        PokemonType::Flying,
+        PokemonType::Psychic,
+        PokemonType::Bug,
+        PokemonType::Rock,
+        PokemonType::Ghost,
+        PokemonType::Dragon,
+        PokemonType::Dark,
+        PokemonType::Steel,
+        PokemonType::Fairy,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PokemonType::Normal => "Normal",
+            PokemonType::Fire => "Fire",
+            PokemonType::Water => "Water",
+            PokemonType::Electric => "Electric",
+            PokemonType::Grass => "Grass",
+            PokemonType::Ice => "Ice",
+            PokemonType::Fighting => "Fighting",
+            PokemonType::Poison => "Poison",
+            PokemonType::Ground => "Ground",
+            PokemonType::Flying => "Flying",
+            PokemonType::Psychic => "Psychic",
+            PokemonType::Bug => "Bug",
+            PokemonType::Rock => "Rock",
+            PokemonType::Ghost => "Ghost",
+            PokemonType::Dragon => "Dragon",
+            PokemonType::Dark => "Dark",
+            PokemonType::Steel => "Steel",
+            PokemonType::Fairy => "Fairy",
+        }
+    }
+
+    /// Canonical names of every valid type, in type chart order.
+    pub fn names() -> Vec<&'static str> {
+        Self::ALL.iter().map(PokemonType::name).collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PokemonTypeError {
+    Unknown(String),
+}
+
+impl fmt::Display for PokemonTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PokemonTypeError::Unknown(tipe) => write!(f, "{tipe:?} is not a Pokemon type"),
+        }
+    }
+}
+
+impl ValidationError for PokemonTypeError {
+    fn code(&self) -> &'static str {
+        match self {
+            PokemonTypeError::Unknown(_) => "unknown_type",
+        }
+    }
 }
 
 impl TryFrom<String> for PokemonType {
-    type Error = ();
+    type Error = PokemonTypeError;
 
     fn try_from(tipe: String) -> Result<Self, Self::Error> {
-        match tipe.as_str() {
-            "Electric" => Ok(PokemonType::Electric),
-            "Fire" => Ok(PokemonType::Fire),
-            "Grass" => Ok(PokemonType::Grass),
-            _ => Err(()),
-        }
+        Self::ALL
+            .into_iter()
+            .find(|t| t.name().eq_ignore_ascii_case(tipe.trim()))
+            .ok_or(PokemonTypeError::Unknown(tipe))
     }
 }
 
 impl From<PokemonType> for String {
     fn from(tipe: PokemonType) -> Self {
-        match tipe {
-            PokemonType::Electric => "Electric".to_owned(),
-            PokemonType::Fire => "Fire".to_owned(),
-            PokemonType::Grass => "Grass".to_owned(),
-        }
+        tipe.name().to_owned()
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct PokemonTypes(Vec<PokemonType>);
 
+impl PokemonTypes {
+    pub fn as_slice(&self) -> &[PokemonType] {
+        &self.0
+    }
+}
+
 #[cfg(test)]
 impl PokemonTypes {
     pub fn pikachu() -> Self {
@@ -151,20 +612,60 @@ impl PokemonTypes {
     pub fn vulpix() -> Self {
         Self(vec![PokemonType::Fire])
     }
+
+    pub fn alolan_vulpix() -> Self {
+        Self(vec![PokemonType::Ice])
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PokemonTypesError {
+    Empty,
+    TooMany(usize),
+    Duplicated(PokemonType),
+    Unknown(PokemonTypeError),
+}
+
+impl fmt::Display for PokemonTypesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PokemonTypesError::Empty => write!(f, "at least one type is required"),
+            PokemonTypesError::TooMany(n) => write!(f, "{n} types given, at most 2 are allowed"),
+            PokemonTypesError::Duplicated(tipe) => write!(f, "{} is given twice", tipe.name()),
+            PokemonTypesError::Unknown(e) => e.fmt(f),
+        }
+    }
+}
+
+impl ValidationError for PokemonTypesError {
+    fn code(&self) -> &'static str {
+        match self {
+            PokemonTypesError::Empty => "empty",
+            PokemonTypesError::TooMany(_) => "too_many",
+            PokemonTypesError::Duplicated(_) => "duplicated",
+            PokemonTypesError::Unknown(e) => e.code(),
+        }
+    }
 }
 
 impl TryFrom<Vec<String>> for PokemonTypes {
-    type Error = ();
+    type Error = PokemonTypesError;
     fn try_from(types: Vec<String>) -> Result<Self, Self::Error> {
         if types.is_empty() {
-            return Err(());
+            return Err(PokemonTypesError::Empty);
+        }
+        if types.len() > 2 {
+            return Err(PokemonTypesError::TooMany(types.len()));
         }
 
         let mut pokemon_types: Vec<PokemonType> = Vec::with_capacity(types.len());
         for tipe in types.into_iter() {
             match PokemonType::try_from(tipe) {
+                Ok(pokemon_type) if pokemon_types.contains(&pokemon_type) => {
+                    return Err(PokemonTypesError::Duplicated(pokemon_type))
+                }
                 Ok(pokemon_type) => pokemon_types.push(pokemon_type),
-                _ => return Err(()),
+                Err(e) => return Err(PokemonTypesError::Unknown(e)),
             }
         }
 
@@ -177,3 +678,1151 @@ impl From<PokemonTypes> for Vec<String> {
         types.0.into_iter().map(String::from).collect()
     }
 }
+
+/// Base stats in the order HP, Attack, Defense, Sp. Atk, Sp. Def and Speed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PokemonStats([u16; 6]);
+
+impl PokemonStats {
+    pub const NAMES: [&'static str; 6] = [
+        "hp",
+        "attack",
+        "defense",
+        "special_attack",
+        "special_defense",
+        "speed",
+    ];
+
+    pub fn hp(&self) -> u16 {
+        self.0[0]
+    }
+
+    pub fn attack(&self) -> u16 {
+        self.0[1]
+    }
+
+    pub fn defense(&self) -> u16 {
+        self.0[2]
+    }
+
+    pub fn special_attack(&self) -> u16 {
+        self.0[3]
+    }
+
+    pub fn special_defense(&self) -> u16 {
+        self.0[4]
+    }
+
+    pub fn speed(&self) -> u16 {
+        self.0[5]
+    }
+
+    pub fn total(&self) -> u16 {
+        self.0.iter().sum()
+    }
+}
+
+#[cfg(test)]
+impl PokemonStats {
+    pub fn pikachu() -> Self {
+        Self([35, 55, 40, 50, 50, 90])
+    }
+
+    pub fn vulpix() -> Self {
+        Self([38, 41, 40, 50, 65, 65])
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PokemonStatsError {
+    OutOfRange { stat: &'static str, value: u16 },
+}
+
+impl fmt::Display for PokemonStatsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PokemonStatsError::OutOfRange { stat, value } => {
+                write!(f, "{stat} {value} is not between 1 and 255")
+            }
+        }
+    }
+}
+
+impl ValidationError for PokemonStatsError {
+    fn code(&self) -> &'static str {
+        match self {
+            PokemonStatsError::OutOfRange { .. } => "out_of_range",
+        }
+    }
+}
+
+impl TryFrom<[u16; 6]> for PokemonStats {
+    type Error = PokemonStatsError;
+
+    fn try_from(stats: [u16; 6]) -> Result<Self, Self::Error> {
+        for (stat, value) in Self::NAMES.into_iter().zip(stats) {
+            if value == 0 || value > 255 {
+                return Err(PokemonStatsError::OutOfRange { stat, value });
+            }
+        }
+        Ok(Self(stats))
+    }
+}
+
+impl From<PokemonStats> for [u16; 6] {
+    fn from(stats: PokemonStats) -> Self {
+        stats.0
+    }
+}
+
+/// What makes a Pokemon evolve into the next stage.
+#[derive(Clone, Debug, PartialEq)]
+pub enum EvolutionTrigger {
+    /// Reaching the given level.
+    Level(u8),
+    /// Being exposed to the named evolution stone.
+    Stone(String),
+    Trade,
+    Friendship,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum EvolutionTriggerError {
+    Unknown(String),
+    LevelOutOfRange(String),
+    MissingStone,
+}
+
+impl fmt::Display for EvolutionTriggerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvolutionTriggerError::Unknown(trigger) => write!(
+                f,
+                "{trigger:?} is not one of level:N, stone:NAME, trade or friendship"
+            ),
+            EvolutionTriggerError::LevelOutOfRange(level) => {
+                write!(f, "level {level} is not between 1 and 100")
+            }
+            EvolutionTriggerError::MissingStone => write!(f, "the stone must be named"),
+        }
+    }
+}
+
+impl ValidationError for EvolutionTriggerError {
+    fn code(&self) -> &'static str {
+        match self {
+            EvolutionTriggerError::Unknown(_) => "unknown_trigger",
+            EvolutionTriggerError::LevelOutOfRange(_) => "out_of_range",
+            EvolutionTriggerError::MissingStone => "empty",
+        }
+    }
+}
+
+/// Parses `level:16`, `stone:Thunder Stone`, `trade` or `friendship`.
+impl TryFrom<String> for EvolutionTrigger {
+    type Error = EvolutionTriggerError;
+
+    fn try_from(trigger: String) -> Result<Self, Self::Error> {
+        let (kind, value) = match trigger.split_once(':') {
+            Some((kind, value)) => (kind.trim(), Some(value.trim())),
+            None => (trigger.trim(), None),
+        };
+        match (kind.to_ascii_lowercase().as_str(), value) {
+            ("level", Some(level)) => match level.parse::<u8>() {
+                Ok(level @ 1..=100) => Ok(Self::Level(level)),
+                _ => Err(EvolutionTriggerError::LevelOutOfRange(level.to_owned())),
+            },
+            ("stone", Some(stone)) if !stone.is_empty() => Ok(Self::Stone(stone.to_owned())),
+            ("stone", _) => Err(EvolutionTriggerError::MissingStone),
+            ("trade", None) => Ok(Self::Trade),
+            ("friendship", None) => Ok(Self::Friendship),
+            _ => Err(EvolutionTriggerError::Unknown(trigger)),
+        }
+    }
+}
+
+impl From<EvolutionTrigger> for String {
+    fn from(trigger: EvolutionTrigger) -> Self {
+        match trigger {
+            EvolutionTrigger::Level(level) => format!("level:{level}"),
+            EvolutionTrigger::Stone(stone) => format!("stone:{stone}"),
+            EvolutionTrigger::Trade => String::from("trade"),
+            EvolutionTrigger::Friendship => String::from("friendship"),
+        }
+    }
+}
+
+/// A link from a Pokemon to the one it evolves into. Conditions are free-form
+/// requirements on top of the trigger, like `daytime` or `knows Ancient Power`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Evolution {
+    pub from: PokemonNumber,
+    pub to: PokemonNumber,
+    pub trigger: EvolutionTrigger,
+    pub conditions: Vec<String>,
+}
+
+impl Evolution {
+    pub fn new(
+        from: PokemonNumber,
+        to: PokemonNumber,
+        trigger: EvolutionTrigger,
+        conditions: Vec<String>,
+    ) -> Self {
+        Self {
+            from,
+            to,
+            trigger,
+            conditions,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MoveId(u16);
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MoveIdError {
+    Zero,
+}
+
+impl fmt::Display for MoveIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveIdError::Zero => write!(f, "move ids start at 1"),
+        }
+    }
+}
+
+impl ValidationError for MoveIdError {
+    fn code(&self) -> &'static str {
+        match self {
+            MoveIdError::Zero => "out_of_range",
+        }
+    }
+}
+
+impl TryFrom<u16> for MoveId {
+    type Error = MoveIdError;
+
+    fn try_from(id: u16) -> Result<Self, Self::Error> {
+        match id {
+            0 => Err(MoveIdError::Zero),
+            id => Ok(Self(id)),
+        }
+    }
+}
+
+impl From<MoveId> for u16 {
+    fn from(id: MoveId) -> Self {
+        id.0
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MoveCategory {
+    Physical,
+    Special,
+    Status,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MoveCategoryError {
+    Unknown(String),
+}
+
+impl fmt::Display for MoveCategoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveCategoryError::Unknown(category) => {
+                write!(f, "{category:?} is not one of physical, special or status")
+            }
+        }
+    }
+}
+
+impl ValidationError for MoveCategoryError {
+    fn code(&self) -> &'static str {
+        match self {
+            MoveCategoryError::Unknown(_) => "unknown_category",
+        }
+    }
+}
+
+impl TryFrom<String> for MoveCategory {
+    type Error = MoveCategoryError;
+
+    fn try_from(category: String) -> Result<Self, Self::Error> {
+        match category.trim().to_ascii_lowercase().as_str() {
+            "physical" => Ok(Self::Physical),
+            "special" => Ok(Self::Special),
+            "status" => Ok(Self::Status),
+            _ => Err(MoveCategoryError::Unknown(category)),
+        }
+    }
+}
+
+impl From<MoveCategory> for String {
+    fn from(category: MoveCategory) -> Self {
+        match category {
+            MoveCategory::Physical => String::from("physical"),
+            MoveCategory::Special => String::from("special"),
+            MoveCategory::Status => String::from("status"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MoveValueError {
+    OutOfRange { value: u16, max: u16 },
+}
+
+impl fmt::Display for MoveValueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveValueError::OutOfRange { value, max } => {
+                write!(f, "{value} is not between 1 and {max}")
+            }
+        }
+    }
+}
+
+impl ValidationError for MoveValueError {
+    fn code(&self) -> &'static str {
+        match self {
+            MoveValueError::OutOfRange { .. } => "out_of_range",
+        }
+    }
+}
+
+fn move_value(value: u16, max: u16) -> Result<u8, MoveValueError> {
+    match value {
+        1.. if value <= max => Ok(value as u8),
+        _ => Err(MoveValueError::OutOfRange { value, max }),
+    }
+}
+
+/// Base power, unset for moves whose damage doesn't come from it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MovePower(u8);
+
+impl TryFrom<u16> for MovePower {
+    type Error = MoveValueError;
+
+    fn try_from(power: u16) -> Result<Self, Self::Error> {
+        move_value(power, 250).map(Self)
+    }
+}
+
+impl From<MovePower> for u16 {
+    fn from(power: MovePower) -> Self {
+        power.0 as u16
+    }
+}
+
+/// Accuracy in percent, unset for moves that never miss.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MoveAccuracy(u8);
+
+impl TryFrom<u16> for MoveAccuracy {
+    type Error = MoveValueError;
+
+    fn try_from(accuracy: u16) -> Result<Self, Self::Error> {
+        move_value(accuracy, 100).map(Self)
+    }
+}
+
+impl From<MoveAccuracy> for u16 {
+    fn from(accuracy: MoveAccuracy) -> Self {
+        accuracy.0 as u16
+    }
+}
+
+/// Base power points, before any PP Up.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MovePp(u8);
+
+impl TryFrom<u16> for MovePp {
+    type Error = MoveValueError;
+
+    fn try_from(pp: u16) -> Result<Self, Self::Error> {
+        move_value(pp, 40).map(Self)
+    }
+}
+
+impl From<MovePp> for u16 {
+    fn from(pp: MovePp) -> Self {
+        pp.0 as u16
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Move {
+    pub id: MoveId,
+    /// Reuses the validation of Pokemon names.
+    pub name: PokemonName,
+    pub tipe: PokemonType,
+    pub category: MoveCategory,
+    pub power: Option<MovePower>,
+    pub accuracy: Option<MoveAccuracy>,
+    pub pp: MovePp,
+}
+
+impl Move {
+    pub fn new(
+        id: MoveId,
+        name: PokemonName,
+        tipe: PokemonType,
+        category: MoveCategory,
+        power: Option<MovePower>,
+        accuracy: Option<MoveAccuracy>,
+        pp: MovePp,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            tipe,
+            category,
+            power,
+            accuracy,
+            pp,
+        }
+    }
+}
+
+#[cfg(test)]
+impl Move {
+    pub fn thunder_shock() -> Self {
+        Self {
+            id: MoveId(84),
+            name: PokemonName(String::from("Thunder Shock")),
+            tipe: PokemonType::Electric,
+            category: MoveCategory::Special,
+            power: Some(MovePower(40)),
+            accuracy: Some(MoveAccuracy(100)),
+            pp: MovePp(30),
+        }
+    }
+
+    pub fn swift() -> Self {
+        Self {
+            id: MoveId(129),
+            name: PokemonName(String::from("Swift")),
+            tipe: PokemonType::Normal,
+            category: MoveCategory::Special,
+            power: Some(MovePower(60)),
+            accuracy: None,
+            pp: MovePp(20),
+        }
+    }
+}
+
+/// How a Pokemon learns a move. Ordered the way learnsets are listed:
+/// level-up moves by level first, then machines, eggs and tutors.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LearnMethod {
+    LevelUp(u8),
+    Machine,
+    Egg,
+    Tutor,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum LearnMethodError {
+    Unknown(String),
+    LevelOutOfRange(String),
+}
+
+impl fmt::Display for LearnMethodError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LearnMethodError::Unknown(method) => {
+                write!(f, "{method:?} is not one of level:N, machine, egg or tutor")
+            }
+            LearnMethodError::LevelOutOfRange(level) => {
+                write!(f, "level {level} is not between 1 and 100")
+            }
+        }
+    }
+}
+
+impl ValidationError for LearnMethodError {
+    fn code(&self) -> &'static str {
+        match self {
+            LearnMethodError::Unknown(_) => "unknown_method",
+            LearnMethodError::LevelOutOfRange(_) => "out_of_range",
+        }
+    }
+}
+
+/// Parses `level:12`, `machine`, `egg` or `tutor`.
+impl TryFrom<String> for LearnMethod {
+    type Error = LearnMethodError;
+
+    fn try_from(method: String) -> Result<Self, Self::Error> {
+        let (kind, level) = match method.split_once(':') {
+            Some((kind, level)) => (kind.trim(), Some(level.trim())),
+            None => (method.trim(), None),
+        };
+        match (kind.to_ascii_lowercase().as_str(), level) {
+            ("level", Some(level)) => match level.parse::<u8>() {
+                Ok(level @ 1..=100) => Ok(Self::LevelUp(level)),
+                _ => Err(LearnMethodError::LevelOutOfRange(level.to_owned())),
+            },
+            ("machine", None) => Ok(Self::Machine),
+            ("egg", None) => Ok(Self::Egg),
+            ("tutor", None) => Ok(Self::Tutor),
+            _ => Err(LearnMethodError::Unknown(method)),
+        }
+    }
+}
+
+impl From<LearnMethod> for String {
+    fn from(method: LearnMethod) -> Self {
+        match method {
+            LearnMethod::LevelUp(level) => format!("level:{level}"),
+            LearnMethod::Machine => String::from("machine"),
+            LearnMethod::Egg => String::from("egg"),
+            LearnMethod::Tutor => String::from("tutor"),
+        }
+    }
+}
+
+/// One way a Pokemon learns one move.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LearnsetEntry {
+    pub move_id: MoveId,
+    pub method: LearnMethod,
+}
+
+impl LearnsetEntry {
+    pub fn new(move_id: MoveId, method: LearnMethod) -> Self {
+        Self { move_id, method }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ability {
+    /// Reuses the validation of Pokemon names, and identifies the ability.
+    pub name: PokemonName,
+    pub description: String,
+}
+
+impl Ability {
+    pub fn new(name: PokemonName, description: String) -> Self {
+        Self { name, description }
+    }
+}
+
+#[cfg(test)]
+impl Ability {
+    pub fn static_() -> Self {
+        Self {
+            name: PokemonName(String::from("Static")),
+            description: String::from("Contact with the Pokemon may cause paralysis."),
+        }
+    }
+
+    pub fn lightning_rod() -> Self {
+        Self {
+            name: PokemonName(String::from("Lightning Rod")),
+            description: String::from("Draws in all Electric-type moves."),
+        }
+    }
+}
+
+/// Up to two regular abilities and at most one hidden ability, all distinct.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PokemonAbilities {
+    regular: Vec<PokemonName>,
+    hidden: Option<PokemonName>,
+}
+
+impl PokemonAbilities {
+    pub fn regular(&self) -> &[PokemonName] {
+        &self.regular
+    }
+
+    pub fn hidden(&self) -> Option<&PokemonName> {
+        self.hidden.as_ref()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regular.is_empty() && self.hidden.is_none()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PokemonAbilitiesError {
+    TooManyRegular(usize),
+    Duplicate(String),
+    Name(PokemonNameError),
+}
+
+impl fmt::Display for PokemonAbilitiesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PokemonAbilitiesError::TooManyRegular(count) => {
+                write!(f, "a Pokemon has at most 2 regular abilities, not {count}")
+            }
+            PokemonAbilitiesError::Duplicate(name) => write!(f, "{name:?} is listed twice"),
+            PokemonAbilitiesError::Name(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl ValidationError for PokemonAbilitiesError {
+    fn code(&self) -> &'static str {
+        match self {
+            PokemonAbilitiesError::TooManyRegular(_) => "too_many",
+            PokemonAbilitiesError::Duplicate(_) => "duplicate",
+            PokemonAbilitiesError::Name(e) => e.code(),
+        }
+    }
+}
+
+/// Takes the regular abilities, then the hidden one.
+impl TryFrom<(Vec<String>, Option<String>)> for PokemonAbilities {
+    type Error = PokemonAbilitiesError;
+
+    fn try_from((regular, hidden): (Vec<String>, Option<String>)) -> Result<Self, Self::Error> {
+        if regular.len() > 2 {
+            return Err(PokemonAbilitiesError::TooManyRegular(regular.len()));
+        }
+        let name = |name: String| PokemonName::try_from(name).map_err(PokemonAbilitiesError::Name);
+        let regular = regular.into_iter().map(name).collect::<Result<Vec<_>, _>>()?;
+        let hidden = hidden.map(name).transpose()?;
+
+        let mut seen: Vec<&PokemonName> = vec![];
+        for ability in regular.iter().chain(hidden.iter()) {
+            if seen.iter().any(|other| other.as_str().eq_ignore_ascii_case(ability.as_str())) {
+                return Err(PokemonAbilitiesError::Duplicate(String::from(ability.clone())));
+            }
+            seen.push(ability);
+        }
+        Ok(Self { regular, hidden })
+    }
+}
+
+impl From<PokemonAbilities> for (Vec<String>, Option<String>) {
+    fn from(abilities: PokemonAbilities) -> Self {
+        (
+            abilities.regular.into_iter().map(String::from).collect(),
+            abilities.hidden.map(String::from),
+        )
+    }
+}
+
+/// The name of a regional Pokedex, such as `johto` or `paldea`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DexName(String);
+
+impl DexName {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+impl DexName {
+    pub fn johto() -> Self {
+        Self(String::from("johto"))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DexNameError {
+    Empty,
+    Invalid(String),
+}
+
+impl fmt::Display for DexNameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DexNameError::Empty => write!(f, "dex name must not be empty"),
+            DexNameError::Invalid(name) => write!(
+                f,
+                "dex name {name:?} must only hold up to 32 letters, digits and dashes"
+            ),
+        }
+    }
+}
+
+impl ValidationError for DexNameError {
+    fn code(&self) -> &'static str {
+        match self {
+            DexNameError::Empty => "empty",
+            DexNameError::Invalid(_) => "invalid_dex_name",
+        }
+    }
+}
+
+/// Dex names are case-insensitive, and stored in lowercase.
+impl TryFrom<String> for DexName {
+    type Error = DexNameError;
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        let slug = name.trim().to_ascii_lowercase();
+        if slug.is_empty() {
+            Err(DexNameError::Empty)
+        } else if !is_slug(&slug) {
+            Err(DexNameError::Invalid(name))
+        } else {
+            Ok(Self(slug))
+        }
+    }
+}
+
+impl From<DexName> for String {
+    fn from(name: DexName) -> Self {
+        name.0
+    }
+}
+
+/// A position in a regional dex. Some start at 0, as Victini does in Unova,
+/// and none holds more Pokemons than the national dex.
+/// Indices read back from storage are taken as they are.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DexIndex(u16);
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DexIndexError {
+    OutOfRange { value: u16, max: u16 },
+}
+
+impl fmt::Display for DexIndexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DexIndexError::OutOfRange { value, max } => {
+                write!(f, "index {value} is not between 0 and {max}")
+            }
+        }
+    }
+}
+
+impl ValidationError for DexIndexError {
+    fn code(&self) -> &'static str {
+        match self {
+            DexIndexError::OutOfRange { .. } => "out_of_range",
+        }
+    }
+}
+
+impl DexIndex {
+    /// Validates an index given by a user against the configured national dex limit.
+    pub fn within(index: u16, max: u16) -> Result<Self, DexIndexError> {
+        match index <= max {
+            true => Ok(Self(index)),
+            false => Err(DexIndexError::OutOfRange { value: index, max }),
+        }
+    }
+}
+
+impl From<u16> for DexIndex {
+    fn from(index: u16) -> Self {
+        Self(index)
+    }
+}
+
+impl From<DexIndex> for u16 {
+    fn from(index: DexIndex) -> Self {
+        index.0
+    }
+}
+
+/// The national number found at an index of a regional dex.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DexEntry {
+    pub index: DexIndex,
+    pub number: PokemonNumber,
+}
+
+impl DexEntry {
+    pub fn new(index: DexIndex, number: PokemonNumber) -> Self {
+        Self { index, number }
+    }
+}
+
+/// Identifies a trainer account, such as `red`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TrainerId(String);
+
+impl TrainerId {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TrainerIdError {
+    Empty,
+    Invalid(String),
+}
+
+impl fmt::Display for TrainerIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrainerIdError::Empty => write!(f, "trainer id must not be empty"),
+            TrainerIdError::Invalid(id) => write!(
+                f,
+                "trainer id {id:?} must only hold up to 32 letters, digits and dashes"
+            ),
+        }
+    }
+}
+
+impl ValidationError for TrainerIdError {
+    fn code(&self) -> &'static str {
+        match self {
+            TrainerIdError::Empty => "empty",
+            TrainerIdError::Invalid(_) => "invalid_trainer_id",
+        }
+    }
+}
+
+/// Trainer ids are case-insensitive, and stored in lowercase.
+impl TryFrom<String> for TrainerId {
+    type Error = TrainerIdError;
+
+    fn try_from(id: String) -> Result<Self, Self::Error> {
+        let slug = id.trim().to_ascii_lowercase();
+        if slug.is_empty() {
+            Err(TrainerIdError::Empty)
+        } else if !is_slug(&slug) {
+            Err(TrainerIdError::Invalid(id))
+        } else {
+            Ok(Self(slug))
+        }
+    }
+}
+
+impl From<TrainerId> for String {
+    fn from(id: TrainerId) -> Self {
+        id.0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trainer {
+    pub id: TrainerId,
+    /// Reuses the validation of Pokemon names.
+    pub name: PokemonName,
+}
+
+impl Trainer {
+    pub fn new(id: TrainerId, name: PokemonName) -> Self {
+        Self { id, name }
+    }
+}
+
+#[cfg(test)]
+impl Trainer {
+    pub fn red() -> Self {
+        Self {
+            id: TrainerId(String::from("red")),
+            name: PokemonName(String::from("Red")),
+        }
+    }
+}
+
+/// What a trainer can record about a Pokemon. Each mark implies the previous
+/// ones: a caught Pokemon was seen, and a shiny one was caught.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DexMark {
+    Seen,
+    Caught,
+    Shiny,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DexMarkError {
+    Unknown(String),
+}
+
+impl fmt::Display for DexMarkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DexMarkError::Unknown(mark) => {
+                write!(f, "{mark:?} is not one of seen, caught and shiny")
+            }
+        }
+    }
+}
+
+impl ValidationError for DexMarkError {
+    fn code(&self) -> &'static str {
+        match self {
+            DexMarkError::Unknown(_) => "unknown_mark",
+        }
+    }
+}
+
+impl TryFrom<String> for DexMark {
+    type Error = DexMarkError;
+
+    fn try_from(mark: String) -> Result<Self, Self::Error> {
+        match mark.to_lowercase().as_str() {
+            "seen" => Ok(DexMark::Seen),
+            "caught" => Ok(DexMark::Caught),
+            "shiny" => Ok(DexMark::Shiny),
+            _ => Err(DexMarkError::Unknown(mark)),
+        }
+    }
+}
+
+/// The marks a trainer has set on one Pokemon, always consistent with each other.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DexStatus {
+    seen: bool,
+    caught: bool,
+    shiny: bool,
+}
+
+impl DexStatus {
+    pub fn seen(&self) -> bool {
+        self.seen
+    }
+
+    pub fn caught(&self) -> bool {
+        self.caught
+    }
+
+    pub fn shiny(&self) -> bool {
+        self.shiny
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.seen
+    }
+
+    /// Also sets the marks implied by `mark`.
+    pub fn mark(self, mark: DexMark) -> Self {
+        match mark {
+            DexMark::Seen => Self { seen: true, ..self },
+            DexMark::Caught => Self {
+                seen: true,
+                caught: true,
+                ..self
+            },
+            DexMark::Shiny => Self {
+                seen: true,
+                caught: true,
+                shiny: true,
+            },
+        }
+    }
+
+    /// Also clears the marks implying `mark`.
+    pub fn unmark(self, mark: DexMark) -> Self {
+        match mark {
+            DexMark::Seen => Self::default(),
+            DexMark::Caught => Self {
+                caught: false,
+                shiny: false,
+                ..self
+            },
+            DexMark::Shiny => Self {
+                shiny: false,
+                ..self
+            },
+        }
+    }
+}
+
+/// Takes the seen, caught and shiny flags, and sets the ones they imply.
+impl From<(bool, bool, bool)> for DexStatus {
+    fn from((seen, caught, shiny): (bool, bool, bool)) -> Self {
+        Self {
+            seen: seen || caught || shiny,
+            caught: caught || shiny,
+            shiny,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_parse_every_type_name() {
+        for name in PokemonType::names() {
+            let tipe = PokemonType::try_from(name.to_owned()).expect("type should be valid");
+            assert_eq!(String::from(tipe), name);
+        }
+    }
+
+    #[test]
+    fn it_should_parse_types_ignoring_case() {
+        let types = PokemonTypes::try_from(vec!["water".to_owned(), "PSYCHIC".to_owned()])
+            .expect("types should be valid");
+
+        assert_eq!(Vec::<String>::from(types), vec!["Water", "Psychic"]);
+    }
+
+    #[test]
+    fn it_should_reject_more_than_two_types() {
+        let types = vec!["Fire".to_owned(), "Water".to_owned(), "Grass".to_owned()];
+
+        assert!(PokemonTypes::try_from(types).is_err());
+    }
+
+    #[test]
+    fn it_should_reject_duplicated_types() {
+        let types = vec!["Fire".to_owned(), "fire".to_owned()];
+
+        assert!(PokemonTypes::try_from(types).is_err());
+    }
+
+    #[test]
+    fn it_should_accept_numbers_up_to_the_limit() {
+        assert!(PokemonNumber::within(1025, 1025).is_ok());
+        assert_eq!(
+            PokemonNumber::within(906, 898),
+            Err(PokemonNumberError::OutOfRange { value: 906, max: 898 })
+        );
+        assert!(PokemonNumber::within(0, 1025).is_err());
+        assert!(PokemonNumber::try_from(0).is_err());
+        assert!(PokemonNumber::try_from(2000).is_ok());
+        assert_eq!(
+            PokemonNumber::within(1026, 1025).unwrap_err().to_string(),
+            "number 1026 is not between 1 and 1025"
+        );
+    }
+
+    #[test]
+    fn it_should_derive_the_generation_and_region_from_the_number() {
+        let lookup = |n: u16| {
+            let number = PokemonNumber(n);
+            (number.generation().map(u8::from), number.region().map(String::from))
+        };
+
+        assert_eq!(lookup(1), (Some(1), Some(String::from("Kanto"))));
+        assert_eq!(lookup(152), (Some(2), Some(String::from("Johto"))));
+        assert_eq!(lookup(898), (Some(8), Some(String::from("Galar"))));
+        assert_eq!(lookup(899), (Some(8), Some(String::from("Hisui"))));
+        assert_eq!(lookup(1025), (Some(9), Some(String::from("Paldea"))));
+        assert_eq!(lookup(1026), (None, None));
+    }
+
+    #[test]
+    fn it_should_span_every_region_of_a_generation() {
+        let galar = Generation::try_from(8).unwrap();
+
+        assert_eq!(u16::from(galar.first()), 810);
+        assert_eq!(u16::from(galar.last()), 905);
+        assert_eq!(Generation::try_from(10).unwrap_err().code(), "unknown_generation");
+    }
+
+    #[test]
+    fn it_should_compute_the_base_stat_total() {
+        assert_eq!(PokemonStats::pikachu().total(), 320);
+    }
+
+    #[test]
+    fn it_should_reject_stats_out_of_bounds() {
+        assert!(PokemonStats::try_from([0, 55, 40, 50, 50, 90]).is_err());
+        assert!(PokemonStats::try_from([35, 55, 40, 50, 50, 256]).is_err());
+        assert!(PokemonStats::try_from([255, 255, 255, 255, 255, 255]).is_ok());
+    }
+
+    #[test]
+    fn it_should_reject_unknown_types() {
+        let err = PokemonType::try_from("Shadow".to_owned()).expect_err("type should be invalid");
+
+        assert_eq!(err, PokemonTypeError::Unknown("Shadow".to_owned()));
+        assert_eq!(err.code(), "unknown_type");
+    }
+
+    #[test]
+    fn it_should_describe_the_invalid_field() {
+        let err = PokemonStats::try_from([35, 55, 40, 50, 50, 0]).expect_err("stats should be invalid");
+        let field = FieldError::new("stats", &err);
+
+        assert_eq!(field.field, "stats");
+        assert_eq!(field.code, "out_of_range");
+        assert_eq!(field.message, "speed 0 is not between 1 and 255");
+    }
+
+    #[test]
+    fn it_should_parse_evolution_triggers() {
+        let parse = |trigger: &str| EvolutionTrigger::try_from(trigger.to_owned());
+
+        assert_eq!(parse("level:16"), Ok(EvolutionTrigger::Level(16)));
+        assert_eq!(
+            parse("Stone: Thunder Stone"),
+            Ok(EvolutionTrigger::Stone(String::from("Thunder Stone")))
+        );
+        assert_eq!(parse("trade"), Ok(EvolutionTrigger::Trade));
+        assert_eq!(String::from(parse("friendship").unwrap()), "friendship");
+        assert!(matches!(parse("level:0"), Err(EvolutionTriggerError::LevelOutOfRange(_))));
+        assert!(matches!(parse("stone:"), Err(EvolutionTriggerError::MissingStone)));
+        assert!(matches!(parse("trade:yes"), Err(EvolutionTriggerError::Unknown(_))));
+    }
+
+    #[test]
+    fn it_should_order_learn_methods_like_a_learnset() {
+        let parse = |method: &str| LearnMethod::try_from(method.to_owned()).unwrap();
+        let mut methods = vec![parse("tutor"), parse("level:20"), parse("egg"), parse("level:5")];
+
+        methods.sort();
+
+        let methods = methods.into_iter().map(String::from).collect::<Vec<String>>();
+        assert_eq!(methods, vec!["level:5", "level:20", "egg", "tutor"]);
+        assert!(LearnMethod::try_from(String::from("level:101")).is_err());
+        assert!(MovePower::try_from(251).is_err());
+        assert!(MoveAccuracy::try_from(0).is_err());
+    }
+
+    #[test]
+    fn it_should_name_regional_dexes_in_lowercase() {
+        assert_eq!(DexName::try_from(String::from(" Johto ")), Ok(DexName::johto()));
+        assert_eq!(DexName::try_from(String::from("")).unwrap_err().code(), "empty");
+        assert_eq!(
+            DexName::try_from(String::from("johto/gsc")).unwrap_err().code(),
+            "invalid_dex_name"
+        );
+        assert!(DexIndex::within(0, 1025).is_ok());
+        assert!(DexIndex::within(1026, 1025).is_err());
+    }
+
+    #[test]
+    fn it_should_keep_dex_marks_consistent() {
+        let status = DexStatus::default().mark(DexMark::Shiny);
+
+        assert_eq!(status, DexStatus::from((false, false, true)));
+        assert!(status.seen() && status.caught());
+        assert_eq!(status.unmark(DexMark::Caught), DexStatus::from((true, false, false)));
+        assert!(status.unmark(DexMark::Seen).is_empty());
+        assert_eq!(
+            DexMark::try_from(String::from("Caught")),
+            Ok(DexMark::Caught)
+        );
+        assert_eq!(
+            TrainerId::try_from(String::from("Red")).unwrap(),
+            Trainer::red().id
+        );
+    }
+
+    #[test]
+    fn it_should_limit_and_deduplicate_abilities() {
+        let abilities = |regular: &[&str], hidden: Option<&str>| {
+            PokemonAbilities::try_from((
+                regular.iter().map(|name| String::from(*name)).collect(),
+                hidden.map(String::from),
+            ))
+        };
+
+        assert!(abilities(&["Static"], Some("Lightning Rod")).is_ok());
+        assert!(abilities(&[], None).unwrap().is_empty());
+        assert_eq!(
+            abilities(&["Static", "Run Away", "Guts"], None).unwrap_err().code(),
+            "too_many"
+        );
+        assert_eq!(
+            abilities(&["Static"], Some("static")).unwrap_err().code(),
+            "duplicate"
+        );
+        assert_eq!(abilities(&[""], None).unwrap_err().code(), "empty");
+    }
+}
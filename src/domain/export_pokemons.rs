@@ -0,0 +1,280 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use crate::domain::entities::Pokemon;
+use crate::domain::import_pokemons::{
+    CsvRow, Header, JsonRow, JsonStats, CSV_VERSION_PREFIX, SCHEMA_VERSION,
+};
+use crate::repositories::pokemon::{FetchAllError, FetchAllQuery, Repository, StorageError};
+
+/// Pokemons are read from the repository this many at a time.
+const PAGE_SIZE: usize = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Csv,
+    Json,
+    JsonLines,
+}
+
+impl TryFrom<String> for Format {
+    type Error = ();
+
+    fn try_from(format: String) -> Result<Self, Self::Error> {
+        match format.to_lowercase().as_str() {
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            "jsonl" => Ok(Format::JsonLines),
+            _ => Err(()),
+        }
+    }
+}
+
+pub struct Request {
+    /// Either `csv`, `json` or `jsonl`.
+    pub format: String,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    BadRequest,
+    Unknown(StorageError),
+}
+
+/// The exported file, produced chunk by chunk as Pokemons are read from the
+/// repository. Each chunk ends with a new line.
+pub struct Response {
+    repo: Arc<dyn Repository>,
+    format: Format,
+    pokemons: VecDeque<Pokemon>,
+    offset: usize,
+    exhausted: bool,
+    written: usize,
+    header: Option<String>,
+    footer: Option<String>,
+}
+
+impl Response {
+    /// The format the file is written in, as parsed from the request.
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    fn fetch_page(&mut self) -> Result<(), StorageError> {
+        let query = FetchAllQuery {
+            limit: Some(PAGE_SIZE),
+            offset: self.offset,
+            ..FetchAllQuery::default()
+        };
+        let pokemons = match self.repo.fetch_all(&query) {
+            Ok(pokemons) => pokemons,
+            Err(FetchAllError::Interrupted(e)) | Err(FetchAllError::Storage(e)) => return Err(e),
+        };
+        self.offset += pokemons.len();
+        self.exhausted = pokemons.len() < PAGE_SIZE;
+        self.pokemons.extend(pokemons);
+        Ok(())
+    }
+
+    fn render(&self, pokemon: Pokemon) -> String {
+        let number = u16::from(pokemon.number);
+        let form = pokemon.form.map(String::from);
+        let name = String::from(pokemon.name);
+        let types = Vec::<String>::from(pokemon.types);
+        let [hp, attack, defense, special_attack, special_defense, speed] =
+            <[u16; 6]>::from(pokemon.stats);
+        match self.format {
+            Format::Csv => {
+                let mut writer = csv::WriterBuilder::new()
+                    .has_headers(false)
+                    .from_writer(vec![]);
+                let row = CsvRow {
+                    number,
+                    name,
+                    types: types.join("/"),
+                    hp,
+                    attack,
+                    defense,
+                    special_attack,
+                    special_defense,
+                    speed,
+                    form,
+                };
+                writer.serialize(row).unwrap();
+                String::from_utf8(writer.into_inner().unwrap()).unwrap()
+            }
+            format => {
+                let row = JsonRow {
+                    number,
+                    form,
+                    name,
+                    types,
+                    stats: JsonStats {
+                        hp,
+                        attack,
+                        defense,
+                        special_attack,
+                        special_defense,
+                        speed,
+                    },
+                };
+                let json = serde_json::to_string(&row).unwrap();
+                match (format, self.written) {
+                    (Format::Json, 0) => format!("  {json}"),
+                    (Format::Json, _) => format!(",\n  {json}"),
+                    _ => format!("{json}\n"),
+                }
+            }
+        }
+    }
+}
+
+impl Iterator for Response {
+    type Item = Result<String, StorageError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(header) = self.header.take() {
+            return Some(Ok(header));
+        }
+        if self.pokemons.is_empty() && !self.exhausted {
+            if let Err(e) = self.fetch_page() {
+                self.exhausted = true;
+                self.footer = None;
+                return Some(Err(e));
+            }
+        }
+        match self.pokemons.pop_front() {
+            Some(pokemon) => {
+                let chunk = self.render(pokemon);
+                self.written += 1;
+                Some(Ok(chunk))
+            }
+            None => self.footer.take().map(Ok),
+        }
+    }
+}
+
+fn header(format: Format) -> String {
+    let version = serde_json::to_string(&Header {
+        schema_version: SCHEMA_VERSION,
+    })
+    .unwrap();
+    match format {
+        Format::Csv => format!(
+            "{CSV_VERSION_PREFIX}{SCHEMA_VERSION}\n\
+            number,name,types,hp,attack,defense,special_attack,special_defense,speed,form\n"
+        ),
+        Format::Json => format!("{{\"schema_version\":{SCHEMA_VERSION},\"pokemons\":[\n"),
+        Format::JsonLines => format!("{version}\n"),
+    }
+}
+
+/// The first page is read right away, so that an unavailable repository is
+/// reported before anything has been written.
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let format = Format::try_from(req.format).map_err(|_| Error::BadRequest)?;
+    let mut res = Response {
+        repo,
+        format,
+        pokemons: VecDeque::new(),
+        offset: 0,
+        exhausted: false,
+        written: 0,
+        header: Some(header(format)),
+        footer: match format {
+            Format::Json => Some(String::from("\n]}\n")),
+            _ => None,
+        },
+    };
+    res.fetch_page().map_err(Error::Unknown)?;
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        PokemonName, PokemonNumber, PokemonStats, PokemonTypes, DEFAULT_NATIONAL_DEX_LIMIT,
+    };
+    use crate::domain::import_pokemons;
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    fn repo() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        for number in 1..=(PAGE_SIZE as u16 + 5) {
+            repo.insert(
+                PokemonNumber::try_from(number).unwrap().into(),
+                PokemonName::try_from(format!("Pokemon {number}, \"the\" test")).unwrap(),
+                PokemonTypes::try_from(vec![String::from("Grass"), String::from("Poison")])
+                    .unwrap(),
+                PokemonStats::pikachu(),
+            )
+            .unwrap();
+        }
+        repo
+    }
+
+    fn export(repo: Arc<InMemoryRepository>, format: &str) -> String {
+        let req = Request {
+            format: String::from(format),
+        };
+        execute(repo, req)
+            .unwrap()
+            .collect::<Result<String, StorageError>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn it_should_record_the_schema_version() {
+        let content = export(repo(), "jsonl");
+
+        assert_eq!(content.lines().next(), Some("{\"schema_version\":1}"));
+        assert_eq!(content.lines().count(), PAGE_SIZE + 6);
+    }
+
+    #[test]
+    fn it_should_round_trip_through_the_import() {
+        for format in ["csv", "json", "jsonl"] {
+            let content = export(repo(), format);
+            let target = Arc::new(InMemoryRepository::new());
+
+            let req = import_pokemons::Request {
+                format: String::from(format),
+                content: content.clone(),
+                mode: None,
+                dry_run: false,
+                national_dex_limit: DEFAULT_NATIONAL_DEX_LIMIT,
+            };
+            let res = import_pokemons::execute(target.clone(), req).unwrap();
+
+            assert!(res.errors.is_empty(), "{format}: {:?}", res.errors);
+            assert_eq!(res.created, PAGE_SIZE + 5);
+            assert_eq!(export(target, format), content, "{format}");
+        }
+    }
+
+    #[test]
+    fn it_should_return_bad_request_when_format_is_unknown() {
+        let req = Request {
+            format: String::from("yaml"),
+        };
+
+        match execute(repo(), req) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_an_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request {
+            format: String::from("json"),
+        };
+
+        match execute(repo, req) {
+            Err(Error::Unknown(_)) => {}
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use crate::repositories::pokemon::{FetchOneError, Repository, StorageError};
+
+pub struct Request {
+    name: String,
+}
+
+impl Request {
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub number: u16,
+    pub name: String,
+    /// Whether this is the hidden ability of the Pokemon.
+    pub hidden: bool,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    NotFound,
+    Unknown(StorageError),
+}
+
+/// Every Pokemon having the ability, sorted by number. The ability is
+/// matched by name regardless of case.
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Vec<Response>, Error> {
+    let ability = match super::fetch_all_abilities::resolve(&repo, &req.name) {
+        Ok(Some(ability)) => ability,
+        Ok(None) => return Err(Error::NotFound),
+        Err(e) => return Err(Error::Unknown(e)),
+    };
+
+    let holders = repo
+        .fetch_ability_holders(ability.name)
+        .map_err(Error::Unknown)?;
+    let mut pokemons = Vec::with_capacity(holders.len());
+    for (number, hidden) in holders {
+        match repo.fetch_one(number.into()) {
+            Ok(pokemon) => pokemons.push(Response {
+                number: u16::from(pokemon.number),
+                name: String::from(pokemon.name),
+                hidden,
+            }),
+            Err(FetchOneError::NotFound) => {}
+            Err(FetchOneError::Storage(e)) => return Err(Error::Unknown(e)),
+        }
+    }
+    Ok(pokemons)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Ability, Pokemon, PokemonAbilities};
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_every_pokemon_with_the_ability() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![Pokemon::pikachu(), Pokemon::vulpix()]).unwrap();
+        repo.insert_ability(Ability::static_()).unwrap();
+        let abilities = PokemonAbilities::try_from((vec![], Some(String::from("Static")))).unwrap();
+        repo.replace_abilities(Pokemon::pikachu().number, abilities)
+            .unwrap();
+
+        let res = execute(repo, Request::new(String::from("static"))).unwrap();
+
+        let holders = res
+            .iter()
+            .map(|holder| (holder.number, holder.name.as_str(), holder.hidden))
+            .collect::<Vec<_>>();
+        assert_eq!(holders, vec![(25, "Pikachu", true)]);
+    }
+
+    #[test]
+    fn it_should_return_not_found_when_the_ability_does_not_exist() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo, Request::new(String::from("Static"))) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use crate::domain::entities::Ability;
+use crate::repositories::pokemon::{Repository, StorageError};
+
+#[derive(Debug)]
+pub struct Response {
+    pub name: String,
+    pub description: String,
+}
+
+impl From<Ability> for Response {
+    fn from(ability: Ability) -> Self {
+        Self {
+            name: String::from(ability.name),
+            description: ability.description,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Unknown(StorageError),
+}
+
+/// Every ability, sorted by name.
+pub fn execute(repo: Arc<dyn Repository>) -> Result<Vec<Response>, Error> {
+    match repo.fetch_abilities() {
+        Ok(abilities) => Ok(abilities.into_iter().map(Response::from).collect()),
+        Err(e) => Err(Error::Unknown(e)),
+    }
+}
+
+/// The stored name of an ability, looked up regardless of case.
+pub(super) fn resolve(
+    repo: &Arc<dyn Repository>,
+    name: &str,
+) -> Result<Option<Ability>, StorageError> {
+    Ok(repo
+        .fetch_abilities()?
+        .into_iter()
+        .find(|ability| ability.name.as_str().eq_ignore_ascii_case(name.trim())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_every_ability_sorted_by_name() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert_ability(Ability::static_()).unwrap();
+        repo.insert_ability(Ability::lightning_rod()).unwrap();
+
+        let res = execute(repo).unwrap();
+
+        let names = res.iter().map(|ability| ability.name.as_str()).collect::<Vec<&str>>();
+        assert_eq!(names, vec!["Lightning Rod", "Static"]);
+    }
+}
@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use crate::repositories::pokemon::{Repository, StorageError};
+
+pub use super::fetch_move::Response;
+
+#[derive(Debug)]
+pub enum Error {
+    Unknown(StorageError),
+}
+
+/// Every move, sorted by id.
+pub fn execute(repo: Arc<dyn Repository>) -> Result<Vec<Response>, Error> {
+    match repo.fetch_moves() {
+        Ok(moves) => Ok(moves.into_iter().map(Response::from).collect()),
+        Err(e) => Err(Error::Unknown(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::Move;
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_every_move_sorted_by_id() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert_move(Move::swift()).unwrap();
+        repo.insert_move(Move::thunder_shock()).unwrap();
+
+        let res = execute(repo).unwrap();
+
+        let ids = res.iter().map(|found| found.id).collect::<Vec<u16>>();
+        assert_eq!(ids, vec![84, 129]);
+    }
+}
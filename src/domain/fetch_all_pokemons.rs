@@ -1,36 +1,129 @@
 use std::sync::Arc;
 
-use crate::repositories::pokemon::Repository;
+use crate::domain::entities::{Generation, PokemonNumber, PokemonType};
+use crate::repositories::pokemon::{
+    FetchAllError, FetchAllQuery, Repository, SortDirection, SortField, StorageError,
+};
 
 #[derive(Debug)]
 pub enum Error {
-    Unknown,
+    BadRequest,
+    Unknown(StorageError),
+}
+
+#[derive(Default)]
+pub struct Request {
+    pub tipe: Option<String>,
+    pub name: Option<String>,
+    pub min_number: Option<u16>,
+    pub max_number: Option<u16>,
+    pub generation: Option<u8>,
+    /// Either `number` or `name`.
+    pub sort: Option<String>,
+    /// Either `asc` or `desc`.
+    pub direction: Option<String>,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
 }
 
 #[derive(Debug)]
 pub struct Response {
     pub number: u16,
+    pub generation: Option<u8>,
+    pub form: Option<String>,
     pub name: String,
     pub types: Vec<String>,
+    pub stats: [u16; 6],
+    pub total: u16,
+}
+
+#[derive(Debug)]
+pub struct Page {
+    pub pokemons: Vec<Response>,
+    pub offset: usize,
+    pub limit: Option<usize>,
+    /// Offset of the next page, if there is one.
+    pub next_offset: Option<usize>,
+}
+
+fn parse<T, U: TryFrom<T>>(value: Option<T>) -> Result<Option<U>, Error> {
+    match value.map(U::try_from) {
+        Some(Ok(value)) => Ok(Some(value)),
+        Some(Err(_)) => Err(Error::BadRequest),
+        None => Ok(None),
+    }
 }
 
-pub fn execute(repo: Arc<dyn Repository>) -> Result<Vec<Response>, Error> {
-    match repo.fetch_all() {
-        Ok(pokemons) => Ok(
-            pokemons.into_iter().map(|pokemon| Response {
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Page, Error> {
+    let min_number = parse::<u16, PokemonNumber>(req.min_number)?;
+    let max_number = parse::<u16, PokemonNumber>(req.max_number)?;
+    if let (Some(min), Some(max)) = (&min_number, &max_number) {
+        if min > max {
+            return Err(Error::BadRequest);
+        }
+    }
+    if req.limit == Some(0) {
+        return Err(Error::BadRequest);
+    }
+    // a generation narrows the number range down to its own
+    let (min_number, max_number) = match parse::<u8, Generation>(req.generation)? {
+        Some(generation) => (
+            min_number.max(Some(generation.first())),
+            Some(max_number.map_or(generation.last(), |max| max.min(generation.last()))),
+        ),
+        None => (min_number, max_number),
+    };
+
+    let offset = req.offset.unwrap_or(0);
+    let query = FetchAllQuery {
+        tipe: parse::<String, PokemonType>(req.tipe)?,
+        name: req.name,
+        min_number,
+        max_number,
+        sort: parse::<String, SortField>(req.sort)?.unwrap_or_default(),
+        direction: parse::<String, SortDirection>(req.direction)?.unwrap_or_default(),
+        // one more than asked for tells whether there is a next page
+        limit: req.limit.map(|limit| limit.saturating_add(1)),
+        offset,
+    };
+
+    let mut pokemons = match repo.fetch_all(&query) {
+        Ok(pokemons) => pokemons,
+        Err(FetchAllError::Interrupted(e)) => return Err(Error::Unknown(e)),
+        Err(FetchAllError::Storage(e)) => return Err(Error::Unknown(e)),
+    };
+
+    let next_offset = match req.limit {
+        Some(limit) if pokemons.len() > limit => {
+            pokemons.truncate(limit);
+            offset.checked_add(limit)
+        }
+        _ => None,
+    };
+
+    Ok(Page {
+        pokemons: pokemons
+            .into_iter()
+            .map(|pokemon| Response {
+                generation: pokemon.number.generation().map(u8::from),
                 number: u16::from(pokemon.number),
+                form: pokemon.form.map(String::from),
                 name: String::from(pokemon.name),
-                types: Vec::<String>::from(pokemon.types)
-            }).collect()
-        ),
-        Err(_) => Err(Error::Unknown)
-    }
+                types: Vec::<String>::from(pokemon.types),
+                total: pokemon.stats.total(),
+                stats: <[u16; 6]>::from(pokemon.stats),
+            })
+            .collect(),
+        offset,
+        limit: req.limit,
+        next_offset,
+    })
 }
 
 #[cfg(test)]
 mod tests {
 
-    use crate::domain::entities::{PokemonName, PokemonNumber, PokemonTypes};
+    use crate::domain::entities::{PokemonName, PokemonNumber, PokemonStats, PokemonTypes};
     use crate::repositories::inmemory_pokemon::InMemoryRepository;
 
     use super::*;
@@ -39,28 +132,32 @@ mod tests {
     fn it_should_return_an_error_when_an_unexpected_error_happens() {
         let repo = Arc::new(InMemoryRepository::new().with_error());
 
-        let res = execute(repo);
+        let res = execute(repo, Request::default());
 
-        assert!(matches!(res, Err(Error::Unknown)), "didn't returned error");
+        assert!(matches!(res, Err(Error::Unknown(_))), "didn't returned error");
     }
 
     #[test]
     fn it_should_return_all_pokemon_in_repository() {
         let repo = Arc::new(InMemoryRepository::new());
         repo.insert(
-            PokemonNumber::pikachu(),
+            PokemonNumber::pikachu().into(),
             PokemonName::pikachu(),
             PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
         )
         .expect("error inserting pikachu");
         repo.insert(
-            PokemonNumber::vulpix(),
+            PokemonNumber::vulpix().into(),
             PokemonName::vulpix(),
             PokemonTypes::vulpix(),
+            PokemonStats::vulpix(),
         )
         .expect("error inserting vulpix");
 
-        let res = execute(repo).expect("execute returned an error");
+        let res = execute(repo, Request::default())
+            .expect("execute returned an error")
+            .pokemons;
 
         assert_eq!(res[0].number, 25);
         assert_eq!(res[0].name, "Pikachu".to_owned());
@@ -69,5 +166,166 @@ mod tests {
         assert_eq!(res[1].number, 37);
         assert_eq!(res[1].name, "Vulpix".to_owned());
         assert_eq!(res[1].types, vec!["Fire".to_owned()]);
+        assert_eq!(res[1].total, 299);
+    }
+
+    fn repo_with_pokemons() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::vulpix().into(),
+            PokemonName::vulpix(),
+            PokemonTypes::vulpix(),
+            PokemonStats::vulpix(),
+        )
+        .expect("error inserting vulpix");
+        repo.insert(
+            PokemonNumber::pikachu().into(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .expect("error inserting pikachu");
+        repo
+    }
+
+    #[test]
+    fn it_should_return_bad_request_when_query_is_invalid() {
+        let repo = repo_with_pokemons();
+
+        let reqs = [
+            Request {
+                tipe: Some(String::from("Shadow")),
+                ..Request::default()
+            },
+            Request {
+                sort: Some(String::from("weight")),
+                ..Request::default()
+            },
+            Request {
+                min_number: Some(40),
+                max_number: Some(30),
+                ..Request::default()
+            },
+            Request {
+                limit: Some(0),
+                ..Request::default()
+            },
+            Request {
+                generation: Some(10),
+                ..Request::default()
+            },
+        ];
+
+        for req in reqs {
+            let res = execute(repo.clone(), req);
+            assert!(matches!(res, Err(Error::BadRequest)));
+        }
+    }
+
+    #[test]
+    fn it_should_filter_pokemons() {
+        let repo = repo_with_pokemons();
+        let req = Request {
+            tipe: Some(String::from("fire")),
+            name: Some(String::from("PIX")),
+            ..Request::default()
+        };
+
+        let res = execute(repo, req).expect("execute returned an error");
+
+        assert_eq!(res.pokemons.len(), 1);
+        assert_eq!(res.pokemons[0].name, "Vulpix");
+    }
+
+    #[test]
+    fn it_should_filter_pokemons_by_generation() {
+        let repo = repo_with_pokemons();
+        let kanto = Request {
+            generation: Some(1),
+            min_number: Some(30),
+            ..Request::default()
+        };
+        let johto = Request {
+            generation: Some(2),
+            ..Request::default()
+        };
+
+        let kanto = execute(repo.clone(), kanto).expect("execute returned an error");
+        let johto = execute(repo, johto).expect("execute returned an error");
+
+        assert_eq!(kanto.pokemons.len(), 1);
+        assert_eq!(kanto.pokemons[0].number, 37);
+        assert_eq!(kanto.pokemons[0].generation, Some(1));
+        assert!(johto.pokemons.is_empty());
+    }
+
+    #[test]
+    fn it_should_sort_pokemons() {
+        let repo = repo_with_pokemons();
+        let req = Request {
+            sort: Some(String::from("name")),
+            direction: Some(String::from("desc")),
+            ..Request::default()
+        };
+
+        let res = execute(repo, req).expect("execute returned an error");
+
+        assert_eq!(res.pokemons[0].name, "Vulpix");
+        assert_eq!(res.pokemons[1].name, "Pikachu");
+    }
+
+    #[test]
+    fn it_should_paginate_pokemons() {
+        let repo = repo_with_pokemons();
+
+        let first = execute(
+            repo.clone(),
+            Request {
+                limit: Some(1),
+                ..Request::default()
+            },
+        )
+        .expect("execute returned an error");
+        let second = execute(
+            repo,
+            Request {
+                limit: Some(1),
+                offset: first.next_offset,
+                ..Request::default()
+            },
+        )
+        .expect("execute returned an error");
+
+        assert_eq!(first.pokemons[0].number, 25);
+        assert_eq!(first.next_offset, Some(1));
+        assert_eq!(second.pokemons[0].number, 37);
+        assert_eq!(second.next_offset, None);
+    }
+
+    #[test]
+    fn it_should_not_overflow_with_extreme_pagination() {
+        let repo = repo_with_pokemons();
+
+        let all = execute(
+            repo.clone(),
+            Request {
+                limit: Some(usize::MAX),
+                ..Request::default()
+            },
+        )
+        .expect("execute returned an error");
+        let none = execute(
+            repo,
+            Request {
+                limit: Some(usize::MAX),
+                offset: Some(usize::MAX),
+                ..Request::default()
+            },
+        )
+        .expect("execute returned an error");
+
+        assert_eq!(all.pokemons.len(), 2);
+        assert_eq!(all.next_offset, None);
+        assert!(none.pokemons.is_empty());
     }
 }
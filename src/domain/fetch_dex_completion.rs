@@ -0,0 +1,144 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::domain::entities::{DexStatus, TrainerId};
+use crate::repositories::pokemon::{FetchTrainerError, Repository, StorageError};
+
+pub struct Request {
+    trainer: String,
+}
+
+impl Request {
+    pub fn new(trainer: String) -> Self {
+        Self { trainer }
+    }
+}
+
+/// How much of a set of Pokemons the trainer recorded.
+#[derive(Debug, Default, PartialEq)]
+pub struct Completion {
+    /// Every base form stored in the set.
+    pub total: usize,
+    pub seen: usize,
+    pub caught: usize,
+    pub shiny: usize,
+    /// Share of the set caught, from 0 to 100, rounded down to a tenth.
+    pub percentage: f64,
+}
+
+impl Completion {
+    fn add(&mut self, status: DexStatus) {
+        self.total += 1;
+        self.seen += usize::from(status.seen());
+        self.caught += usize::from(status.caught());
+        self.shiny += usize::from(status.shiny());
+    }
+
+    fn finish(mut self) -> Self {
+        if let Some(permille) = (self.caught * 1000).checked_div(self.total) {
+            self.percentage = permille as f64 / 10.0;
+        }
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub overall: Completion,
+    /// Only the generations with stored Pokemons, in order.
+    pub generations: Vec<(u8, Completion)>,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    BadRequest,
+    NotFound,
+    Unknown(StorageError),
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let trainer = TrainerId::try_from(req.trainer).map_err(|_| Error::BadRequest)?;
+    match repo.fetch_trainer(trainer.clone()) {
+        Ok(_) => {}
+        Err(FetchTrainerError::NotFound) => return Err(Error::NotFound),
+        Err(FetchTrainerError::Storage(e)) => return Err(Error::Unknown(e)),
+    }
+    let statuses = repo
+        .fetch_trainer_dex(trainer)
+        .map_err(Error::Unknown)?
+        .into_iter()
+        .collect::<BTreeMap<_, _>>();
+    let pokemons = super::fetch_regional_dex::base_forms(&repo).map_err(Error::Unknown)?;
+
+    let mut overall = Completion::default();
+    let mut generations = BTreeMap::<u8, Completion>::new();
+    for number in pokemons.into_keys() {
+        let status = statuses.get(&number).copied().unwrap_or_default();
+        overall.add(status);
+        if let Some(generation) = number.generation() {
+            generations
+                .entry(u8::from(generation))
+                .or_default()
+                .add(status);
+        }
+    }
+
+    Ok(Response {
+        overall: overall.finish(),
+        generations: generations
+            .into_iter()
+            .map(|(generation, completion)| (generation, completion.finish()))
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{DexMark, Pokemon, PokemonNumber, Trainer};
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_not_found_when_the_trainer_does_not_exist() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo, Request::new(String::from("red"))) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_compute_completion_overall_and_per_generation() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let mut chikorita = Pokemon::pikachu();
+        chikorita.number = PokemonNumber::try_from(152).unwrap();
+        repo.upsert_all(vec![Pokemon::pikachu(), Pokemon::vulpix(), chikorita])
+            .unwrap();
+        repo.insert_trainer(Trainer::red()).unwrap();
+        let shiny = DexStatus::default().mark(DexMark::Shiny);
+        let seen = DexStatus::default().mark(DexMark::Seen);
+        repo.replace_dex_status(Trainer::red().id, Pokemon::pikachu().number, shiny)
+            .unwrap();
+        repo.replace_dex_status(Trainer::red().id, Pokemon::vulpix().number, seen)
+            .unwrap();
+
+        let res = execute(repo, Request::new(String::from("red"))).unwrap();
+
+        assert_eq!(
+            res.overall,
+            Completion {
+                total: 3,
+                seen: 2,
+                caught: 1,
+                shiny: 1,
+                percentage: 33.3,
+            }
+        );
+        assert_eq!(res.generations.len(), 2);
+        assert_eq!(res.generations[0].0, 1);
+        assert_eq!(res.generations[0].1.percentage, 50.0);
+        assert_eq!(res.generations[1].0, 2);
+        assert_eq!(res.generations[1].1.percentage, 0.0);
+    }
+}
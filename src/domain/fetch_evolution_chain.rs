@@ -0,0 +1,192 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::domain::entities::{Evolution, PokemonNumber};
+use crate::repositories::pokemon::{FetchOneError, Repository, StorageError};
+
+pub struct Request {
+    number: u16,
+}
+
+impl Request {
+    pub fn new(number: u16) -> Self {
+        Self { number }
+    }
+}
+
+/// A stage of the chain, with the stages it can evolve into.
+#[derive(Debug)]
+pub struct Stage {
+    pub number: u16,
+    pub name: String,
+    /// How the previous stage evolves into this one, unset for the first stage.
+    pub trigger: Option<String>,
+    pub conditions: Vec<String>,
+    pub evolutions: Vec<Stage>,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    BadRequest,
+    NotFound,
+    Unknown(StorageError),
+}
+
+/// Builds the stage of `number` and, recursively, every stage after it.
+/// Evolutions to Pokemons that can't be found, or that are already in the
+/// tree, are left out of it.
+fn stage(
+    repo: &Arc<dyn Repository>,
+    children: &HashMap<u16, Vec<&Evolution>>,
+    visited: &mut HashSet<u16>,
+    number: PokemonNumber,
+    evolution: Option<&Evolution>,
+) -> Result<Option<Stage>, Error> {
+    if !visited.insert(u16::from(number.clone())) {
+        return Ok(None);
+    }
+    let pokemon = match repo.fetch_one(number.into()) {
+        Ok(pokemon) => pokemon,
+        Err(FetchOneError::NotFound) => return Ok(None),
+        Err(FetchOneError::Storage(e)) => return Err(Error::Unknown(e)),
+    };
+    let number = u16::from(pokemon.number);
+
+    let mut evolutions = vec![];
+    for next in children.get(&number).into_iter().flatten() {
+        if let Some(next) = stage(repo, children, visited, next.to.clone(), Some(next))? {
+            evolutions.push(next);
+        }
+    }
+
+    Ok(Some(Stage {
+        number,
+        name: String::from(pokemon.name),
+        trigger: evolution.map(|evolution| String::from(evolution.trigger.clone())),
+        conditions: evolution.map_or(vec![], |evolution| evolution.conditions.clone()),
+        evolutions,
+    }))
+}
+
+/// Returns the whole family of the Pokemon, starting from its first stage.
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Stage, Error> {
+    let number = PokemonNumber::try_from(req.number).map_err(|_| Error::BadRequest)?;
+    match repo.fetch_one(number.clone().into()) {
+        Ok(_) => {}
+        Err(FetchOneError::NotFound) => return Err(Error::NotFound),
+        Err(FetchOneError::Storage(e)) => return Err(Error::Unknown(e)),
+    }
+
+    let evolutions = repo.fetch_evolutions().map_err(Error::Unknown)?;
+    let parents = evolutions
+        .iter()
+        .map(|evolution| (evolution.to.clone(), evolution.from.clone()))
+        .collect::<HashMap<PokemonNumber, PokemonNumber>>();
+    let mut children: HashMap<u16, Vec<&Evolution>> = HashMap::new();
+    for evolution in &evolutions {
+        children
+            .entry(u16::from(evolution.from.clone()))
+            .or_default()
+            .push(evolution);
+    }
+
+    // Bounded in case a backend edited by hand holds a cycle.
+    let mut first = number;
+    for _ in 0..parents.len() {
+        match parents.get(&first) {
+            Some(parent) => first = parent.clone(),
+            None => break,
+        }
+    }
+
+    match stage(&repo, &children, &mut HashSet::new(), first, None)? {
+        Some(stage) => Ok(stage),
+        None => Err(Error::NotFound),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        EvolutionTrigger, Pokemon, PokemonName, PokemonStats, PokemonTypes,
+    };
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    fn eevee_family() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        let family = [
+            (133, "Eevee", None),
+            (134, "Vaporeon", Some("stone:Water Stone")),
+            (135, "Jolteon", Some("stone:Thunder Stone")),
+            (196, "Espeon", Some("friendship")),
+        ];
+        for (number, name, trigger) in family {
+            let number = PokemonNumber::try_from(number).unwrap();
+            repo.insert(
+                number.clone().into(),
+                PokemonName::try_from(String::from(name)).unwrap(),
+                PokemonTypes::pikachu(),
+                PokemonStats::pikachu(),
+            )
+            .unwrap();
+            if let Some(trigger) = trigger {
+                let conditions = match name {
+                    "Espeon" => vec![String::from("daytime")],
+                    _ => vec![],
+                };
+                repo.insert_evolution(Evolution::new(
+                    PokemonNumber::try_from(133).unwrap(),
+                    number,
+                    EvolutionTrigger::try_from(String::from(trigger)).unwrap(),
+                    conditions,
+                ))
+                .unwrap();
+            }
+        }
+        repo
+    }
+
+    #[test]
+    fn it_should_return_the_whole_branching_family() {
+        let repo = eevee_family();
+
+        let res = execute(repo, Request::new(135)).unwrap();
+
+        assert_eq!((res.number, res.name.as_str(), res.trigger), (133, "Eevee", None));
+        let stages = res
+            .evolutions
+            .iter()
+            .map(|stage| (stage.number, stage.trigger.clone().unwrap(), stage.conditions.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            stages,
+            vec![
+                (134, String::from("stone:Water Stone"), vec![]),
+                (135, String::from("stone:Thunder Stone"), vec![]),
+                (196, String::from("friendship"), vec![String::from("daytime")]),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_should_return_a_single_stage_for_a_pokemon_without_evolutions() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![Pokemon::pikachu()]).unwrap();
+
+        let res = execute(repo, Request::new(25)).unwrap();
+
+        assert_eq!(res.number, 25);
+        assert!(res.evolutions.is_empty());
+    }
+
+    #[test]
+    fn it_should_return_not_found_when_the_pokemon_does_not_exist() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo, Request::new(25)) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+}
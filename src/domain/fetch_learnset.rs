@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::domain::entities::{LearnsetEntry, PokemonNumber};
+use crate::repositories::pokemon::{FetchOneError, Repository, StorageError};
+
+pub struct Request {
+    number: u16,
+}
+
+impl Request {
+    pub fn new(number: u16) -> Self {
+        Self { number }
+    }
+}
+
+/// A move of the learnset, with the way it is learned.
+#[derive(Debug)]
+pub struct Response {
+    pub id: u16,
+    pub name: String,
+    pub tipe: String,
+    pub category: String,
+    pub power: Option<u16>,
+    pub accuracy: Option<u16>,
+    pub pp: u16,
+    /// `level:N`, `machine`, `egg` or `tutor`.
+    pub method: String,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    BadRequest,
+    NotFound,
+    Unknown(StorageError),
+}
+
+/// Joins the learnset with the moves it refers to, keeping its order.
+pub(super) fn with_moves(
+    repo: &Arc<dyn Repository>,
+    learnset: Vec<LearnsetEntry>,
+) -> Result<Vec<Response>, StorageError> {
+    let moves = repo
+        .fetch_moves()?
+        .into_iter()
+        .map(|found| (found.id.clone(), found))
+        .collect::<HashMap<_, _>>();
+
+    Ok(learnset
+        .into_iter()
+        .filter_map(|entry| {
+            let found = super::fetch_move::Response::from(moves.get(&entry.move_id)?.clone());
+            Some(Response {
+                id: found.id,
+                name: found.name,
+                tipe: found.tipe,
+                category: found.category,
+                power: found.power,
+                accuracy: found.accuracy,
+                pp: found.pp,
+                method: String::from(entry.method),
+            })
+        })
+        .collect())
+}
+
+/// The moves a Pokemon learns, level-up moves first by level, then
+/// machines, eggs and tutors.
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Vec<Response>, Error> {
+    let number = PokemonNumber::try_from(req.number).map_err(|_| Error::BadRequest)?;
+    match repo.fetch_one(number.clone().into()) {
+        Ok(_) => {}
+        Err(FetchOneError::NotFound) => return Err(Error::NotFound),
+        Err(FetchOneError::Storage(e)) => return Err(Error::Unknown(e)),
+    }
+
+    let learnset = repo.fetch_learnset(number).map_err(Error::Unknown)?;
+    with_moves(&repo, learnset).map_err(Error::Unknown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{LearnMethod, Move, Pokemon};
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_the_moves_with_their_method() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![Pokemon::pikachu()]).unwrap();
+        repo.insert_move(Move::thunder_shock()).unwrap();
+        repo.insert_move(Move::swift()).unwrap();
+        repo.replace_learnset(
+            PokemonNumber::pikachu(),
+            vec![
+                LearnsetEntry::new(Move::swift().id, LearnMethod::Machine),
+                LearnsetEntry::new(Move::thunder_shock().id, LearnMethod::LevelUp(1)),
+            ],
+        )
+        .unwrap();
+
+        let res = execute(repo, Request::new(25)).unwrap();
+
+        let moves = res
+            .iter()
+            .map(|learned| (learned.name.as_str(), learned.method.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(moves, vec![("Thunder Shock", "level:1"), ("Swift", "machine")]);
+    }
+
+    #[test]
+    fn it_should_return_not_found_when_the_pokemon_does_not_exist() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo, Request::new(25)) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{Move, MoveId};
+use crate::repositories::pokemon::{FetchMoveError, Repository, StorageError};
+
+#[derive(Debug)]
+pub enum Error {
+    Unknown(StorageError),
+    BadRequest,
+    NotFound,
+}
+
+pub struct Request {
+    id: u16,
+}
+
+impl Request {
+    pub fn new(id: u16) -> Self {
+        Self { id }
+    }
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub id: u16,
+    pub name: String,
+    pub tipe: String,
+    pub category: String,
+    pub power: Option<u16>,
+    pub accuracy: Option<u16>,
+    pub pp: u16,
+}
+
+impl From<Move> for Response {
+    fn from(found: Move) -> Self {
+        Self {
+            id: u16::from(found.id),
+            name: String::from(found.name),
+            tipe: String::from(found.tipe),
+            category: String::from(found.category),
+            power: found.power.map(u16::from),
+            accuracy: found.accuracy.map(u16::from),
+            pp: u16::from(found.pp),
+        }
+    }
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    match MoveId::try_from(req.id) {
+        Ok(id) => match repo.fetch_move(id) {
+            Ok(found) => Ok(Response::from(found)),
+            Err(FetchMoveError::NotFound) => Err(Error::NotFound),
+            Err(FetchMoveError::Storage(e)) => Err(Error::Unknown(e)),
+        },
+        Err(_) => Err(Error::BadRequest),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_the_move_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert_move(Move::swift()).unwrap();
+
+        let res = execute(repo, Request::new(129)).unwrap();
+
+        assert_eq!((res.id, res.name.as_str(), res.accuracy), (129, "Swift", None));
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_move_does_not_exist() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo.clone(), Request::new(129)) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+        match execute(repo, Request::new(0)) {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+}
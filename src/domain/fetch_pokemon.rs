@@ -1,24 +1,34 @@
 use std::sync::Arc;
 
-use crate::repositories::pokemon::{FetchOneError, Repository};
+use crate::repositories::pokemon::{FetchOneError, Repository, StorageError};
 
-use super::entities::PokemonNumber;
+use super::entities::{Pokemon, PokemonForm, PokemonKey, PokemonNumber};
 
 #[derive(Debug)]
 pub enum Error {
-    Unknown,
+    Unknown(StorageError),
     BadRequest,
     NotFound,
 }
 
 pub struct Request {
     number: u16,
+    form: Option<String>,
 }
 
 impl Request {
+    /// Fetches the base form of the number.
     pub fn new(number: u16) -> Self {
         Self {
             number,
+            form: None,
+        }
+    }
+
+    pub fn with_form(self, form: String) -> Self {
+        Self {
+            form: Some(form),
+            ..self
         }
     }
 }
@@ -26,29 +36,44 @@ impl Request {
 #[derive(Debug)]
 pub struct Response {
     pub number: u16,
+    pub generation: Option<u8>,
+    pub form: Option<String>,
     pub name: String,
     pub types: Vec<String>,
+    pub stats: [u16; 6],
+    pub total: u16,
+}
+
+impl From<Pokemon> for Response {
+    fn from(pokemon: Pokemon) -> Self {
+        Self {
+            generation: pokemon.number.generation().map(u8::from),
+            number: u16::from(pokemon.number),
+            form: pokemon.form.map(String::from),
+            name: String::from(pokemon.name),
+            types: Vec::<String>::from(pokemon.types),
+            total: pokemon.stats.total(),
+            stats: <[u16; 6]>::from(pokemon.stats),
+        }
+    }
 }
 
 pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
-    match PokemonNumber::try_from(req.number) {
-        Ok(number) => match repo.fetch_one(number) {
-            Ok(pokemon) => Ok(Response {
-                number: u16::from(pokemon.number),
-                name: String::from(pokemon.name),
-                types: Vec::<String>::from(pokemon.types),
-            }),
+    let form = req.form.map(PokemonForm::try_from).transpose();
+    match (PokemonNumber::try_from(req.number), form) {
+        (Ok(number), Ok(form)) => match repo.fetch_one(PokemonKey::new(number, form)) {
+            Ok(pokemon) => Ok(Response::from(pokemon)),
             Err(FetchOneError::NotFound) => Err(Error::NotFound),
-            Err(FetchOneError::Unknown) => Err(Error::Unknown),
+            Err(FetchOneError::Storage(e)) => Err(Error::Unknown(e)),
         },
-        Err(_) => Err(Error::BadRequest),
+        _ => Err(Error::BadRequest),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{domain::entities::{PokemonName, PokemonNumber, PokemonTypes}, repositories::inmemory_pokemon::InMemoryRepository};
+    use crate::{domain::entities::{PokemonName, PokemonNumber, PokemonStats, PokemonTypes}, repositories::inmemory_pokemon::InMemoryRepository};
 
     #[test]
     fn it_should_return_unknow_error_when_an_unexpected_error_happens() {
@@ -57,7 +82,7 @@ mod tests {
         let req = Request::new(25);
         let res = execute(repo, req);
 
-        assert!(matches!(res, Err(Error::Unknown)))
+        assert!(matches!(res, Err(Error::Unknown(_))))
     }
 
     #[test]
@@ -84,9 +109,10 @@ mod tests {
     fn it_should_return_pokemon_otherwise() {
         let repo = Arc::new(InMemoryRepository::new());
         repo.insert(
-            PokemonNumber::pikachu(),
+            PokemonNumber::pikachu().into(),
             PokemonName::pikachu(),
             PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
         )
         .expect("error inserting pikachu");
 
@@ -96,5 +122,32 @@ mod tests {
         assert_eq!(res.number, 25);
         assert_eq!(res.name, "Pikachu");
         assert_eq!(res.types, vec!["Electric"]);
+        assert_eq!(res.stats, [35, 55, 40, 50, 50, 90]);
+        assert_eq!(res.total, 320);
+    }
+
+    #[test]
+    fn it_should_return_the_requested_form() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::vulpix().into(),
+            PokemonName::vulpix(),
+            PokemonTypes::vulpix(),
+            PokemonStats::vulpix(),
+        )
+        .expect("error inserting vulpix");
+        repo.insert(
+            PokemonKey::new(PokemonNumber::vulpix(), Some(PokemonForm::alola())),
+            PokemonName::vulpix(),
+            PokemonTypes::alolan_vulpix(),
+            PokemonStats::vulpix(),
+        )
+        .expect("error inserting alolan vulpix");
+
+        let req = Request::new(37).with_form(String::from("Alola"));
+        let res = execute(repo, req).expect("error on execute");
+
+        assert_eq!(res.form.as_deref(), Some("alola"));
+        assert_eq!(res.types, vec!["Ice"]);
     }
 }
@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{PokemonAbilities, PokemonNumber};
+use crate::repositories::pokemon::{FetchOneError, Repository, StorageError};
+
+pub struct Request {
+    number: u16,
+}
+
+impl Request {
+    pub fn new(number: u16) -> Self {
+        Self { number }
+    }
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub regular: Vec<String>,
+    pub hidden: Option<String>,
+}
+
+impl From<PokemonAbilities> for Response {
+    fn from(abilities: PokemonAbilities) -> Self {
+        let (regular, hidden) = abilities.into();
+        Self { regular, hidden }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    BadRequest,
+    NotFound,
+    Unknown(StorageError),
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let number = PokemonNumber::try_from(req.number).map_err(|_| Error::BadRequest)?;
+    match repo.fetch_one(number.clone().into()) {
+        Ok(_) => {}
+        Err(FetchOneError::NotFound) => return Err(Error::NotFound),
+        Err(FetchOneError::Storage(e)) => return Err(Error::Unknown(e)),
+    }
+
+    match repo.fetch_pokemon_abilities(number) {
+        Ok(abilities) => Ok(Response::from(abilities)),
+        Err(e) => Err(Error::Unknown(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_not_found_when_the_pokemon_does_not_exist() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo, Request::new(25)) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+}
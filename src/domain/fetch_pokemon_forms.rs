@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use crate::domain::entities::PokemonNumber;
+use crate::domain::fetch_pokemon;
+use crate::repositories::pokemon::{FetchAllError, FetchAllQuery, Repository, StorageError};
+
+pub struct Request {
+    number: u16,
+}
+
+impl Request {
+    pub fn new(number: u16) -> Self {
+        Self { number }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    BadRequest,
+    NotFound,
+    Unknown(StorageError),
+}
+
+/// Every form of the national number, the base form first.
+pub fn execute(
+    repo: Arc<dyn Repository>,
+    req: Request,
+) -> Result<Vec<fetch_pokemon::Response>, Error> {
+    let number = PokemonNumber::try_from(req.number).map_err(|_| Error::BadRequest)?;
+    let pokemons = match repo.fetch_all(&FetchAllQuery::by_number(number)) {
+        Ok(pokemons) => pokemons,
+        Err(FetchAllError::Interrupted(e)) | Err(FetchAllError::Storage(e)) => {
+            return Err(Error::Unknown(e))
+        }
+    };
+
+    if pokemons.is_empty() {
+        return Err(Error::NotFound);
+    }
+    Ok(pokemons
+        .into_iter()
+        .map(fetch_pokemon::Response::from)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        PokemonForm, PokemonKey, PokemonName, PokemonStats, PokemonTypes,
+    };
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_not_found_when_the_number_has_no_form() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo, Request::new(37)) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_the_base_form_first() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonKey::new(PokemonNumber::vulpix(), Some(PokemonForm::alola())),
+            PokemonName::vulpix(),
+            PokemonTypes::alolan_vulpix(),
+            PokemonStats::vulpix(),
+        )
+        .unwrap();
+        repo.insert(
+            PokemonNumber::vulpix().into(),
+            PokemonName::vulpix(),
+            PokemonTypes::vulpix(),
+            PokemonStats::vulpix(),
+        )
+        .unwrap();
+
+        let res = execute(repo, Request::new(37)).unwrap();
+
+        let forms = res
+            .into_iter()
+            .map(|pokemon| pokemon.form)
+            .collect::<Vec<_>>();
+        assert_eq!(forms, vec![None, Some(String::from("alola"))]);
+    }
+}
@@ -0,0 +1,190 @@
+use std::sync::Arc;
+
+use crate::repositories::pokemon::{FetchOneError, Repository, StorageError};
+
+use super::entities::{PokemonForm, PokemonKey, PokemonNumber, PokemonType};
+use super::type_chart;
+
+#[derive(Debug)]
+pub enum Error {
+    Unknown(StorageError),
+    BadRequest,
+    NotFound,
+}
+
+pub struct Request {
+    number: u16,
+    form: Option<String>,
+}
+
+impl Request {
+    /// Fetches the matchups of the base form of the number.
+    pub fn new(number: u16) -> Self {
+        Self {
+            number,
+            form: None,
+        }
+    }
+
+    pub fn with_form(self, form: String) -> Self {
+        Self {
+            form: Some(form),
+            ..self
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Matchup {
+    pub tipe: String,
+    pub multiplier: f32,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub number: u16,
+    pub form: Option<String>,
+    pub name: String,
+    pub types: Vec<String>,
+    pub weaknesses: Vec<Matchup>,
+    pub resistances: Vec<Matchup>,
+    pub immunities: Vec<Matchup>,
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let form = req.form.map(PokemonForm::try_from).transpose();
+    let pokemon = match (PokemonNumber::try_from(req.number), form) {
+        (Ok(number), Ok(form)) => match repo.fetch_one(PokemonKey::new(number, form)) {
+            Ok(pokemon) => pokemon,
+            Err(FetchOneError::NotFound) => return Err(Error::NotFound),
+            Err(FetchOneError::Storage(e)) => return Err(Error::Unknown(e)),
+        },
+        _ => return Err(Error::BadRequest),
+    };
+
+    let mut weaknesses = vec![];
+    let mut resistances = vec![];
+    let mut immunities = vec![];
+    for attack in PokemonType::ALL {
+        let multiplier = type_chart::multiplier(attack, &pokemon.types);
+        let matchup = Matchup {
+            tipe: String::from(attack),
+            multiplier,
+        };
+        if multiplier == 0.0 {
+            immunities.push(matchup);
+        } else if multiplier < 1.0 {
+            resistances.push(matchup);
+        } else if multiplier > 1.0 {
+            weaknesses.push(matchup);
+        }
+    }
+
+    Ok(Response {
+        number: u16::from(pokemon.number),
+        form: pokemon.form.map(String::from),
+        name: String::from(pokemon.name),
+        types: Vec::<String>::from(pokemon.types),
+        weaknesses,
+        resistances,
+        immunities,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Pokemon, PokemonName, PokemonStats, PokemonTypes};
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+
+        let res = execute(repo, Request::new(25));
+
+        assert!(matches!(res, Err(Error::Unknown(_))));
+    }
+
+    #[test]
+    fn it_should_return_bad_request_when_request_is_invalid() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        let res = execute(repo, Request::new(0));
+
+        assert!(matches!(res, Err(Error::BadRequest)));
+    }
+
+    #[test]
+    fn it_should_return_not_found_when_repo_does_not_contain_pokemon() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        let res = execute(repo, Request::new(25));
+
+        assert!(matches!(res, Err(Error::NotFound)));
+    }
+
+    #[test]
+    fn it_should_return_matchups_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu().into(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .expect("error inserting pikachu");
+
+        let res = execute(repo, Request::new(25)).expect("error on execute");
+
+        let names = |matchups: &[Matchup]| {
+            matchups
+                .iter()
+                .map(|m| m.tipe.clone())
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(res.number, 25);
+        assert_eq!(names(&res.weaknesses), vec!["Ground"]);
+        assert_eq!(names(&res.resistances), vec!["Electric", "Flying", "Steel"]);
+        assert!(res.immunities.is_empty());
+    }
+    #[test]
+    fn it_should_return_the_matchups_of_the_requested_form() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![
+            Pokemon::vulpix(),
+            Pokemon {
+                types: PokemonTypes::alolan_vulpix(),
+                ..Pokemon::vulpix()
+            }
+            .with_form(Some(PokemonForm::alola())),
+        ])
+        .unwrap();
+        let fire = |res: Response| {
+            res.weaknesses
+                .into_iter()
+                .chain(res.resistances)
+                .find(|m| m.tipe == "Fire")
+                .map(|m| m.multiplier)
+        };
+
+        let base = execute(repo.clone(), Request::new(37)).unwrap();
+        let alola = execute(repo, Request::new(37).with_form(String::from("alola"))).unwrap();
+
+        assert_eq!(base.form, None);
+        assert_eq!(fire(base), Some(0.5));
+        assert_eq!(alola.form, Some(String::from("alola")));
+        assert_eq!(alola.types, vec!["Ice"]);
+        assert_eq!(fire(alola), Some(2.0));
+    }
+
+    #[test]
+    fn it_should_return_not_found_when_the_form_does_not_exist() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![Pokemon::vulpix()]).unwrap();
+
+        let res = execute(repo, Request::new(37).with_form(String::from("alola")));
+
+        assert!(matches!(res, Err(Error::NotFound)));
+    }
+}
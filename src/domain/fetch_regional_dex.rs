@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::domain::entities::{DexEntry, DexName, Pokemon, PokemonNumber};
+use crate::domain::fetch_pokemon;
+use crate::repositories::pokemon::{FetchAllError, FetchAllQuery, Repository, StorageError};
+
+pub struct Request {
+    region: String,
+}
+
+impl Request {
+    pub fn new(region: String) -> Self {
+        Self { region }
+    }
+}
+
+/// The base form of the Pokemon found at an index of the dex.
+#[derive(Debug)]
+pub struct Response {
+    pub index: u16,
+    pub pokemon: fetch_pokemon::Response,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    BadRequest,
+    NotFound,
+    Unknown(StorageError),
+}
+
+/// The base form of every stored Pokemon, by national number.
+pub(super) fn base_forms(
+    repo: &Arc<dyn Repository>,
+) -> Result<HashMap<PokemonNumber, Pokemon>, StorageError> {
+    match repo.fetch_all(&FetchAllQuery::default()) {
+        Ok(pokemons) => Ok(pokemons
+            .into_iter()
+            .filter(|pokemon| pokemon.form.is_none())
+            .map(|pokemon| (pokemon.number.clone(), pokemon))
+            .collect()),
+        Err(FetchAllError::Interrupted(e)) | Err(FetchAllError::Storage(e)) => Err(e),
+    }
+}
+
+/// Joins the entries with the Pokemons they refer to, keeping their order.
+pub(super) fn with_pokemons(
+    repo: &Arc<dyn Repository>,
+    entries: Vec<DexEntry>,
+) -> Result<Vec<Response>, StorageError> {
+    let mut pokemons = base_forms(repo)?;
+
+    Ok(entries
+        .into_iter()
+        .filter_map(|entry| {
+            Some(Response {
+                index: u16::from(entry.index),
+                pokemon: fetch_pokemon::Response::from(pokemons.remove(&entry.number)?),
+            })
+        })
+        .collect())
+}
+
+/// Every Pokemon of a regional dex, in the dex order.
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Vec<Response>, Error> {
+    let name = DexName::try_from(req.region).map_err(|_| Error::BadRequest)?;
+    let entries = repo.fetch_regional_dex(name).map_err(Error::Unknown)?;
+    if entries.is_empty() {
+        return Err(Error::NotFound);
+    }
+
+    with_pokemons(&repo, entries).map_err(Error::Unknown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{DexIndex, PokemonForm};
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_not_found_when_the_dex_does_not_exist() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo, Request::new(String::from("johto"))) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_list_the_base_forms_in_dex_order() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![
+            Pokemon::pikachu(),
+            Pokemon::vulpix(),
+            Pokemon::vulpix().with_form(Some(PokemonForm::alola())),
+        ])
+        .unwrap();
+        let entry = |index, number| DexEntry::new(DexIndex::from(index), number);
+        repo.replace_regional_dex(
+            DexName::johto(),
+            vec![
+                entry(22, PokemonNumber::pikachu()),
+                entry(1, PokemonNumber::vulpix()),
+            ],
+        )
+        .unwrap();
+
+        let res = execute(repo, Request::new(String::from("Johto"))).unwrap();
+
+        let entries = res
+            .iter()
+            .map(|entry| {
+                (
+                    entry.index,
+                    entry.pokemon.number,
+                    entry.pokemon.form.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(entries, vec![(1, 37, None), (22, 25, None)]);
+    }
+}
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{DexIndex, DexName};
+use crate::domain::fetch_pokemon;
+use crate::repositories::pokemon::{FetchOneError, Repository, StorageError};
+
+pub use super::fetch_regional_dex::Response;
+
+pub struct Request {
+    region: String,
+    index: u16,
+}
+
+impl Request {
+    pub fn new(region: String, index: u16) -> Self {
+        Self { region, index }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    BadRequest,
+    NotFound,
+    Unknown(StorageError),
+}
+
+/// The Pokemon found at an index of a regional dex, in its base form.
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let name = DexName::try_from(req.region).map_err(|_| Error::BadRequest)?;
+    let index = DexIndex::from(req.index);
+    let entry = match repo.fetch_regional_dex(name) {
+        Ok(entries) => entries.into_iter().find(|entry| entry.index == index),
+        Err(e) => return Err(Error::Unknown(e)),
+    };
+    let entry = entry.ok_or(Error::NotFound)?;
+
+    match repo.fetch_one(entry.number.into()) {
+        Ok(pokemon) => Ok(Response {
+            index: u16::from(index),
+            pokemon: fetch_pokemon::Response::from(pokemon),
+        }),
+        Err(FetchOneError::NotFound) => Err(Error::NotFound),
+        Err(FetchOneError::Storage(e)) => Err(Error::Unknown(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{DexEntry, Pokemon, PokemonNumber};
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_find_the_pokemon_at_the_index() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![Pokemon::pikachu()]).unwrap();
+        repo.replace_regional_dex(
+            DexName::johto(),
+            vec![DexEntry::new(
+                DexIndex::from(22),
+                PokemonNumber::pikachu(),
+            )],
+        )
+        .unwrap();
+
+        let found = execute(repo.clone(), Request::new(String::from("johto"), 22)).unwrap();
+        let missing = execute(repo, Request::new(String::from("johto"), 23));
+
+        assert_eq!(found.pokemon.name, "Pikachu");
+        assert!(matches!(missing, Err(Error::NotFound)));
+    }
+}
@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{DexStatus, Pokemon, TrainerId};
+use crate::repositories::pokemon::{FetchTrainerError, Repository, StorageError};
+
+pub struct Request {
+    trainer: String,
+}
+
+impl Request {
+    pub fn new(trainer: String) -> Self {
+        Self { trainer }
+    }
+}
+
+/// What the trainer recorded about one Pokemon, in its base form.
+#[derive(Debug)]
+pub struct Response {
+    pub number: u16,
+    pub generation: Option<u8>,
+    pub name: String,
+    pub seen: bool,
+    pub caught: bool,
+    pub shiny: bool,
+}
+
+impl Response {
+    pub(super) fn new(pokemon: Pokemon, status: DexStatus) -> Self {
+        Self {
+            generation: pokemon.number.generation().map(u8::from),
+            number: u16::from(pokemon.number),
+            name: String::from(pokemon.name),
+            seen: status.seen(),
+            caught: status.caught(),
+            shiny: status.shiny(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    BadRequest,
+    NotFound,
+    Unknown(StorageError),
+}
+
+/// Every Pokemon the trainer has at least seen, sorted by number.
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Vec<Response>, Error> {
+    let trainer = TrainerId::try_from(req.trainer).map_err(|_| Error::BadRequest)?;
+    match repo.fetch_trainer(trainer.clone()) {
+        Ok(_) => {}
+        Err(FetchTrainerError::NotFound) => return Err(Error::NotFound),
+        Err(FetchTrainerError::Storage(e)) => return Err(Error::Unknown(e)),
+    }
+    let statuses = repo.fetch_trainer_dex(trainer).map_err(Error::Unknown)?;
+    let mut pokemons = super::fetch_regional_dex::base_forms(&repo).map_err(Error::Unknown)?;
+
+    Ok(statuses
+        .into_iter()
+        .filter_map(|(number, status)| Some(Response::new(pokemons.remove(&number)?, status)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{DexMark, Trainer};
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_return_not_found_when_the_trainer_does_not_exist() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo, Request::new(String::from("red"))) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_list_what_the_trainer_recorded() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![Pokemon::pikachu(), Pokemon::vulpix()])
+            .unwrap();
+        repo.insert_trainer(Trainer::red()).unwrap();
+        let caught = DexStatus::default().mark(DexMark::Caught);
+        repo.replace_dex_status(Trainer::red().id, Pokemon::pikachu().number, caught)
+            .unwrap();
+
+        let res = execute(repo, Request::new(String::from("red"))).unwrap();
+
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].name, "Pikachu");
+        assert!(res[0].seen && res[0].caught && !res[0].shiny);
+    }
+}
@@ -0,0 +1,617 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+
+use crate::domain::entities::{
+    FieldError, Pokemon, PokemonForm, PokemonKey, PokemonName, PokemonNumber, PokemonStats,
+    PokemonTypes,
+};
+use crate::repositories::pokemon::{
+    FetchAllError, FetchAllQuery, Repository, StorageError, UpsertAllError,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Csv,
+    /// A single JSON array of Pokemons.
+    Json,
+    /// One JSON Pokemon per line.
+    JsonLines,
+}
+
+impl TryFrom<String> for Format {
+    type Error = ();
+
+    fn try_from(format: String) -> Result<Self, Self::Error> {
+        match format.to_lowercase().as_str() {
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            "jsonl" => Ok(Format::JsonLines),
+            _ => Err(()),
+        }
+    }
+}
+
+/// What to do with a row whose number is already in the repository.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Mode {
+    /// Report the row as an error.
+    #[default]
+    Fail,
+    Skip,
+    Overwrite,
+}
+
+impl TryFrom<String> for Mode {
+    type Error = ();
+
+    fn try_from(mode: String) -> Result<Self, Self::Error> {
+        match mode.to_lowercase().as_str() {
+            "fail" => Ok(Mode::Fail),
+            "skip" => Ok(Mode::Skip),
+            "overwrite" => Ok(Mode::Overwrite),
+            _ => Err(()),
+        }
+    }
+}
+
+pub struct Request {
+    /// Either `csv`, `json` or `jsonl`.
+    pub format: String,
+    pub content: String,
+    /// Either `fail`, `skip` or `overwrite`, `fail` by default.
+    pub mode: Option<String>,
+    /// Validates every row without writing anything.
+    pub dry_run: bool,
+    /// Highest national number accepted.
+    pub national_dex_limit: u16,
+}
+
+/// The rows of the file that could not be imported, by line number.
+#[derive(Debug)]
+pub struct RowError {
+    pub line: usize,
+    pub errors: Vec<FieldError>,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub created: usize,
+    pub updated: usize,
+    pub skipped: usize,
+    pub errors: Vec<RowError>,
+    pub dry_run: bool,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    BadRequest,
+    /// The backend failed after writing only part of the valid rows.
+    Interrupted {
+        written: usize,
+        source: StorageError,
+    },
+    Unknown(StorageError),
+}
+
+/// Version of the file layout written by `export_pokemons`. Files without a
+/// version header are read as the current version.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Prefix of the comment line holding the schema version in CSV files.
+pub(super) const CSV_VERSION_PREFIX: &str = "#schema_version=";
+
+#[derive(Deserialize, Serialize)]
+pub(super) struct Header {
+    pub(super) schema_version: u32,
+}
+
+/// A JSON file is either a bare array of Pokemons or this envelope.
+#[derive(Deserialize)]
+struct Envelope<'a> {
+    schema_version: u32,
+    #[serde(borrow)]
+    pokemons: &'a RawValue,
+}
+
+#[derive(Deserialize, Serialize)]
+pub(super) struct CsvRow {
+    pub(super) number: u16,
+    pub(super) name: String,
+    /// Types separated by `/`, like in the CLI output.
+    pub(super) types: String,
+    pub(super) hp: u16,
+    pub(super) attack: u16,
+    pub(super) defense: u16,
+    pub(super) special_attack: u16,
+    pub(super) special_defense: u16,
+    pub(super) speed: u16,
+    /// Empty for the base form, and missing from files written before forms.
+    #[serde(default)]
+    pub(super) form: Option<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub(super) struct JsonStats {
+    pub(super) hp: u16,
+    pub(super) attack: u16,
+    pub(super) defense: u16,
+    pub(super) special_attack: u16,
+    pub(super) special_defense: u16,
+    pub(super) speed: u16,
+}
+
+#[derive(Deserialize, Serialize)]
+pub(super) struct JsonRow {
+    pub(super) number: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) form: Option<String>,
+    pub(super) name: String,
+    pub(super) types: Vec<String>,
+    pub(super) stats: JsonStats,
+}
+
+struct Row {
+    line: usize,
+    number: u16,
+    form: Option<String>,
+    name: String,
+    types: Vec<String>,
+    stats: [u16; 6],
+}
+
+impl From<CsvRow> for Row {
+    fn from(row: CsvRow) -> Self {
+        Self {
+            line: 0,
+            number: row.number,
+            form: row.form.filter(|form| !form.is_empty()),
+            name: row.name,
+            types: row.types.split('/').map(|tipe| tipe.to_owned()).collect(),
+            stats: [
+                row.hp,
+                row.attack,
+                row.defense,
+                row.special_attack,
+                row.special_defense,
+                row.speed,
+            ],
+        }
+    }
+}
+
+impl From<JsonRow> for Row {
+    fn from(row: JsonRow) -> Self {
+        let stats = row.stats;
+        Self {
+            line: 0,
+            number: row.number,
+            form: row.form,
+            name: row.name,
+            types: row.types,
+            stats: [
+                stats.hp,
+                stats.attack,
+                stats.defense,
+                stats.special_attack,
+                stats.special_defense,
+                stats.speed,
+            ],
+        }
+    }
+}
+
+fn malformed(line: usize, message: String) -> RowError {
+    RowError {
+        line,
+        errors: vec![FieldError {
+            field: "row".to_owned(),
+            code: "malformed",
+            message,
+        }],
+    }
+}
+
+fn line_of(content: &str, offset: usize) -> usize {
+    content[..offset].matches('\n').count() + 1
+}
+
+fn check_version(version: u32) -> Result<(), Error> {
+    match version {
+        1..=SCHEMA_VERSION => Ok(()),
+        _ => Err(Error::BadRequest),
+    }
+}
+
+fn parse_csv(content: &str) -> Result<Vec<Result<Row, RowError>>, Error> {
+    if let Some(line) = content.lines().next() {
+        if let Some(version) = line.trim().strip_prefix(CSV_VERSION_PREFIX) {
+            check_version(version.parse().map_err(|_| Error::BadRequest)?)?;
+        }
+    }
+    let mut reader = csv::ReaderBuilder::new()
+        .comment(Some(b'#'))
+        .from_reader(content.as_bytes());
+    let headers = reader.headers().map_err(|_| Error::BadRequest)?.clone();
+    Ok(reader
+        .records()
+        .map(|record| {
+            let record = record.map_err(|e| {
+                let line = e.position().map_or(0, |p| p.line() as usize);
+                malformed(line, e.to_string())
+            })?;
+            let line = record.position().map_or(0, |p| p.line() as usize);
+            match record.deserialize::<CsvRow>(Some(&headers)) {
+                Ok(row) => Ok(Row {
+                    line,
+                    ..Row::from(row)
+                }),
+                Err(e) => Err(malformed(line, e.to_string())),
+            }
+        })
+        .collect())
+}
+
+fn parse_json_row(content: &str, line: usize) -> Result<Row, RowError> {
+    match serde_json::from_str::<JsonRow>(content) {
+        Ok(row) => Ok(Row {
+            line,
+            ..Row::from(row)
+        }),
+        Err(e) => Err(malformed(line, e.to_string())),
+    }
+}
+
+fn parse_json(content: &str) -> Result<Vec<Result<Row, RowError>>, Error> {
+    if !content.trim_start().starts_with('{') {
+        return parse_json_array(content, 0);
+    }
+    let envelope = serde_json::from_str::<Envelope>(content).map_err(|_| Error::BadRequest)?;
+    check_version(envelope.schema_version)?;
+    let offset = envelope.pokemons.get().as_ptr() as usize - content.as_ptr() as usize;
+    parse_json_array(content, offset)
+}
+
+/// Walks the array starting at `offset` element by element, so that each row
+/// keeps the line it starts on.
+fn parse_json_array(content: &str, offset: usize) -> Result<Vec<Result<Row, RowError>>, Error> {
+    let skip_whitespace = |offset: usize| {
+        offset + content[offset..].len() - content[offset..].trim_start().len()
+    };
+
+    let mut offset = skip_whitespace(offset);
+    if !content[offset..].starts_with('[') {
+        return Err(Error::BadRequest);
+    }
+    offset = skip_whitespace(offset + 1);
+    if content[offset..].starts_with(']') {
+        return Ok(vec![]);
+    }
+
+    let mut rows = vec![];
+    loop {
+        let mut values =
+            serde_json::Deserializer::from_str(&content[offset..]).into_iter::<serde_json::Value>();
+        let value = match values.next() {
+            Some(Ok(value)) => value,
+            _ => return Err(Error::BadRequest),
+        };
+        let line = line_of(content, offset);
+        let end = offset + values.byte_offset();
+        rows.push(parse_json_row(&value.to_string(), line));
+
+        offset = skip_whitespace(end);
+        match content[offset..].chars().next() {
+            Some(',') => offset = skip_whitespace(offset + 1),
+            Some(']') => return Ok(rows),
+            _ => return Err(Error::BadRequest),
+        }
+    }
+}
+
+fn parse_json_lines(content: &str) -> Result<Vec<Result<Row, RowError>>, Error> {
+    let mut lines = content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .peekable();
+    if let Some((_, line)) = lines.peek() {
+        if let Ok(header) = serde_json::from_str::<Header>(line) {
+            check_version(header.schema_version)?;
+            lines.next();
+        }
+    }
+    Ok(lines
+        .map(|(index, line)| parse_json_row(line, index + 1))
+        .collect())
+}
+
+fn validate(row: Row, limit: u16) -> Result<Pokemon, RowError> {
+    match (
+        PokemonNumber::within(row.number, limit),
+        row.form.map(PokemonForm::try_from).transpose(),
+        PokemonName::try_from(row.name),
+        PokemonTypes::try_from(row.types),
+        PokemonStats::try_from(row.stats),
+    ) {
+        (Ok(number), Ok(form), Ok(name), Ok(types), Ok(stats)) => {
+            Ok(Pokemon::new(number, name, types, stats).with_form(form))
+        }
+        (number, form, name, types, stats) => {
+            let mut errors = vec![];
+            if let Err(e) = number {
+                errors.push(FieldError::new("number", &e));
+            }
+            if let Err(e) = form {
+                errors.push(FieldError::new("form", &e));
+            }
+            if let Err(e) = name {
+                errors.push(FieldError::new("name", &e));
+            }
+            if let Err(e) = types {
+                errors.push(FieldError::new("types", &e));
+            }
+            if let Err(e) = stats {
+                errors.push(FieldError::new("stats", &e));
+            }
+            Err(RowError {
+                line: row.line,
+                errors,
+            })
+        }
+    }
+}
+
+fn number_error(line: usize, code: &'static str, message: &str) -> RowError {
+    RowError {
+        line,
+        errors: vec![FieldError {
+            field: "number".to_owned(),
+            code,
+            message: message.to_owned(),
+        }],
+    }
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let format = Format::try_from(req.format).map_err(|_| Error::BadRequest)?;
+    let mode = match req.mode.map(Mode::try_from) {
+        Some(Ok(mode)) => mode,
+        Some(Err(_)) => return Err(Error::BadRequest),
+        None => Mode::default(),
+    };
+    let rows = match format {
+        Format::Csv => parse_csv(&req.content)?,
+        Format::Json => parse_json(&req.content)?,
+        Format::JsonLines => parse_json_lines(&req.content)?,
+    };
+
+    let existing = match repo.fetch_all(&FetchAllQuery::default()) {
+        Ok(pokemons) => pokemons
+            .into_iter()
+            .map(|p| p.key())
+            .collect::<HashSet<PokemonKey>>(),
+        Err(FetchAllError::Interrupted(e)) | Err(FetchAllError::Storage(e)) => {
+            return Err(Error::Unknown(e))
+        }
+    };
+
+    let mut res = Response {
+        created: 0,
+        updated: 0,
+        skipped: 0,
+        errors: vec![],
+        dry_run: req.dry_run,
+    };
+    let mut seen = HashSet::new();
+    let mut pokemons = vec![];
+    for row in rows {
+        let line = match &row {
+            Ok(row) => row.line,
+            Err(e) => e.line,
+        };
+        let pokemon = match row.and_then(|row| validate(row, req.national_dex_limit)) {
+            Ok(pokemon) => pokemon,
+            Err(e) => {
+                res.errors.push(e);
+                continue;
+            }
+        };
+        let key = pokemon.key();
+        if !seen.insert(key.clone()) {
+            res.errors.push(number_error(
+                line,
+                "duplicated",
+                "the number and form appear more than once in the file",
+            ));
+            continue;
+        }
+        if existing.contains(&key) {
+            match mode {
+                Mode::Fail => {
+                    res.errors
+                        .push(number_error(line, "conflict", "the Pokemon already exists"));
+                    continue;
+                }
+                Mode::Skip => {
+                    res.skipped += 1;
+                    continue;
+                }
+                Mode::Overwrite => res.updated += 1,
+            }
+        } else {
+            res.created += 1;
+        }
+        pokemons.push(pokemon);
+    }
+
+    if req.dry_run || pokemons.is_empty() {
+        return Ok(res);
+    }
+    match repo.upsert_all(pokemons) {
+        Ok(()) => Ok(res),
+        Err(UpsertAllError::Interrupted { written, source }) => {
+            Err(Error::Interrupted { written, source })
+        }
+        Err(UpsertAllError::Storage(e)) => Err(Error::Unknown(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::DEFAULT_NATIONAL_DEX_LIMIT;
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    const CSV: &str = "number,name,types,hp,attack,defense,special_attack,special_defense,speed
+25,Pikachu,Electric,35,55,40,50,50,90
+37,Vulpix,Fire,38,41,40,50,65,65
+";
+
+    fn request(format: &str, content: &str, mode: Option<&str>) -> Request {
+        Request {
+            format: String::from(format),
+            content: String::from(content),
+            mode: mode.map(String::from),
+            dry_run: false,
+            national_dex_limit: DEFAULT_NATIONAL_DEX_LIMIT,
+        }
+    }
+
+    fn repo_with_pikachu() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu().into(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .ok();
+        repo
+    }
+
+    #[test]
+    fn it_should_import_every_csv_row() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        let res = execute(repo.clone(), request("csv", CSV, None)).unwrap();
+
+        assert_eq!(res.created, 2);
+        assert!(res.errors.is_empty());
+        assert_eq!(repo.fetch_all(&FetchAllQuery::default()).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn it_should_report_invalid_rows_with_their_line_and_import_the_others() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let content = "[
+  {\"number\": 25, \"name\": \"Pikachu\", \"types\": [\"Electric\"],
+   \"stats\": {\"hp\": 35, \"attack\": 55, \"defense\": 40, \"special_attack\": 50, \"special_defense\": 50, \"speed\": 90}},
+  {\"number\": 0, \"name\": \"\", \"types\": [\"Fire\"],
+   \"stats\": {\"hp\": 38, \"attack\": 41, \"defense\": 40, \"special_attack\": 50, \"special_defense\": 65, \"speed\": 65}},
+  {\"number\": 37}
+]";
+
+        let res = execute(repo.clone(), request("json", content, None)).unwrap();
+
+        assert_eq!(res.created, 1);
+        assert_eq!(res.errors.len(), 2);
+        assert_eq!(res.errors[0].line, 4);
+        let fields = res.errors[0]
+            .errors
+            .iter()
+            .map(|e| e.field.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(fields, vec!["number", "name"]);
+        assert_eq!(res.errors[1].line, 6);
+        assert_eq!(res.errors[1].errors[0].code, "malformed");
+        assert_eq!(repo.fetch_all(&FetchAllQuery::default()).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn it_should_report_conflicts_by_default() {
+        let repo = repo_with_pikachu();
+
+        let res = execute(repo, request("csv", CSV, None)).unwrap();
+
+        assert_eq!(res.created, 1);
+        assert_eq!(res.errors.len(), 1);
+        assert_eq!(res.errors[0].line, 2);
+        assert_eq!(res.errors[0].errors[0].code, "conflict");
+    }
+
+    #[test]
+    fn it_should_skip_or_overwrite_existing_pokemons() {
+        let content = CSV.replace("Pikachu", "Raichu");
+
+        let skipped = repo_with_pikachu();
+        let res = execute(skipped.clone(), request("csv", &content, Some("skip"))).unwrap();
+        assert_eq!((res.created, res.skipped, res.updated), (1, 1, 0));
+        let pikachu = skipped.fetch_one(PokemonNumber::pikachu().into()).unwrap();
+        assert_eq!(pikachu.name.as_str(), "Pikachu");
+
+        let overwritten = repo_with_pikachu();
+        let res = execute(overwritten.clone(), request("csv", &content, Some("overwrite"))).unwrap();
+        assert_eq!((res.created, res.skipped, res.updated), (1, 0, 1));
+        let raichu = overwritten.fetch_one(PokemonNumber::pikachu().into()).unwrap();
+        assert_eq!(raichu.name.as_str(), "Raichu");
+    }
+
+    #[test]
+    fn it_should_not_write_anything_on_a_dry_run() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let content = "{\"number\": 25, \"name\": \"Pikachu\", \"types\": [\"Electric\"], \"stats\": {\"hp\": 35, \"attack\": 55, \"defense\": 40, \"special_attack\": 50, \"special_defense\": 50, \"speed\": 90}}
+
+{\"number\": 37, \"name\": \"Vulpix\", \"types\": [\"Fire\"], \"stats\": {\"hp\": 38, \"attack\": 41, \"defense\": 40, \"special_attack\": 50, \"special_defense\": 65, \"speed\": 65}}
+";
+        let req = Request {
+            dry_run: true,
+            national_dex_limit: DEFAULT_NATIONAL_DEX_LIMIT,
+            ..request("jsonl", content, None)
+        };
+
+        let res = execute(repo.clone(), req).unwrap();
+
+        assert_eq!(res.created, 2);
+        assert!(res.dry_run);
+        assert!(repo.fetch_all(&FetchAllQuery::default()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn it_should_count_the_version_header_in_line_numbers() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let content = format!("#schema_version=1\n{}", CSV.replace("37,", "0,"));
+
+        let res = execute(repo, request("csv", &content, None)).unwrap();
+
+        assert_eq!(res.created, 1);
+        assert_eq!(res.errors[0].line, 4);
+    }
+
+    #[test]
+    fn it_should_refuse_files_from_a_newer_schema() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let content = "{\"schema_version\": 2}\n";
+
+        let res = execute(repo, request("jsonl", content, None));
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_return_bad_request_when_format_is_unknown() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        let res = execute(repo, request("xml", CSV, None));
+
+        match res {
+            Err(Error::BadRequest) => {}
+            _ => unreachable!(),
+        };
+    }
+}
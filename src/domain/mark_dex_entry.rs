@@ -0,0 +1,123 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{DexMark, DexStatus, FieldError, PokemonNumber, TrainerId};
+use crate::repositories::pokemon::{FetchOneError, FetchTrainerError, Repository, StorageError};
+
+pub use super::fetch_trainer_dex::Response;
+
+pub struct Request {
+    pub trainer: String,
+    pub number: u16,
+    /// `seen`, `caught` or `shiny`.
+    pub mark: String,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    BadRequest(Vec<FieldError>),
+    /// Either the trainer or the Pokemon does not exist.
+    NotFound,
+    Unknown(StorageError),
+}
+
+/// Applies `change` to what the trainer recorded about the Pokemon.
+pub(super) fn update(
+    repo: Arc<dyn Repository>,
+    req: Request,
+    change: fn(DexStatus, DexMark) -> DexStatus,
+) -> Result<Response, Error> {
+    let mut errors = vec![];
+    let trainer =
+        TrainerId::try_from(req.trainer).map_err(|e| errors.push(FieldError::new("trainer", &e)));
+    let number =
+        PokemonNumber::try_from(req.number).map_err(|e| errors.push(FieldError::new("number", &e)));
+    let mark = DexMark::try_from(req.mark).map_err(|e| errors.push(FieldError::new("mark", &e)));
+    let (trainer, number, mark) = match (trainer, number, mark) {
+        (Ok(trainer), Ok(number), Ok(mark)) => (trainer, number, mark),
+        _ => return Err(Error::BadRequest(errors)),
+    };
+
+    match repo.fetch_trainer(trainer.clone()) {
+        Ok(_) => {}
+        Err(FetchTrainerError::NotFound) => return Err(Error::NotFound),
+        Err(FetchTrainerError::Storage(e)) => return Err(Error::Unknown(e)),
+    }
+    let pokemon = match repo.fetch_one(number.clone().into()) {
+        Ok(pokemon) => pokemon,
+        Err(FetchOneError::NotFound) => return Err(Error::NotFound),
+        Err(FetchOneError::Storage(e)) => return Err(Error::Unknown(e)),
+    };
+
+    let status = repo
+        .fetch_trainer_dex(trainer.clone())
+        .map_err(Error::Unknown)?
+        .into_iter()
+        .find(|(recorded, _)| *recorded == number)
+        .map(|(_, status)| status)
+        .unwrap_or_default();
+    let status = repo
+        .replace_dex_status(trainer, number, change(status, mark))
+        .map_err(Error::Unknown)?;
+    Ok(Response::new(pokemon, status))
+}
+
+/// Records that the trainer has seen, caught or found a shiny Pokemon,
+/// along with what it implies.
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    update(repo, req, DexStatus::mark)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Pokemon, Trainer};
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    fn request(trainer: &str, number: u16, mark: &str) -> Request {
+        Request {
+            trainer: String::from(trainer),
+            number,
+            mark: String::from(mark),
+        }
+    }
+
+    fn repo() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![Pokemon::pikachu()]).unwrap();
+        repo.insert_trainer(Trainer::red()).unwrap();
+        repo
+    }
+
+    #[test]
+    fn it_should_mark_what_the_catch_implies() {
+        let repo = repo();
+
+        let res = execute(repo.clone(), request("red", 25, "caught")).unwrap();
+
+        assert!(res.seen && res.caught && !res.shiny);
+        assert_eq!(repo.fetch_trainer_dex(Trainer::red().id).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn it_should_return_not_found_for_unknown_trainers_and_pokemons() {
+        let repo = repo();
+
+        for req in [request("blue", 25, "seen"), request("red", 37, "seen")] {
+            match execute(repo.clone(), req) {
+                Err(Error::NotFound) => {}
+                _ => unreachable!(),
+            };
+        }
+    }
+
+    #[test]
+    fn it_should_describe_invalid_fields() {
+        match execute(repo(), request("red", 0, "owned")) {
+            Err(Error::BadRequest(errors)) => {
+                let codes = errors.iter().map(|e| e.code).collect::<Vec<_>>();
+                assert_eq!(codes, vec!["out_of_range", "unknown_mark"]);
+            }
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,306 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::domain::entities::{Pokemon, PokemonKey};
+use crate::domain::import_pokemons::Mode;
+use crate::repositories::pokemon::{
+    FetchAllError, FetchAllQuery, Repository, StorageError, UpsertAllError,
+};
+
+/// Pokemons are written to the target this many at a time, and progress is
+/// reported after each batch.
+const BATCH_SIZE: usize = 50;
+
+pub struct Request {
+    /// Either `fail`, `skip` or `overwrite`, `fail` by default.
+    pub mode: Option<String>,
+    /// Compares both repositories once everything has been written.
+    pub verify: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Progress {
+    pub written: usize,
+    pub total: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Mismatch {
+    Missing(PokemonKey),
+    Different(PokemonKey),
+}
+
+impl Mismatch {
+    pub fn key(&self) -> &PokemonKey {
+        match self {
+            Mismatch::Missing(key) | Mismatch::Different(key) => key,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub created: usize,
+    pub updated: usize,
+    pub skipped: usize,
+    /// Only set when the migration was verified.
+    pub mismatches: Option<Vec<Mismatch>>,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    BadRequest,
+    /// Pokemons that already exist in the target. Nothing has been written.
+    Conflict(Vec<PokemonKey>),
+    Source(StorageError),
+    Target(StorageError),
+    /// The target failed after the first `written` Pokemons.
+    Interrupted {
+        written: usize,
+        source: StorageError,
+    },
+}
+
+fn fetch_all(repo: &Arc<dyn Repository>) -> Result<Vec<Pokemon>, StorageError> {
+    match repo.fetch_all(&FetchAllQuery::default()) {
+        Ok(pokemons) => Ok(pokemons),
+        Err(FetchAllError::Interrupted(e)) | Err(FetchAllError::Storage(e)) => Err(e),
+    }
+}
+
+fn verify(
+    target: &Arc<dyn Repository>,
+    written: &[Pokemon],
+    skipped: &[PokemonKey],
+) -> Result<Vec<Mismatch>, Error> {
+    let migrated = fetch_all(target)
+        .map_err(Error::Target)?
+        .into_iter()
+        .map(|pokemon| (pokemon.key(), pokemon))
+        .collect::<HashMap<PokemonKey, Pokemon>>();
+
+    let mut mismatches = vec![];
+    for pokemon in written {
+        let key = pokemon.key();
+        match migrated.get(&key) {
+            Some(migrated) if migrated == pokemon => {}
+            Some(_) => mismatches.push(Mismatch::Different(key)),
+            None => mismatches.push(Mismatch::Missing(key)),
+        }
+    }
+    for key in skipped {
+        if !migrated.contains_key(key) {
+            mismatches.push(Mismatch::Missing(key.clone()));
+        }
+    }
+    mismatches.sort_by(|a, b| a.key().cmp(b.key()));
+    Ok(mismatches)
+}
+
+/// Copies every Pokemon of `from` into `to`, calling `progress` after each batch.
+pub fn execute(
+    from: Arc<dyn Repository>,
+    to: Arc<dyn Repository>,
+    req: Request,
+    progress: &dyn Fn(Progress),
+) -> Result<Response, Error> {
+    let mode = match req.mode.map(Mode::try_from) {
+        Some(Ok(mode)) => mode,
+        Some(Err(_)) => return Err(Error::BadRequest),
+        None => Mode::default(),
+    };
+
+    let pokemons = fetch_all(&from).map_err(Error::Source)?;
+    let existing = fetch_all(&to)
+        .map_err(Error::Target)?
+        .into_iter()
+        .map(|pokemon| pokemon.key())
+        .collect::<HashSet<PokemonKey>>();
+
+    let (conflicts, created): (Vec<Pokemon>, Vec<Pokemon>) = pokemons
+        .into_iter()
+        .partition(|pokemon| existing.contains(&pokemon.key()));
+    let conflicts_keys = conflicts.iter().map(Pokemon::key).collect::<Vec<PokemonKey>>();
+
+    let mut res = Response {
+        created: created.len(),
+        updated: 0,
+        skipped: 0,
+        mismatches: None,
+    };
+    let mut skipped = vec![];
+    let mut pokemons = created;
+    match mode {
+        Mode::Fail if !conflicts.is_empty() => return Err(Error::Conflict(conflicts_keys)),
+        Mode::Fail => {}
+        Mode::Skip => {
+            res.skipped = conflicts.len();
+            skipped = conflicts_keys;
+        }
+        Mode::Overwrite => {
+            res.updated = conflicts.len();
+            pokemons.extend(conflicts);
+        }
+    }
+
+    let total = pokemons.len();
+    let mut written = 0;
+    progress(Progress { written, total });
+    for batch in pokemons.chunks(BATCH_SIZE) {
+        match to.upsert_all(batch.to_vec()) {
+            Ok(()) => written += batch.len(),
+            Err(UpsertAllError::Interrupted {
+                written: batch_written,
+                source,
+            }) => {
+                return Err(Error::Interrupted {
+                    written: written + batch_written,
+                    source,
+                })
+            }
+            Err(UpsertAllError::Storage(e)) if written == 0 => return Err(Error::Target(e)),
+            Err(UpsertAllError::Storage(source)) => {
+                return Err(Error::Interrupted { written, source })
+            }
+        }
+        progress(Progress { written, total });
+    }
+
+    if req.verify {
+        res.mismatches = Some(verify(&to, &pokemons, &skipped)?);
+    }
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::domain::entities::{
+        PokemonForm, PokemonName, PokemonNumber, PokemonStats, PokemonTypes,
+    };
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    fn repo(pokemons: Vec<Pokemon>) -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(pokemons).unwrap();
+        repo
+    }
+
+    fn raichu() -> Pokemon {
+        Pokemon::new(
+            PokemonNumber::pikachu(),
+            PokemonName::try_from(String::from("Raichu")).unwrap(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+    }
+
+    fn request(mode: &str) -> Request {
+        Request {
+            mode: Some(String::from(mode)),
+            verify: true,
+        }
+    }
+
+    #[test]
+    fn it_should_copy_every_pokemon_and_report_progress() {
+        let pokemons = (1..=120)
+            .map(|number| {
+                Pokemon::new(
+                    PokemonNumber::try_from(number).unwrap(),
+                    PokemonName::pikachu(),
+                    PokemonTypes::pikachu(),
+                    PokemonStats::pikachu(),
+                )
+            })
+            .collect();
+        let from = repo(pokemons);
+        let to = Arc::new(InMemoryRepository::new());
+        let reported = Mutex::new(vec![]);
+
+        let res = execute(from, to.clone(), request("fail"), &|p| {
+            reported.lock().unwrap().push(p.written)
+        })
+        .unwrap();
+
+        assert_eq!(res.created, 120);
+        assert_eq!(res.mismatches, Some(vec![]));
+        assert_eq!(*reported.lock().unwrap(), vec![0, 50, 100, 120]);
+        assert_eq!(to.fetch_all(&FetchAllQuery::default()).unwrap().len(), 120);
+    }
+
+    #[test]
+    fn it_should_write_nothing_when_failing_on_conflicts() {
+        let from = repo(vec![Pokemon::pikachu(), Pokemon::vulpix()]);
+        let to = repo(vec![raichu()]);
+
+        let res = execute(from, to.clone(), request("fail"), &|_| {});
+
+        match res {
+            Err(Error::Conflict(keys)) => {
+                assert_eq!(keys, vec![PokemonKey::from(PokemonNumber::pikachu())])
+            }
+            _ => unreachable!(),
+        };
+        assert_eq!(to.fetch_all(&FetchAllQuery::default()).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn it_should_skip_or_overwrite_conflicts() {
+        let from = repo(vec![Pokemon::pikachu(), Pokemon::vulpix()]);
+
+        let skipped = repo(vec![raichu()]);
+        let res = execute(from.clone(), skipped.clone(), request("skip"), &|_| {}).unwrap();
+        assert_eq!((res.created, res.updated, res.skipped), (1, 0, 1));
+        let pokemon = skipped.fetch_one(PokemonNumber::pikachu().into()).unwrap();
+        assert_eq!(pokemon.name.as_str(), "Raichu");
+
+        let overwritten = repo(vec![raichu()]);
+        let res = execute(from, overwritten.clone(), request("overwrite"), &|_| {}).unwrap();
+        assert_eq!((res.created, res.updated, res.skipped), (1, 1, 0));
+        assert_eq!(res.mismatches, Some(vec![]));
+        let pokemon = overwritten.fetch_one(PokemonNumber::pikachu().into()).unwrap();
+        assert_eq!(pokemon.name.as_str(), "Pikachu");
+    }
+
+    fn alolan_vulpix() -> Pokemon {
+        Pokemon {
+            types: PokemonTypes::alolan_vulpix(),
+            ..Pokemon::vulpix()
+        }
+        .with_form(Some(PokemonForm::alola()))
+    }
+
+    #[test]
+    fn it_should_migrate_the_forms_of_a_number_apart() {
+        let from = repo(vec![Pokemon::vulpix(), alolan_vulpix()]);
+        let empty = Arc::new(InMemoryRepository::new());
+        let with_base_form = repo(vec![Pokemon::vulpix()]);
+
+        let both = execute(from.clone(), empty.clone(), request("fail"), &|_| {}).unwrap();
+        let alola = execute(from, with_base_form.clone(), request("skip"), &|_| {}).unwrap();
+
+        assert_eq!((both.created, both.skipped), (2, 0));
+        assert_eq!(both.mismatches, Some(vec![]));
+        assert_eq!(empty.fetch_all(&FetchAllQuery::default()).unwrap().len(), 2);
+        assert_eq!((alola.created, alola.skipped), (1, 1));
+        assert_eq!(alola.mismatches, Some(vec![]));
+        let key = PokemonKey::new(PokemonNumber::vulpix(), Some(PokemonForm::alola()));
+        assert_eq!(with_base_form.fetch_one(key).unwrap(), alolan_vulpix());
+    }
+
+    #[test]
+    fn it_should_return_a_source_error_when_the_source_fails() {
+        let from = Arc::new(InMemoryRepository::new().with_error());
+        let to = Arc::new(InMemoryRepository::new());
+
+        let res = execute(from, to, request("fail"), &|_| {});
+
+        match res {
+            Err(Error::Source(_)) => {}
+            _ => unreachable!(),
+        };
+    }
+}
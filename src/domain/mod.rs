@@ -1,5 +1,34 @@
+pub mod create_ability;
+pub mod create_evolution;
+pub mod create_move;
 pub mod create_pokemon;
+pub mod create_trainer;
 pub mod entities;
+pub mod export_pokemons;
+pub mod fetch_ability_holders;
+pub mod fetch_all_abilities;
+pub mod fetch_all_moves;
 pub mod fetch_all_pokemons;
+pub mod fetch_dex_completion;
+pub mod fetch_evolution_chain;
+pub mod fetch_learnset;
+pub mod fetch_move;
 pub mod fetch_pokemon;
+pub mod fetch_pokemon_abilities;
+pub mod fetch_pokemon_forms;
+pub mod fetch_pokemon_matchups;
+pub mod fetch_regional_dex;
+pub mod fetch_regional_dex_entry;
+pub mod fetch_trainer_dex;
+pub mod import_pokemons;
+pub mod mark_dex_entry;
+pub mod migrate_pokemons;
+pub mod delete_move;
 pub mod delete_pokemon;
+pub mod type_chart;
+pub mod unmark_dex_entry;
+pub mod update_learnset;
+pub mod update_move;
+pub mod update_pokemon;
+pub mod update_pokemon_abilities;
+pub mod update_regional_dex;
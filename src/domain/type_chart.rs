@@ -0,0 +1,109 @@
+use super::entities::{PokemonType, PokemonTypes};
+
+use PokemonType::*;
+
+/// Damage multiplier of a single attacking type against a single defending type.
+pub fn effectiveness(attack: PokemonType, defense: PokemonType) -> f32 {
+    let (super_effective, not_very_effective, no_effect): (
+        &[PokemonType],
+        &[PokemonType],
+        &[PokemonType],
+    ) = match attack {
+        Normal => (&[], &[Rock, Steel], &[Ghost]),
+        Fire => (&[Grass, Ice, Bug, Steel], &[Fire, Water, Rock, Dragon], &[]),
+        Water => (&[Fire, Ground, Rock], &[Water, Grass, Dragon], &[]),
+        Electric => (&[Water, Flying], &[Electric, Grass, Dragon], &[Ground]),
+        Grass => (
+            &[Water, Ground, Rock],
+            &[Fire, Grass, Poison, Flying, Bug, Dragon, Steel],
+            &[],
+        ),
+        Ice => (&[Grass, Ground, Flying, Dragon], &[Fire, Water, Ice, Steel], &[]),
+        Fighting => (
+            &[Normal, Ice, Rock, Dark, Steel],
+            &[Poison, Flying, Psychic, Bug, Fairy],
+            &[Ghost],
+        ),
+        Poison => (&[Grass, Fairy], &[Poison, Ground, Rock, Ghost], &[Steel]),
+        Ground => (
+            &[Fire, Electric, Poison, Rock, Steel],
+            &[Grass, Bug],
+            &[Flying],
+        ),
+        Flying => (&[Grass, Fighting, Bug], &[Electric, Rock, Steel], &[]),
+        Psychic => (&[Fighting, Poison], &[Psychic, Steel], &[Dark]),
+        Bug => (
+            &[Grass, Psychic, Dark],
+            &[Fire, Fighting, Poison, Flying, Ghost, Steel, Fairy],
+            &[],
+        ),
+        Rock => (&[Fire, Ice, Flying, Bug], &[Fighting, Ground, Steel], &[]),
+        Ghost => (&[Psychic, Ghost], &[Dark], &[Normal]),
+        Dragon => (&[Dragon], &[Steel], &[Fairy]),
+        Dark => (&[Psychic, Ghost], &[Fighting, Dark, Fairy], &[]),
+        Steel => (&[Ice, Rock, Fairy], &[Fire, Water, Electric, Steel], &[]),
+        Fairy => (&[Fighting, Dragon, Dark], &[Fire, Poison, Steel], &[]),
+    };
+
+    if no_effect.contains(&defense) {
+        0.0
+    } else if super_effective.contains(&defense) {
+        2.0
+    } else if not_very_effective.contains(&defense) {
+        0.5
+    } else {
+        1.0
+    }
+}
+
+/// Combined multiplier of an attacking type against every type of the defender,
+/// which is one of 0, 0.25, 0.5, 1, 2 or 4 for a dual-typed defender.
+pub fn multiplier(attack: PokemonType, defense: &PokemonTypes) -> f32 {
+    defense
+        .as_slice()
+        .iter()
+        .map(|tipe| effectiveness(attack, *tipe))
+        .product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn types(names: &[&str]) -> PokemonTypes {
+        PokemonTypes::try_from(names.iter().map(|n| n.to_string()).collect::<Vec<String>>())
+            .expect("types should be valid")
+    }
+
+    #[test]
+    fn it_should_return_no_effect() {
+        assert_eq!(multiplier(Electric, &types(&["Ground"])), 0.0);
+        assert_eq!(multiplier(Dragon, &types(&["Fairy", "Steel"])), 0.0);
+    }
+
+    #[test]
+    fn it_should_return_quarter_damage() {
+        assert_eq!(multiplier(Fire, &types(&["Water", "Dragon"])), 0.25);
+    }
+
+    #[test]
+    fn it_should_return_half_damage() {
+        assert_eq!(multiplier(Electric, &types(&["Electric"])), 0.5);
+    }
+
+    #[test]
+    fn it_should_return_neutral_damage() {
+        assert_eq!(multiplier(Normal, &types(&["Fire"])), 1.0);
+        assert_eq!(multiplier(Ice, &types(&["Water", "Flying"])), 1.0);
+    }
+
+    #[test]
+    fn it_should_return_double_damage() {
+        assert_eq!(multiplier(Water, &types(&["Fire"])), 2.0);
+    }
+
+    #[test]
+    fn it_should_return_quadruple_damage() {
+        assert_eq!(multiplier(Ice, &types(&["Dragon", "Flying"])), 4.0);
+    }
+}
@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use crate::domain::entities::DexStatus;
+use crate::repositories::pokemon::Repository;
+
+pub use super::mark_dex_entry::{Error, Request, Response};
+
+/// Clears a mark the trainer set, along with the marks implying it. Clearing
+/// `seen` forgets the Pokemon altogether.
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    super::mark_dex_entry::update(repo, req, DexStatus::unmark)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{DexMark, Pokemon, Trainer};
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    #[test]
+    fn it_should_clear_the_marks_implying_the_cleared_one() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![Pokemon::pikachu()]).unwrap();
+        repo.insert_trainer(Trainer::red()).unwrap();
+        let shiny = DexStatus::default().mark(DexMark::Shiny);
+        repo.replace_dex_status(Trainer::red().id, Pokemon::pikachu().number, shiny)
+            .unwrap();
+        let request = |mark: &str| Request {
+            trainer: String::from("red"),
+            number: 25,
+            mark: String::from(mark),
+        };
+
+        let uncaught = execute(repo.clone(), request("caught")).unwrap();
+        let unseen = execute(repo.clone(), request("seen")).unwrap();
+
+        assert!(uncaught.seen && !uncaught.caught && !uncaught.shiny);
+        assert!(!unseen.seen);
+        assert!(repo
+            .fetch_trainer_dex(Trainer::red().id)
+            .unwrap()
+            .is_empty());
+    }
+}
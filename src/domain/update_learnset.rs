@@ -0,0 +1,157 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{FieldError, LearnMethod, LearnsetEntry, MoveId, PokemonNumber};
+use crate::repositories::pokemon::{FetchOneError, Repository, StorageError};
+
+pub use super::fetch_learnset::Response;
+
+pub struct Entry {
+    pub id: u16,
+    /// `level:N`, `machine`, `egg` or `tutor`.
+    pub method: String,
+}
+
+pub struct Request {
+    pub number: u16,
+    pub moves: Vec<Entry>,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    BadRequest(Vec<FieldError>),
+    NotFound,
+    Unknown(StorageError),
+}
+
+fn validate(entries: Vec<Entry>) -> Result<Vec<LearnsetEntry>, Vec<FieldError>> {
+    let mut errors = vec![];
+    let mut learnset: Vec<LearnsetEntry> = vec![];
+    for (index, entry) in entries.into_iter().enumerate() {
+        let field = |name: &str| format!("moves[{index}].{name}");
+        let id = MoveId::try_from(entry.id).map_err(|e| errors.push(FieldError::new(&field("id"), &e)));
+        let method = LearnMethod::try_from(entry.method)
+            .map_err(|e| errors.push(FieldError::new(&field("method"), &e)));
+        if let (Ok(id), Ok(method)) = (id, method) {
+            let entry = LearnsetEntry::new(id, method);
+            if learnset.contains(&entry) {
+                errors.push(FieldError {
+                    field: field("method"),
+                    code: "duplicate",
+                    message: String::from("the move is already learned this way"),
+                });
+            }
+            learnset.push(entry);
+        }
+    }
+
+    match errors.is_empty() {
+        true => Ok(learnset),
+        false => Err(errors),
+    }
+}
+
+/// Replaces the whole learnset of a Pokemon. Every move must already exist.
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Vec<Response>, Error> {
+    let number = PokemonNumber::try_from(req.number)
+        .map_err(|e| Error::BadRequest(vec![FieldError::new("number", &e)]))?;
+    let learnset = validate(req.moves).map_err(Error::BadRequest)?;
+
+    match repo.fetch_one(number.clone().into()) {
+        Ok(_) => {}
+        Err(FetchOneError::NotFound) => return Err(Error::NotFound),
+        Err(FetchOneError::Storage(e)) => return Err(Error::Unknown(e)),
+    }
+    let moves = repo.fetch_moves().map_err(Error::Unknown)?;
+    let unknown = learnset
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| !moves.iter().any(|known| known.id == entry.move_id))
+        .map(|(index, entry)| FieldError {
+            field: format!("moves[{index}].id"),
+            code: "unknown_move",
+            message: format!("move {} does not exist", u16::from(entry.move_id.clone())),
+        })
+        .collect::<Vec<FieldError>>();
+    if !unknown.is_empty() {
+        return Err(Error::BadRequest(unknown));
+    }
+
+    let learnset = repo.replace_learnset(number, learnset).map_err(Error::Unknown)?;
+    super::fetch_learnset::with_moves(&repo, learnset).map_err(Error::Unknown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Move, Pokemon};
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    fn entry(id: u16, method: &str) -> Entry {
+        Entry {
+            id,
+            method: String::from(method),
+        }
+    }
+
+    fn repo() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![Pokemon::pikachu()]).unwrap();
+        repo.insert_move(Move::thunder_shock()).unwrap();
+        repo.insert_move(Move::swift()).unwrap();
+        repo
+    }
+
+    #[test]
+    fn it_should_replace_the_learnset_otherwise() {
+        let repo = repo();
+        let req = Request {
+            number: 25,
+            moves: vec![entry(129, "tutor"), entry(84, "level:1"), entry(84, "machine")],
+        };
+
+        let res = execute(repo, req).unwrap();
+
+        let moves = res
+            .iter()
+            .map(|learned| (learned.id, learned.method.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(moves, vec![(84, "level:1"), (84, "machine"), (129, "tutor")]);
+    }
+
+    #[test]
+    fn it_should_reject_invalid_duplicate_and_unknown_moves() {
+        let repo = repo();
+        let req = Request {
+            number: 25,
+            moves: vec![entry(84, "level:0"), entry(129, "egg"), entry(129, "Egg")],
+        };
+        match execute(repo.clone(), req) {
+            Err(Error::BadRequest(errors)) => {
+                let fields = errors.iter().map(|e| e.field.as_str()).collect::<Vec<&str>>();
+                assert_eq!(fields, vec!["moves[0].method", "moves[2].method"]);
+            }
+            _ => unreachable!(),
+        };
+
+        let req = Request {
+            number: 25,
+            moves: vec![entry(84, "egg"), entry(1, "egg")],
+        };
+        match execute(repo.clone(), req) {
+            Err(Error::BadRequest(errors)) => {
+                assert_eq!(errors[0].field, "moves[1].id");
+                assert_eq!(errors[0].code, "unknown_move");
+            }
+            _ => unreachable!(),
+        };
+
+        let req = Request {
+            number: 26,
+            moves: vec![],
+        };
+        match execute(repo, req) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+}
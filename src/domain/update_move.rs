@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use crate::domain::entities::FieldError;
+use crate::repositories::pokemon::{Repository, StorageError, UpdateMoveError};
+
+pub use super::create_move::Request;
+pub use super::fetch_move::Response;
+
+#[derive(Debug)]
+pub enum Error {
+    BadRequest(Vec<FieldError>),
+    NotFound,
+    Unknown(StorageError),
+}
+
+/// Replaces every field of the move with the given id.
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let new_move = super::create_move::validate(req).map_err(Error::BadRequest)?;
+
+    match repo.update_move(new_move) {
+        Ok(updated) => Ok(Response::from(updated)),
+        Err(UpdateMoveError::NotFound) => Err(Error::NotFound),
+        Err(UpdateMoveError::Storage(e)) => Err(Error::Unknown(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::Move;
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    fn request(id: u16) -> Request {
+        Request {
+            id,
+            name: String::from("Swift"),
+            tipe: String::from("Normal"),
+            category: String::from("special"),
+            power: Some(60),
+            accuracy: None,
+            pp: 5,
+        }
+    }
+
+    #[test]
+    fn it_should_replace_the_move_otherwise() {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert_move(Move::swift()).unwrap();
+
+        let res = execute(repo.clone(), request(129)).unwrap();
+
+        assert_eq!(res.pp, 5);
+        assert_eq!(u16::from(repo.fetch_move(Move::swift().id).unwrap().pp), 5);
+    }
+
+    #[test]
+    fn it_should_return_a_not_found_error_when_the_move_does_not_exist() {
+        let repo = Arc::new(InMemoryRepository::new());
+
+        match execute(repo, request(129)) {
+            Err(Error::NotFound) => {}
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,213 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{
+    FieldError, PokemonForm, PokemonKey, PokemonName, PokemonNumber, PokemonStats, PokemonTypes,
+};
+use crate::repositories::pokemon::{FetchOneError, Repository, StorageError, UpdateError};
+
+/// Fields left as `None` keep their current value, so a request with every
+/// field set replaces the Pokemon and a request with some of them patches it.
+pub struct Request {
+    pub number: u16,
+    /// Unset for the base form.
+    pub form: Option<String>,
+    pub name: Option<String>,
+    pub types: Option<Vec<String>>,
+    pub stats: Option<[u16; 6]>,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub number: u16,
+    pub generation: Option<u8>,
+    pub form: Option<String>,
+    pub name: String,
+    pub types: Vec<String>,
+    pub stats: [u16; 6],
+    pub total: u16,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    BadRequest(Vec<FieldError>),
+    NotFound,
+    Unknown(StorageError),
+}
+
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let mut errors = vec![];
+    let number = match PokemonNumber::try_from(req.number) {
+        Ok(number) => Some(number),
+        Err(e) => {
+            errors.push(FieldError::new("number", &e));
+            None
+        }
+    };
+    let form = match req.form.map(PokemonForm::try_from).transpose() {
+        Ok(form) => form,
+        Err(e) => {
+            errors.push(FieldError::new("form", &e));
+            None
+        }
+    };
+    let name = match req.name.map(PokemonName::try_from) {
+        Some(Ok(name)) => Some(name),
+        Some(Err(e)) => {
+            errors.push(FieldError::new("name", &e));
+            None
+        }
+        None => None,
+    };
+    let types = match req.types.map(PokemonTypes::try_from) {
+        Some(Ok(types)) => Some(types),
+        Some(Err(e)) => {
+            errors.push(FieldError::new("types", &e));
+            None
+        }
+        None => None,
+    };
+    let stats = match req.stats.map(PokemonStats::try_from) {
+        Some(Ok(stats)) => Some(stats),
+        Some(Err(e)) => {
+            errors.push(FieldError::new("stats", &e));
+            None
+        }
+        None => None,
+    };
+    let key = match number {
+        Some(number) if errors.is_empty() => PokemonKey::new(number, form),
+        _ => return Err(Error::BadRequest(errors)),
+    };
+
+    let (name, types, stats) = match (name, types, stats) {
+        (Some(name), Some(types), Some(stats)) => (name, types, stats),
+        (name, types, stats) => match repo.fetch_one(key.clone()) {
+            Ok(current) => (
+                name.unwrap_or(current.name),
+                types.unwrap_or(current.types),
+                stats.unwrap_or(current.stats),
+            ),
+            Err(FetchOneError::NotFound) => return Err(Error::NotFound),
+            Err(FetchOneError::Storage(e)) => return Err(Error::Unknown(e)),
+        },
+    };
+
+    match repo.update(key, name, types, stats) {
+        Ok(pokemon) => Ok(Response {
+            generation: pokemon.number.generation().map(u8::from),
+            number: u16::from(pokemon.number),
+            form: pokemon.form.map(String::from),
+            name: String::from(pokemon.name),
+            types: Vec::<String>::from(pokemon.types),
+            total: pokemon.stats.total(),
+            stats: <[u16; 6]>::from(pokemon.stats),
+        }),
+        Err(UpdateError::NotFound) => Err(Error::NotFound),
+        Err(UpdateError::Storage(e)) => Err(Error::Unknown(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    fn repo_with_pikachu() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.insert(
+            PokemonNumber::pikachu().into(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .expect("error inserting pikachu");
+        repo
+    }
+
+    #[test]
+    fn it_should_return_unknown_error_when_an_unexpected_error_happens() {
+        let repo = Arc::new(InMemoryRepository::new().with_error());
+        let req = Request {
+            number: 25,
+            form: None,
+            name: Some(String::from("Pikachu")),
+            types: Some(vec![String::from("Electric")]),
+            stats: Some([35, 55, 40, 50, 50, 90]),
+        };
+
+        let res = execute(repo, req);
+
+        assert!(matches!(res, Err(Error::Unknown(_))));
+    }
+
+    #[test]
+    fn it_should_return_bad_request_when_request_is_invalid() {
+        let repo = repo_with_pikachu();
+        let req = Request {
+            number: 25,
+            form: None,
+            name: Some(String::from("")),
+            types: None,
+            stats: None,
+        };
+
+        let res = execute(repo, req);
+
+        assert!(matches!(res, Err(Error::BadRequest(errors)) if errors[0].field == "name"));
+    }
+
+    #[test]
+    fn it_should_return_not_found_when_repo_does_not_contain_pokemon() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let req = Request {
+            number: 25,
+            form: None,
+            name: Some(String::from("Pikachu")),
+            types: Some(vec![String::from("Electric")]),
+            stats: Some([35, 55, 40, 50, 50, 90]),
+        };
+
+        let res = execute(repo, req);
+
+        assert!(matches!(res, Err(Error::NotFound)));
+    }
+
+    #[test]
+    fn it_should_replace_every_field() {
+        let repo = repo_with_pikachu();
+        let req = Request {
+            number: 25,
+            form: None,
+            name: Some(String::from("Pikachu Libre")),
+            types: Some(vec![String::from("Electric"), String::from("Fighting")]),
+            stats: Some([35, 55, 40, 50, 50, 90]),
+        };
+
+        let res = execute(repo, req).expect("error on execute");
+
+        assert_eq!(res.number, 25);
+        assert_eq!(res.name, "Pikachu Libre");
+        assert_eq!(res.types, vec!["Electric", "Fighting"]);
+    }
+
+    #[test]
+    fn it_should_keep_the_fields_missing_from_the_request() {
+        let repo = repo_with_pikachu();
+        let req = Request {
+            number: 25,
+            form: None,
+            name: Some(String::from("Pikachoo")),
+            types: None,
+            stats: None,
+        };
+
+        execute(repo.clone(), req).expect("error on execute");
+
+        let pokemon = repo
+            .fetch_one(PokemonNumber::pikachu().into())
+            .expect("error fetching pikachu");
+        assert_eq!(String::from(pokemon.name), "Pikachoo");
+        assert_eq!(Vec::<String>::from(pokemon.types), vec!["Electric"]);
+        assert_eq!(pokemon.stats, PokemonStats::pikachu());
+    }
+}
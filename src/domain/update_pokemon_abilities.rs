@@ -0,0 +1,121 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{FieldError, PokemonAbilities, PokemonNumber};
+use crate::repositories::pokemon::{FetchOneError, Repository, StorageError};
+
+pub use super::fetch_pokemon_abilities::Response;
+
+pub struct Request {
+    pub number: u16,
+    /// At most two.
+    pub regular: Vec<String>,
+    pub hidden: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    BadRequest(Vec<FieldError>),
+    NotFound,
+    Unknown(StorageError),
+}
+
+/// Replaces the abilities of a Pokemon. Abilities are matched by name
+/// regardless of case, and must already exist.
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Response, Error> {
+    let number = PokemonNumber::try_from(req.number)
+        .map_err(|e| Error::BadRequest(vec![FieldError::new("number", &e)]))?;
+    PokemonAbilities::try_from((req.regular.clone(), req.hidden.clone()))
+        .map_err(|e| Error::BadRequest(vec![FieldError::new("abilities", &e)]))?;
+
+    match repo.fetch_one(number.clone().into()) {
+        Ok(_) => {}
+        Err(FetchOneError::NotFound) => return Err(Error::NotFound),
+        Err(FetchOneError::Storage(e)) => return Err(Error::Unknown(e)),
+    }
+
+    let mut errors = vec![];
+    let mut resolve = |field: String, name: String| {
+        match super::fetch_all_abilities::resolve(&repo, &name) {
+            Ok(Some(ability)) => Ok(String::from(ability.name)),
+            Ok(None) => {
+                errors.push(FieldError {
+                    field,
+                    code: "unknown_ability",
+                    message: format!("ability {name:?} does not exist"),
+                });
+                Ok(name)
+            }
+            Err(e) => Err(Error::Unknown(e)),
+        }
+    };
+    let regular = req
+        .regular
+        .into_iter()
+        .enumerate()
+        .map(|(index, name)| resolve(format!("regular[{index}]"), name))
+        .collect::<Result<Vec<String>, Error>>()?;
+    let hidden = req
+        .hidden
+        .map(|name| resolve(String::from("hidden"), name))
+        .transpose()?;
+    if !errors.is_empty() {
+        return Err(Error::BadRequest(errors));
+    }
+
+    // Already validated above, and resolving only changes the case.
+    let abilities = PokemonAbilities::try_from((regular, hidden))
+        .map_err(|e| Error::BadRequest(vec![FieldError::new("abilities", &e)]))?;
+    match repo.replace_abilities(number, abilities) {
+        Ok(abilities) => Ok(Response::from(abilities)),
+        Err(e) => Err(Error::Unknown(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Ability, Pokemon};
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    fn repo() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![Pokemon::pikachu()]).unwrap();
+        repo.insert_ability(Ability::static_()).unwrap();
+        repo.insert_ability(Ability::lightning_rod()).unwrap();
+        repo
+    }
+
+    fn request(regular: &[&str], hidden: Option<&str>) -> Request {
+        Request {
+            number: 25,
+            regular: regular.iter().map(|name| String::from(*name)).collect(),
+            hidden: hidden.map(String::from),
+        }
+    }
+
+    #[test]
+    fn it_should_store_the_abilities_under_their_stored_name() {
+        let repo = repo();
+
+        let res = execute(repo, request(&["static"], Some("LIGHTNING ROD"))).unwrap();
+
+        assert_eq!(res.regular, vec![String::from("Static")]);
+        assert_eq!(res.hidden, Some(String::from("Lightning Rod")));
+    }
+
+    #[test]
+    fn it_should_reject_too_many_and_unknown_abilities() {
+        let repo = repo();
+
+        match execute(repo.clone(), request(&["Static", "Run Away", "Guts"], None)) {
+            Err(Error::BadRequest(errors)) => assert_eq!(errors[0].code, "too_many"),
+            _ => unreachable!(),
+        };
+        match execute(repo, request(&["Static"], Some("Run Away"))) {
+            Err(Error::BadRequest(errors)) => {
+                assert_eq!((errors[0].field.as_str(), errors[0].code), ("hidden", "unknown_ability"))
+            }
+            _ => unreachable!(),
+        };
+    }
+}
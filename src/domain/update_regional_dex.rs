@@ -0,0 +1,171 @@
+use std::sync::Arc;
+
+use crate::domain::entities::{DexEntry, DexIndex, DexName, FieldError, PokemonNumber};
+use crate::repositories::pokemon::{Repository, StorageError};
+
+pub use super::fetch_regional_dex::Response;
+
+pub struct Entry {
+    pub index: u16,
+    pub number: u16,
+}
+
+pub struct Request {
+    pub region: String,
+    pub entries: Vec<Entry>,
+    /// Highest index accepted, the configured national dex limit.
+    pub national_dex_limit: u16,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    BadRequest(Vec<FieldError>),
+    Unknown(StorageError),
+}
+
+fn duplicate(field: String, message: &str) -> FieldError {
+    FieldError {
+        field,
+        code: "duplicate",
+        message: message.to_owned(),
+    }
+}
+
+fn validate(entries: Vec<Entry>, limit: u16) -> Result<Vec<DexEntry>, Vec<FieldError>> {
+    let mut errors = vec![];
+    let mut dex: Vec<DexEntry> = vec![];
+    for (position, entry) in entries.into_iter().enumerate() {
+        let field = |name: &str| format!("entries[{position}].{name}");
+        let index = DexIndex::within(entry.index, limit)
+            .map_err(|e| errors.push(FieldError::new(&field("index"), &e)));
+        let number = PokemonNumber::try_from(entry.number)
+            .map_err(|e| errors.push(FieldError::new(&field("number"), &e)));
+        if let (Ok(index), Ok(number)) = (index, number) {
+            if dex.iter().any(|other| other.index == index) {
+                errors.push(duplicate(field("index"), "the index is already taken"));
+            }
+            if dex.iter().any(|other| other.number == number) {
+                errors.push(duplicate(field("number"), "the Pokemon is already listed"));
+            }
+            dex.push(DexEntry::new(index, number));
+        }
+    }
+
+    match errors.is_empty() {
+        true => Ok(dex),
+        false => Err(errors),
+    }
+}
+
+/// Creates or replaces a whole regional dex, an empty one deleting it.
+/// Every Pokemon must already exist.
+pub fn execute(repo: Arc<dyn Repository>, req: Request) -> Result<Vec<Response>, Error> {
+    let mut errors = vec![];
+    let name =
+        DexName::try_from(req.region).map_err(|e| errors.push(FieldError::new("region", &e)));
+    let entries = validate(req.entries, req.national_dex_limit).map_err(|e| errors.extend(e));
+    let (name, entries) = match (name, entries) {
+        (Ok(name), Ok(entries)) => (name, entries),
+        _ => return Err(Error::BadRequest(errors)),
+    };
+
+    let pokemons = super::fetch_regional_dex::base_forms(&repo).map_err(Error::Unknown)?;
+    let unknown = entries
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| !pokemons.contains_key(&entry.number))
+        .map(|(position, entry)| FieldError {
+            field: format!("entries[{position}].number"),
+            code: "unknown_pokemon",
+            message: format!("Pokemon {} does not exist", u16::from(entry.number.clone())),
+        })
+        .collect::<Vec<FieldError>>();
+    if !unknown.is_empty() {
+        return Err(Error::BadRequest(unknown));
+    }
+
+    let entries = repo
+        .replace_regional_dex(name, entries)
+        .map_err(Error::Unknown)?;
+    super::fetch_regional_dex::with_pokemons(&repo, entries).map_err(Error::Unknown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Pokemon, DEFAULT_NATIONAL_DEX_LIMIT};
+    use crate::repositories::inmemory_pokemon::InMemoryRepository;
+
+    fn entry(index: u16, number: u16) -> Entry {
+        Entry { index, number }
+    }
+
+    fn repo() -> Arc<InMemoryRepository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        repo.upsert_all(vec![Pokemon::pikachu(), Pokemon::vulpix()])
+            .unwrap();
+        repo
+    }
+
+    #[test]
+    fn it_should_replace_the_dex_in_index_order() {
+        let req = Request {
+            region: String::from("Johto"),
+            entries: vec![entry(22, 25), entry(1, 37)],
+            national_dex_limit: DEFAULT_NATIONAL_DEX_LIMIT,
+        };
+
+        let res = execute(repo(), req).unwrap();
+
+        let entries = res
+            .iter()
+            .map(|entry| (entry.index, entry.pokemon.name.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(entries, vec![(1, "Vulpix"), (22, "Pikachu")]);
+    }
+
+    #[test]
+    fn it_should_describe_every_invalid_entry() {
+        let req = Request {
+            region: String::from("johto/gsc"),
+            entries: vec![entry(1, 25), entry(1, 0), entry(2, 25)],
+            national_dex_limit: DEFAULT_NATIONAL_DEX_LIMIT,
+        };
+
+        let res = execute(repo(), req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => {
+                let fields = errors
+                    .iter()
+                    .map(|e| (e.field.as_str(), e.code))
+                    .collect::<Vec<_>>();
+                assert_eq!(
+                    fields,
+                    vec![
+                        ("region", "invalid_dex_name"),
+                        ("entries[1].number", "out_of_range"),
+                        ("entries[2].number", "duplicate"),
+                    ]
+                );
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn it_should_refuse_unknown_pokemons() {
+        let req = Request {
+            region: String::from("johto"),
+            entries: vec![entry(1, 152)],
+            national_dex_limit: DEFAULT_NATIONAL_DEX_LIMIT,
+        };
+
+        let res = execute(repo(), req);
+
+        match res {
+            Err(Error::BadRequest(errors)) => assert_eq!(errors[0].code, "unknown_pokemon"),
+            _ => unreachable!(),
+        };
+    }
+}
@@ -0,0 +1,60 @@
+//! A Pokedex built around a hexagonal architecture.
+//!
+//! `domain` holds the entities and one module per use case, `repositories` the
+//! `Repository` port and its adapters, `api` and `cli` the two ways to drive them.
+//!
+//! Other crates can implement `Repository` for their own storage and hand it to
+//! `api::serve` or to any use case:
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//!
+//! use pokedex::config::LogLevel;
+//! use pokedex::domain::entities::{
+//!     Pokemon, PokemonKey, PokemonName, PokemonStats, PokemonTypes, DEFAULT_NATIONAL_DEX_LIMIT,
+//! };
+//! use pokedex::repositories::inmemory_pokemon::InMemoryRepository;
+//! use pokedex::repositories::pokemon::*;
+//!
+//! /// Serves a fixed set of Pokemons and refuses every write.
+//! struct ReadOnlyRepository(InMemoryRepository);
+//!
+//! fn read_only() -> StorageError {
+//!     StorageError::ConstraintViolation(String::from("read-only"))
+//! }
+//!
+//! impl Repository for ReadOnlyRepository {
+//!     fn insert(&self, _: PokemonKey, _: PokemonName, _: PokemonTypes, _: PokemonStats)
+//!         -> Result<Pokemon, InsertError> {
+//!         Err(InsertError::Storage(read_only()))
+//!     }
+//!     fn fetch_all(&self, query: &FetchAllQuery) -> Result<Vec<Pokemon>, FetchAllError> {
+//!         self.0.fetch_all(query)
+//!     }
+//!     fn fetch_one(&self, key: PokemonKey) -> Result<Pokemon, FetchOneError> {
+//!         self.0.fetch_one(key)
+//!     }
+//!     fn update(&self, _: PokemonKey, _: PokemonName, _: PokemonTypes, _: PokemonStats)
+//!         -> Result<Pokemon, UpdateError> {
+//!         Err(UpdateError::Storage(read_only()))
+//!     }
+//!     fn delete(&self, _: PokemonKey) -> Result<(), DeleteError> {
+//!         Err(DeleteError::Storage(read_only()))
+//!     }
+//! }
+//!
+//! let repo = Arc::new(ReadOnlyRepository(InMemoryRepository::new()));
+//! pokedex::api::serve("localhost:8000", repo, LogLevel::Warn, DEFAULT_NATIONAL_DEX_LIMIT);
+//! ```
+
+pub mod api;
+pub mod cli;
+pub mod config;
+pub mod domain;
+pub mod repositories;
+
+#[macro_use]
+extern crate rouille;
+#[macro_use]
+extern crate clap;
+extern crate serde;
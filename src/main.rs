@@ -1,54 +1,47 @@
-mod api;
-mod cli;
-pub mod domain;
-pub mod repositories;
-
-#[macro_use]
-extern crate rouille;
-#[macro_use]
-extern crate clap;
-extern crate serde;
-
-use std::sync::Arc;
-
-use clap::{App, Arg, Values};
-
-use repositories::inmemory_pokemon::InMemoryRepository;
-use repositories::pokemon::Repository;
-use repositories::airtable_pokemon::AirtableRepository;
-use repositories::sqlite_pokemon::SqliteRepository;
+use pokedex::cli::{self, exit_code::ExitCode};
+use pokedex::{api, config, repositories::OpenError};
 
 fn main() {
-    let matches = App::new(crate_name!())
-        .version(crate_version!())
-        .author(crate_authors!())
-        .arg(Arg::with_name("cli").long("cli").help("Runs in CLI mode"))
-        .arg(Arg::with_name("sqlite").long("sqlite").value_name("PATH"))
-        .arg(
-            Arg::with_name("airtable")
-                .long("airtable")
-                .value_names(&["API_KEY", "WORKSPACE_ID"]),
-        )
-        .get_matches();
-
-    let repo = build_repo(matches.value_of("sqlite"), matches.values_of("airtable"));
+    let matches = cli::app().get_matches();
+
+    let config = match cli::flags(&matches).and_then(|flags| {
+        config::load(matches.value_of("config"), &|key| std::env::var(key).ok(), flags)
+    }) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Invalid configuration: {e}");
+            std::process::exit(ExitCode::BadRequest.code());
+        }
+    };
 
-    match matches.occurrences_of("cli") {
-        0 => api::serve("localhost:8000", repo),
-        _ => cli::run(repo),
+    // these never touch the configured backend, which may be the one that is broken
+    match matches.subcommand() {
+        ("config", Some(sub_matches)) => {
+            std::process::exit(cli::config(&config, sub_matches).code())
+        }
+        ("migrate", Some(sub_matches)) => {
+            std::process::exit(cli::migrate(&config, sub_matches).code())
+        }
+        _ => {}
     }
-}
 
-fn build_repo(sqlite_path: Option<&str>, airtable_vars: Option<Values>) -> Arc<dyn Repository> {
-    if let Some(path) = sqlite_path {
-        let repo = SqliteRepository::try_new(path).expect("Erro while creating sqlite repository");
-        return Arc::new(repo);
-    } else if let Some(values) = airtable_vars{
-        if let [apikey, workspace_id] = values.collect::<Vec<&str>>()[..] {
-            let repo = AirtableRepository::try_new(apikey, workspace_id).expect("error while creating airtable repository");
-            return Arc::new(repo);
+    let repo = match config.backend.open(config.timeout()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Error while opening the backend: {e}");
+            let code = match e {
+                OpenError::Disabled(_) => ExitCode::BadRequest,
+                _ => ExitCode::Unknown,
+            };
+            std::process::exit(code.code());
+        }
+    };
+
+    match matches.subcommand() {
+        ("interactive", _) => cli::run(repo, config.national_dex_limit),
+        (name, Some(sub_matches)) => {
+            std::process::exit(cli::exec(repo, &config, name, sub_matches).code())
         }
+        _ => api::serve(&config.listen, repo, config.log_level, config.national_dex_limit),
     }
-    let repo = Arc::new(InMemoryRepository::new());
-    repo
 }
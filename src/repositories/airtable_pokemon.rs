@@ -1,66 +1,286 @@
+use std::io;
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 
-use super::pokemon::{DeleteError, FetchAllError, FetchOneError, InsertError, Repository};
-use crate::domain::entities::{Pokemon, PokemonName, PokemonNumber, PokemonTypes};
+use super::pokemon::{
+    DeleteError, FetchAllError, FetchAllQuery, FetchOneError, InsertError, InsertEvolutionError,
+    Repository, SortDirection, SortField, StorageError, UpdateError,
+};
+use crate::domain::entities::{
+    Evolution, EvolutionTrigger, Pokemon, PokemonForm, PokemonKey, PokemonName, PokemonNumber,
+    PokemonStats, PokemonTypes,
+};
+
+/// Largest page Airtable serves for a single list request.
+const MAX_PAGE_SIZE: usize = 100;
 
 pub struct AirtableRepository {
+    agent: ureq::Agent,
     url: String,
+    evolutions_url: String,
     auth_header: String,
 }
 
 #[derive(Deserialize)]
-struct AirtableJson {
-    records: Vec<AirtableRecord>,
+struct AirtableJson<T> {
+    records: Vec<T>,
+    offset: Option<String>,
+}
+
+enum FetchRowsError {
+    /// The first page could not be fetched.
+    Failed(StorageError),
+    /// Some pages were fetched, but a later one failed.
+    Interrupted(StorageError),
+}
+
+fn storage_error(e: ureq::Error) -> StorageError {
+    match e {
+        ureq::Error::Status(status, res) => StorageError::HttpStatus {
+            status,
+            body: res.into_string().unwrap_or_default(),
+        },
+        ureq::Error::Transport(transport) => {
+            let timed_out = std::error::Error::source(&transport)
+                .and_then(|source| source.downcast_ref::<io::Error>())
+                .is_some_and(|e| {
+                    matches!(e.kind(), io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock)
+                });
+            if timed_out {
+                StorageError::Timeout
+            } else {
+                StorageError::ConnectionLost(transport.to_string())
+            }
+        }
+    }
+}
+
+impl TryFrom<AirtableRecord> for Pokemon {
+    type Error = StorageError;
+
+    fn try_from(record: AirtableRecord) -> Result<Self, Self::Error> {
+        let fields = record.fields;
+        let corrupt = |e: &dyn std::fmt::Display| {
+            StorageError::CorruptRow(format!("record {}: {e}", record.id))
+        };
+        let stats = fields.stats();
+        let number = PokemonNumber::try_from(fields.number).map_err(|e| corrupt(&e))?;
+        let form = match fields.form.is_empty() {
+            true => None,
+            false => Some(PokemonForm::try_from(fields.form).map_err(|e| corrupt(&e))?),
+        };
+        let name = PokemonName::try_from(fields.name).map_err(|e| corrupt(&e))?;
+        let types = PokemonTypes::try_from(fields.types).map_err(|e| corrupt(&e))?;
+        let stats = PokemonStats::try_from(stats).map_err(|e| corrupt(&e))?;
+        Ok(Pokemon::new(number, name, types, stats).with_form(form))
+    }
 }
 
 #[derive(Deserialize)]
-struct AirtableRecord {
+struct AirtableRecord<F = AirtableFields> {
     id: String,
-    fields: AirtableFields,
+    fields: F,
 }
 
 #[derive(Deserialize)]
 struct AirtableFields {
     pub number: u16,
+    /// Left empty for the base form.
+    #[serde(default)]
+    pub form: String,
     pub name: String,
     pub types: Vec<String>,
+    pub hp: u16,
+    pub attack: u16,
+    pub defense: u16,
+    pub special_attack: u16,
+    pub special_defense: u16,
+    pub speed: u16,
+}
+
+impl AirtableFields {
+    fn stats(&self) -> [u16; 6] {
+        [
+            self.hp,
+            self.attack,
+            self.defense,
+            self.special_attack,
+            self.special_defense,
+            self.speed,
+        ]
+    }
+}
+
+/// A row of the `evolutions` table. Conditions are kept one per line.
+#[derive(Deserialize)]
+struct EvolutionFields {
+    from: u16,
+    to: u16,
+    trigger: String,
+    // Airtable leaves empty fields out of its responses.
+    #[serde(default)]
+    conditions: String,
+}
+
+impl TryFrom<AirtableRecord<EvolutionFields>> for Evolution {
+    type Error = StorageError;
+
+    fn try_from(record: AirtableRecord<EvolutionFields>) -> Result<Self, Self::Error> {
+        let fields = record.fields;
+        let corrupt = |e: &dyn std::fmt::Display| {
+            StorageError::CorruptRow(format!("record {}: {e}", record.id))
+        };
+        let from = PokemonNumber::try_from(fields.from).map_err(|e| corrupt(&e))?;
+        let to = PokemonNumber::try_from(fields.to).map_err(|e| corrupt(&e))?;
+        let trigger = EvolutionTrigger::try_from(fields.trigger).map_err(|e| corrupt(&e))?;
+        let conditions = fields.conditions.lines().map(str::to_owned).collect();
+        Ok(Evolution::new(from, to, trigger, conditions))
+    }
+}
+
+/// Fetches every page of a list request, stopping early once `max_records` are read.
+fn fetch_pages<T: DeserializeOwned>(
+    req: ureq::Request,
+    max_records: Option<usize>,
+) -> Result<Vec<T>, FetchRowsError> {
+    let mut records = vec![];
+    let mut offset: Option<String> = None;
+    loop {
+        let page_req = match &offset {
+            Some(offset) => req.clone().query("offset", offset),
+            None => req.clone(),
+        };
+        let failed = match offset {
+            Some(_) => FetchRowsError::Interrupted,
+            None => FetchRowsError::Failed,
+        };
+
+        let res = match page_req.call() {
+            Ok(res) => res,
+            Err(e) => return Err(failed(storage_error(e))),
+        };
+
+        let json: AirtableJson<T> = match res.into_json() {
+            Ok(json) => json,
+            Err(e) => return Err(failed(StorageError::Deserialization(e.to_string()))),
+        };
+
+        records.extend(json.records);
+        offset = json.offset;
+        let exhausted = max_records.is_some_and(|max| records.len() >= max);
+        if offset.is_none() || exhausted {
+            return Ok(records);
+        }
+    }
 }
 
 impl AirtableRepository {
-    pub fn try_new(apikey: &str, workspace_id: &str) -> Result<Self, ()> {
+    /// Every request to Airtable, including the connection check made here,
+    /// gives up after `timeout`.
+    pub fn try_new(apikey: &str, workspace_id: &str, timeout: Duration) -> Result<Self, StorageError> {
+        let agent = ureq::AgentBuilder::new().timeout(timeout).build();
         let url = format!("https://api.airtable.com/v0/{}/pokemons", workspace_id);
+        let evolutions_url = format!("https://api.airtable.com/v0/{}/evolutions", workspace_id);
         let auth_header = format!("Bearer {}", apikey);
 
-        if let Err(_) = ureq::get(&url).set("Authorization", &auth_header).call() {
-            return Err(());
+        if let Err(e) = agent.get(&url).set("Authorization", &auth_header).call() {
+            return Err(storage_error(e));
         }
 
-        Ok(Self { url, auth_header })
+        Ok(Self {
+            agent,
+            url,
+            evolutions_url,
+            auth_header,
+        })
     }
 
-    fn fetch_pokemon_rows(&self, number: Option<u16>) -> Result<AirtableJson, ()> {
-        let url = match number {
-            Some(number) => format!("{}?filterByFormula=number%3D{}", self.url, number),
-            None => format!("{}?sort%5B0%5D%5Bfield%5D=number", self.url),
+    fn fetch_pokemon_rows(
+        &self,
+        query: &FetchAllQuery,
+    ) -> Result<Vec<AirtableRecord>, FetchRowsError> {
+        let mut conditions = vec![];
+        if let Some(tipe) = query.tipe {
+            conditions.push(format!(
+                "FIND(\",{},\", \",\" & ARRAYJOIN({{types}}, \",\") & \",\")",
+                String::from(tipe)
+            ));
+        }
+        if let Some(name) = &query.name {
+            conditions.push(format!(
+                "FIND(LOWER(\"{}\"), LOWER({{name}}))",
+                name.replace('\\', "\\\\").replace('"', "\\\"")
+            ));
+        }
+        if let Some(min) = &query.min_number {
+            conditions.push(format!("{{number}} >= {}", u16::from(min.clone())));
+        }
+        if let Some(max) = &query.max_number {
+            conditions.push(format!("{{number}} <= {}", u16::from(max.clone())));
+        }
+
+        let field = match query.sort {
+            SortField::Number => "number",
+            SortField::Name => "name",
+        };
+        let direction = match query.direction {
+            SortDirection::Ascending => "asc",
+            SortDirection::Descending => "desc",
         };
 
-        let res = match ureq::get(&url)
+        let mut req = self.agent.get(&self.url)
             .set("Authorization", &self.auth_header)
-            .call()
-        {
-            Ok(res) => res,
-            Err(e) => {
-                println!("error calling airtable: {e}");
-                return Err(());
-            }
+            .query("sort[0][field]", field)
+            .query("sort[0][direction]", direction)
+            .query("sort[1][field]", "form")
+            .query("sort[1][direction]", "asc");
+        if !conditions.is_empty() {
+            req = req.query("filterByFormula", &format!("AND({})", conditions.join(", ")));
+        }
+        let max_records = query.limit.map(|limit| query.offset.saturating_add(limit));
+        let page_size = max_records.map_or(MAX_PAGE_SIZE, |max| max.clamp(1, MAX_PAGE_SIZE));
+        req = req.query("pageSize", &page_size.to_string());
+        if let Some(max_records) = max_records {
+            req = req.query("maxRecords", &max_records.to_string());
+        }
+
+        fetch_pages(req, max_records)
+    }
+
+    /// The records of every form of the national number in `key`, and the
+    /// position of the one `key` designates, if any.
+    fn fetch_key_rows(
+        &self,
+        key: &PokemonKey,
+    ) -> Result<(Vec<AirtableRecord>, Option<usize>), StorageError> {
+        let records = match self.fetch_pokemon_rows(&FetchAllQuery::by_number(key.number.clone())) {
+            Ok(records) => records,
+            Err(FetchRowsError::Failed(e) | FetchRowsError::Interrupted(e)) => return Err(e),
         };
+        let form = key.form.as_ref().map_or("", PokemonForm::as_str);
+        let position = records.iter().position(|record| record.fields.form == form);
+        Ok((records, position))
+    }
 
-        match res.into_json() {
-            Ok(json) => Ok(json),
-            Err(e) => {
-                println!("error deserializing json: {e}");
-                Err(())
-            }
+    fn fetch_evolution_rows(
+        &self,
+        formula: Option<String>,
+    ) -> Result<Vec<AirtableRecord<EvolutionFields>>, StorageError> {
+        let mut req = self.agent.get(&self.evolutions_url)
+            .set("Authorization", &self.auth_header)
+            .query("sort[0][field]", "from")
+            .query("sort[0][direction]", "asc")
+            .query("sort[1][field]", "to")
+            .query("sort[1][direction]", "asc")
+            .query("pageSize", &MAX_PAGE_SIZE.to_string());
+        if let Some(formula) = formula {
+            req = req.query("filterByFormula", &formula);
+        }
+        match fetch_pages(req, None) {
+            Ok(records) => Ok(records),
+            Err(FetchRowsError::Failed(e) | FetchRowsError::Interrupted(e)) => Err(e),
         }
     }
 }
@@ -69,8 +289,14 @@ impl AirtableRepository {
 impl AirtableRepository {
     pub fn new_test(url: &str, apikey: &str) -> Self {
         let auth_header = format!("Bearer {apikey}");
+        let evolutions_url = match url.rsplit_once('/') {
+            Some((base, _)) => format!("{base}/evolutions"),
+            None => format!("{url}/evolutions"),
+        };
         Self {
+            agent: ureq::Agent::new(),
             url: url.to_owned(),
+            evolutions_url,
             auth_header,
         }
     }
@@ -79,121 +305,369 @@ impl AirtableRepository {
 impl Repository for AirtableRepository {
     fn insert(
         &self,
-        number: PokemonNumber,
+        key: PokemonKey,
         name: PokemonName,
         types: PokemonTypes,
+        stats: PokemonStats,
     ) -> Result<Pokemon, InsertError> {
-        let json = match self.fetch_pokemon_rows(Some(u16::from(number.clone()))) {
-            Ok(json) => json,
-            _ => return Err(InsertError::Unknown),
-        };
-
-        if !json.records.is_empty() {
-            return Err(InsertError::Conflict);
+        match self.fetch_key_rows(&key) {
+            Ok((_, Some(_))) => return Err(InsertError::Conflict),
+            Ok((_, None)) => {}
+            Err(e) => return Err(InsertError::Storage(e)),
         }
 
-        let body = ureq::json!({
+        let mut body = ureq::json!({
             "records": [{
                 "fields": {
-                    "number": u16::from(number.clone()),
+                    "number": u16::from(key.number.clone()),
                     "name": String::from(name.clone()),
                     "types": Vec::<String>::from(types.clone()),
+                    "hp": stats.hp(),
+                    "attack": stats.attack(),
+                    "defense": stats.defense(),
+                    "special_attack": stats.special_attack(),
+                    "special_defense": stats.special_defense(),
+                    "speed": stats.speed(),
                 },
             }],
         });
+        if let Some(form) = &key.form {
+            body["records"][0]["fields"]["form"] = ureq::json!(form.as_str());
+        }
 
-        if let Err(e) = ureq::post(&self.url)
+        if let Err(e) = self.agent.post(&self.url)
             .set("Authorization", &self.auth_header)
             .send_json(body)
         {
-            println!("error inserting pokemon({:?}) on airtable: {e}", number);
-            return Err(InsertError::Unknown);
+            return Err(InsertError::Storage(storage_error(e)));
         }
 
-        Ok(Pokemon::new(number, name, types))
+        Ok(Pokemon::new(key.number, name, types, stats).with_form(key.form))
     }
 
-    fn fetch_all(&self) -> Result<Vec<Pokemon>, FetchAllError> {
-        let json = match self.fetch_pokemon_rows(None) {
-            Ok(json) => json,
-            Err(_) => return Err(FetchAllError::Unknown),
+    fn fetch_all(&self, query: &FetchAllQuery) -> Result<Vec<Pokemon>, FetchAllError> {
+        let records = match self.fetch_pokemon_rows(query) {
+            Ok(records) => records,
+            Err(FetchRowsError::Interrupted(e)) => return Err(FetchAllError::Interrupted(e)),
+            Err(FetchRowsError::Failed(e)) => return Err(FetchAllError::Storage(e)),
         };
 
-        let mut pokemons = Vec::with_capacity(json.records.len());
-        for record in json.records {
-            match (
-                PokemonNumber::try_from(record.fields.number),
-                PokemonName::try_from(record.fields.name),
-                PokemonTypes::try_from(record.fields.types),
-            ) {
-                (Ok(number), Ok(name), Ok(types)) => {
-                    pokemons.push(Pokemon::new(number, name, types))
-                }
-                _ => {
-                    println!("error parsing pokemon({})", record.fields.number);
-                    return Err(FetchAllError::Unknown);
-                }
-            }
-        }
+        records
+            .into_iter()
+            .skip(query.offset)
+            .map(Pokemon::try_from)
+            .collect::<Result<Vec<Pokemon>, StorageError>>()
+            .map_err(FetchAllError::Storage)
+    }
+
+    fn fetch_one(&self, key: PokemonKey) -> Result<Pokemon, FetchOneError> {
+        let (mut records, position) = match self.fetch_key_rows(&key) {
+            Ok((records, Some(position))) => (records, position),
+            Ok((_, None)) => return Err(FetchOneError::NotFound),
+            Err(e) => return Err(FetchOneError::Storage(e)),
+        };
 
-        Ok(pokemons)
+        Pokemon::try_from(records.remove(position)).map_err(FetchOneError::Storage)
     }
 
-    fn fetch_one(&self, number: PokemonNumber) -> Result<Pokemon, FetchOneError> {
-        let number = u16::from(number);
-        let mut json = match self.fetch_pokemon_rows(Some(number)) {
-            Ok(json) => json,
-            Err(_) => return Err(FetchOneError::Unknown),
+    fn update(
+        &self,
+        key: PokemonKey,
+        name: PokemonName,
+        types: PokemonTypes,
+        stats: PokemonStats,
+    ) -> Result<Pokemon, UpdateError> {
+        let (mut records, position) = match self.fetch_key_rows(&key) {
+            Ok((records, Some(position))) => (records, position),
+            Ok((_, None)) => return Err(UpdateError::NotFound),
+            Err(e) => return Err(UpdateError::Storage(e)),
         };
 
-        if json.records.is_empty() {
-            return Err(FetchOneError::NotFound);
-        }
+        let record = records.remove(position);
+        let path = format!("{}/{}", self.url, record.id);
+        let body = ureq::json!({
+            "fields": {
+                "name": String::from(name.clone()),
+                "types": Vec::<String>::from(types.clone()),
+                "hp": stats.hp(),
+                "attack": stats.attack(),
+                "defense": stats.defense(),
+                "special_attack": stats.special_attack(),
+                "special_defense": stats.special_defense(),
+                "speed": stats.speed(),
+            },
+        });
 
-        let fields = json.records.remove(0).fields;
-        match (
-            PokemonNumber::try_from(fields.number),
-            PokemonName::try_from(fields.name),
-            PokemonTypes::try_from(fields.types),
-        ) {
-            (Ok(number), Ok(name), Ok(types)) => Ok(Pokemon::new(number, name, types)),
-            _ => Err(FetchOneError::Unknown),
+        if let Err(e) = self.agent.patch(&path)
+            .set("Authorization", &self.auth_header)
+            .send_json(body)
+        {
+            return Err(UpdateError::Storage(storage_error(e)));
         }
+
+        Ok(Pokemon::new(key.number, name, types, stats).with_form(key.form))
     }
 
-    fn delete(&self, number: PokemonNumber) -> Result<(), DeleteError> {
-        let mut json = match self.fetch_pokemon_rows(Some(u16::from(number.clone()))) {
-            Ok(json) => json,
-            _ => return Err(DeleteError::Unknown),
+    fn delete(&self, key: PokemonKey) -> Result<(), DeleteError> {
+        let (mut records, position) = match self.fetch_key_rows(&key) {
+            Ok((records, Some(position))) => (records, position),
+            Ok((_, None)) => return Err(DeleteError::NotFound),
+            Err(e) => return Err(DeleteError::Storage(e)),
         };
 
-        if json.records.is_empty() {
-            return Err(DeleteError::NotFound);
+        // Deleting the base form deletes the whole national number.
+        if !key.is_base() {
+            records = vec![records.remove(position)];
+        }
+        for record in records {
+            let path = format!("{}/{}", self.url, record.id);
+            if let Err(e) = self.agent.delete(&path)
+                .set("Authorization", &self.auth_header)
+                .call()
+            {
+                return Err(DeleteError::Storage(storage_error(e)));
+            }
+        }
+        if !key.is_base() {
+            return Ok(());
         }
 
-        let record = json.records.remove(0);
-        let path = format!("{}/{}", self.url, record.id);
-        let req = ureq::delete(&path)
-            .set("Authorization", &self.auth_header)
-            .call();
-
-        if let Err(e) = req {
-            println!("error deleting pokemon({:?}) on airtable: {e}", number);
-            return Err(DeleteError::Unknown);
+        let number = u16::from(key.number);
+        let formula = format!("OR({{from}} = {number}, {{to}} = {number})");
+        let evolutions = match self.fetch_evolution_rows(Some(formula)) {
+            Ok(evolutions) => evolutions,
+            Err(e) => return Err(DeleteError::Storage(e)),
+        };
+        for evolution in evolutions {
+            let path = format!("{}/{}", self.evolutions_url, evolution.id);
+            if let Err(e) = self.agent.delete(&path)
+                .set("Authorization", &self.auth_header)
+                .call()
+            {
+                return Err(DeleteError::Storage(storage_error(e)));
+            }
         }
         Ok(())
     }
+
+    fn insert_evolution(&self, evolution: Evolution) -> Result<Evolution, InsertEvolutionError> {
+        let to = u16::from(evolution.to.clone());
+        match self.fetch_evolution_rows(Some(format!("{{to}} = {to}"))) {
+            Ok(records) if !records.is_empty() => return Err(InsertEvolutionError::Conflict),
+            Ok(_) => {}
+            Err(e) => return Err(InsertEvolutionError::Storage(e)),
+        }
+
+        let body = ureq::json!({
+            "records": [{
+                "fields": {
+                    "from": u16::from(evolution.from.clone()),
+                    "to": to,
+                    "trigger": String::from(evolution.trigger.clone()),
+                    "conditions": evolution.conditions.join("\n"),
+                },
+            }],
+        });
+
+        if let Err(e) = self.agent.post(&self.evolutions_url)
+            .set("Authorization", &self.auth_header)
+            .send_json(body)
+        {
+            return Err(InsertEvolutionError::Storage(storage_error(e)));
+        }
+
+        Ok(evolution)
+    }
+
+    fn fetch_evolutions(&self) -> Result<Vec<Evolution>, StorageError> {
+        self.fetch_evolution_rows(None)?
+            .into_iter()
+            .map(Evolution::try_from)
+            .collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::entities::PokemonNumber;
+    use crate::domain::entities::{
+        PokemonName, PokemonNumber, PokemonStats, PokemonType, PokemonTypes,
+    };
     use httpmock::prelude;
     use serde_json::json;
+    use std::collections::BTreeMap;
+    use std::sync::{Arc, Mutex};
 
     const APIKEY: &str = "TEST-KEY";
 
+    fn pikachu_record() -> serde_json::Value {
+        json!({
+            "id": "PIKACHU",
+            "fields": {
+                "number": 25u16,
+                "name": "Pikachu",
+                "types": ["Electric"],
+                "hp": 35u16,
+                "attack": 55u16,
+                "defense": 40u16,
+                "special_attack": 50u16,
+                "special_defense": 50u16,
+                "speed": 90u16
+            }
+        })
+    }
+
+    fn vulpix_record() -> serde_json::Value {
+        json!({
+            "id": "VULPIX",
+            "fields": {
+                "number": 37u16,
+                "name": "Vulpix",
+                "types": ["Fire"],
+                "hp": 38u16,
+                "attack": 41u16,
+                "defense": 40u16,
+                "special_attack": 50u16,
+                "special_defense": 65u16,
+                "speed": 65u16
+            }
+        })
+    }
+
+    #[test]
+    fn it_should_fetch_every_page() {
+        let server = prelude::MockServer::start();
+        let url = server.url("/test/api");
+        let repo = AirtableRepository::new_test(url.as_str(), APIKEY);
+
+        let second_page = server.mock(|when, then| {
+            when.method(prelude::GET)
+                .path("/test/api")
+                .query_param("offset", "PAGE2");
+            then.status(200)
+                .json_body(json!({"records": [vulpix_record()]}));
+        });
+        let first_page = server.mock(|when, then| {
+            when.method(prelude::GET)
+                .path("/test/api")
+                .query_param("pageSize", "100");
+            then.status(200)
+                .json_body(json!({"records": [pikachu_record()], "offset": "PAGE2"}));
+        });
+
+        let pokemons = repo
+            .fetch_all(&FetchAllQuery::default())
+            .expect("error while fetching all pokemons");
+
+        assert_eq!(first_page.hits(), 1);
+        assert_eq!(second_page.hits(), 1);
+        assert_eq!(pokemons.len(), 2);
+        assert_eq!(pokemons[0].number, PokemonNumber::pikachu());
+        assert_eq!(pokemons[1].number, PokemonNumber::vulpix());
+    }
+
+    #[test]
+    fn it_should_stop_paging_once_the_limit_is_reached() {
+        let server = prelude::MockServer::start();
+        let url = server.url("/test/api");
+        let repo = AirtableRepository::new_test(url.as_str(), APIKEY);
+
+        let first_page = server.mock(|when, then| {
+            when.method(prelude::GET)
+                .path("/test/api")
+                .query_param("pageSize", "1")
+                .query_param("maxRecords", "1");
+            then.status(200)
+                .json_body(json!({"records": [pikachu_record()], "offset": "PAGE2"}));
+        });
+
+        let query = FetchAllQuery {
+            limit: Some(1),
+            ..FetchAllQuery::default()
+        };
+        let pokemons = repo
+            .fetch_all(&query)
+            .expect("error while fetching all pokemons");
+
+        assert_eq!(first_page.hits(), 1);
+        assert_eq!(pokemons.len(), 1);
+    }
+
+    #[test]
+    fn it_should_fail_as_interrupted_when_a_later_page_fails() {
+        let server = prelude::MockServer::start();
+        let url = server.url("/test/api");
+        let repo = AirtableRepository::new_test(url.as_str(), APIKEY);
+
+        let second_page = server.mock(|when, then| {
+            when.method(prelude::GET)
+                .path("/test/api")
+                .query_param("offset", "PAGE2");
+            then.status(500);
+        });
+        let first_page = server.mock(|when, then| {
+            when.method(prelude::GET).path("/test/api");
+            then.status(200)
+                .json_body(json!({"records": [pikachu_record()], "offset": "PAGE2"}));
+        });
+
+        let err = repo
+            .fetch_all(&FetchAllQuery::default())
+            .expect_err("should have returned error on fetch all");
+
+        assert_eq!(first_page.hits(), 1);
+        assert_eq!(second_page.hits(), 1);
+        assert!(matches!(
+            err,
+            FetchAllError::Interrupted(StorageError::HttpStatus { status: 500, .. })
+        ));
+    }
+
+    #[test]
+    fn it_should_keep_the_status_when_airtable_rate_limits() {
+        let server = prelude::MockServer::start();
+        let url = server.url("/test/api");
+        let repo = AirtableRepository::new_test(url.as_str(), APIKEY);
+
+        let mock = server.mock(|when, then| {
+            when.method(prelude::GET).path("/test/api");
+            then.status(429)
+                .json_body(json!({"errors": [{"error": {"type": "RATE_LIMIT_REACHED"}}]}));
+        });
+
+        let err = repo
+            .fetch_one(PokemonNumber::pikachu().into())
+            .expect_err("should have returned error on fetch one");
+
+        mock.assert();
+        assert!(matches!(
+            err,
+            FetchOneError::Storage(StorageError::HttpStatus { status: 429, .. })
+        ));
+    }
+
+    #[test]
+    fn it_should_fail_when_the_first_page_fails() {
+        let server = prelude::MockServer::start();
+        let url = server.url("/test/api");
+        let repo = AirtableRepository::new_test(url.as_str(), APIKEY);
+
+        let first_page = server.mock(|when, then| {
+            when.method(prelude::GET).path("/test/api");
+            then.status(500);
+        });
+
+        let err = repo
+            .fetch_all(&FetchAllQuery::default())
+            .expect_err("should have returned error on fetch all");
+
+        first_page.assert();
+        assert!(matches!(
+            err,
+            FetchAllError::Storage(StorageError::HttpStatus { status: 500, .. })
+        ));
+    }
+
     #[test]
     fn it_should_create_repository_with_url_and_apikey() {
         let server = prelude::MockServer::start();
@@ -216,7 +690,7 @@ mod tests {
         });
 
         let err = repo
-            .delete(PokemonNumber::pikachu())
+            .delete(PokemonNumber::pikachu().into())
             .expect_err("should have returned error on delete");
 
         pokedex_mock.assert();
@@ -240,11 +714,14 @@ mod tests {
         });
 
         let err = repo
-            .delete(PokemonNumber::pikachu())
+            .delete(PokemonNumber::pikachu().into())
             .expect_err("should have returned error on delete");
 
         pokedex_mock.assert();
-        assert!(matches!(err, DeleteError::Unknown));
+        assert!(matches!(
+            err,
+            DeleteError::Storage(StorageError::Deserialization(_))
+        ));
     }
 
     #[test]
@@ -259,11 +736,14 @@ mod tests {
         });
 
         let err = repo
-            .delete(PokemonNumber::pikachu())
+            .delete(PokemonNumber::pikachu().into())
             .expect_err("should have returned error on delete");
 
         pokedex_mock.assert();
-        assert!(matches!(err, DeleteError::Unknown));
+        assert!(matches!(
+            err,
+            DeleteError::Storage(StorageError::HttpStatus { status: 500, .. })
+        ));
     }
 
     #[test]
@@ -280,7 +760,13 @@ mod tests {
                 "fields": {
                     "number": 25u16,
                     "name": "pikachu",
-                    "types": ["Electric"]
+                    "types": ["Electric"],
+                    "hp": 35u16,
+                    "attack": 55u16,
+                    "defense": 40u16,
+                    "special_attack": 50u16,
+                    "special_defense": 50u16,
+                    "speed": 90u16
                 }
             }]}));
         });
@@ -291,14 +777,96 @@ mod tests {
         });
 
         let err = repo
-            .delete(PokemonNumber::pikachu())
+            .delete(PokemonNumber::pikachu().into())
             .expect_err("should have returned error on delete");
 
-        assert!(matches!(err, DeleteError::Unknown));
+        assert!(matches!(
+            err,
+            DeleteError::Storage(StorageError::HttpStatus { status: 500, .. })
+        ));
         assert_eq!(get_route.hits(), 1);
         assert_eq!(delete_route.hits(), 1);
     }
 
+    #[test]
+    fn it_should_fail_to_update_when_pokemon_does_not_exist() {
+        let server = prelude::MockServer::start();
+        let url = server.url("/test/api");
+        let repo = AirtableRepository::new_test(url.as_str(), APIKEY);
+
+        let pokedex_mock = server.mock(|when, then| {
+            when.method(prelude::GET).path("/test/api");
+            then.status(200).json_body(json!({"records": []}));
+        });
+
+        let err = repo
+            .update(
+                PokemonNumber::pikachu().into(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+                PokemonStats::pikachu(),
+            )
+            .expect_err("should have returned error on update");
+
+        pokedex_mock.assert();
+        assert!(matches!(err, UpdateError::NotFound));
+    }
+
+    #[test]
+    fn it_should_update_otherwise() {
+        let server = prelude::MockServer::start();
+        let url = server.url("/test/api");
+        let repo = AirtableRepository::new_test(url.as_str(), APIKEY);
+
+        let get_route = server.mock(|when, then| {
+            when.method(prelude::GET).path("/test/api");
+            then.status(200).json_body(json!(
+            {"records": [{
+                "id":"ID",
+                "fields": {
+                    "number": 25u16,
+                    "name": "pikachu",
+                    "types": ["Electric"],
+                    "hp": 35u16,
+                    "attack": 55u16,
+                    "defense": 40u16,
+                    "special_attack": 50u16,
+                    "special_defense": 50u16,
+                    "speed": 90u16
+                }
+            }]}));
+        });
+
+        let patch_route = server.mock(|when, then| {
+            when.method(httpmock::Method::PATCH)
+                .path("/test/api/ID")
+                .json_body(json!({"fields": {
+                    "name": "Pikachu",
+                    "types": ["Electric"],
+                    "hp": 35u16,
+                    "attack": 55u16,
+                    "defense": 40u16,
+                    "special_attack": 50u16,
+                    "special_defense": 50u16,
+                    "speed": 90u16
+                }}));
+            then.status(200);
+        });
+
+        let pokemon = repo
+            .update(
+                PokemonNumber::pikachu().into(),
+                PokemonName::pikachu(),
+                PokemonTypes::pikachu(),
+                PokemonStats::pikachu(),
+            )
+            .expect("error while updating pikachu");
+
+        assert_eq!(String::from(pokemon.name), "Pikachu");
+        assert_eq!(get_route.hits(), 1);
+        assert_eq!(patch_route.hits(), 1);
+    }
+
     #[test]
     fn it_should_delete_otherwise() {
         let server = prelude::MockServer::start();
@@ -313,7 +881,13 @@ mod tests {
                 "fields": {
                     "number": 25u16,
                     "name": "pikachu",
-                    "types": ["Electric"]
+                    "types": ["Electric"],
+                    "hp": 35u16,
+                    "attack": 55u16,
+                    "defense": 40u16,
+                    "special_attack": 50u16,
+                    "special_defense": 50u16,
+                    "speed": 90u16
                 }
             }]}));
         });
@@ -323,12 +897,474 @@ mod tests {
             then.status(200);
         });
 
+        let evolutions_route = server.mock(|when, then| {
+            when.method(prelude::GET)
+                .path("/test/evolutions")
+                .query_param("filterByFormula", "OR({from} = 25, {to} = 25)");
+            then.status(200).json_body(json!(
+            {"records": [{
+                "id": "EVOLUTION",
+                "fields": {"from": 25u16, "to": 26u16, "trigger": "stone:Thunder Stone"}
+            }]}));
+        });
+
+        let delete_evolution_route = server.mock(|when, then| {
+            when.method(prelude::DELETE).path("/test/evolutions/EVOLUTION");
+            then.status(200);
+        });
+
         let res = repo
-            .delete(PokemonNumber::pikachu());
+            .delete(PokemonNumber::pikachu().into());
 
         assert!(res.is_ok());
         assert_eq!(get_route.hits(), 1);
         assert_eq!(delete_route.hits(), 1);
+        assert_eq!(evolutions_route.hits(), 1);
+        assert_eq!(delete_evolution_route.hits(), 1);
+    }
+
+    fn without_formula(req: &prelude::HttpMockRequest) -> bool {
+        req.query_params
+            .iter()
+            .flatten()
+            .all(|(name, _)| name != "filterByFormula")
+    }
+
+    #[test]
+    fn it_should_send_the_filters_as_a_formula() {
+        let server = prelude::MockServer::start();
+        let url = server.url("/test/api");
+        let repo = AirtableRepository::new_test(url.as_str(), APIKEY);
+
+        let list = server.mock(|when, then| {
+            when.method(prelude::GET).path("/test/api").query_param(
+                "filterByFormula",
+                "AND(FIND(\",Electric,\", \",\" & ARRAYJOIN({types}, \",\") & \",\"), \
+                FIND(LOWER(\"pi\\\"ka\"), LOWER({name})), {number} >= 10, {number} <= 30)",
+            );
+            then.status(200).json_body(json!({"records": [pikachu_record()]}));
+        });
+
+        let query = FetchAllQuery {
+            tipe: Some(PokemonType::Electric),
+            name: Some(String::from("pi\"ka")),
+            min_number: Some(PokemonNumber::try_from(10).unwrap()),
+            max_number: Some(PokemonNumber::try_from(30).unwrap()),
+            ..FetchAllQuery::default()
+        };
+        let pokemons = repo.fetch_all(&query).expect("error while fetching pokemons");
+
+        assert_eq!(list.hits(), 1);
+        assert_eq!(pokemons.len(), 1);
+    }
+
+    #[test]
+    fn it_should_send_the_sort_and_keep_the_order_of_the_records() {
+        let server = prelude::MockServer::start();
+        let url = server.url("/test/api");
+        let repo = AirtableRepository::new_test(url.as_str(), APIKEY);
+
+        let list = server.mock(|when, then| {
+            when.method(prelude::GET)
+                .path("/test/api")
+                .query_param("sort[0][field]", "name")
+                .query_param("sort[0][direction]", "desc")
+                .query_param("sort[1][field]", "form")
+                .query_param("sort[1][direction]", "asc")
+                .query_param("pageSize", "100")
+                .matches(without_formula);
+            then.status(200)
+                .json_body(json!({"records": [vulpix_record(), pikachu_record()]}));
+        });
+
+        let query = FetchAllQuery {
+            sort: SortField::Name,
+            direction: SortDirection::Descending,
+            ..FetchAllQuery::default()
+        };
+        let pokemons = repo.fetch_all(&query).expect("error while fetching pokemons");
+
+        assert_eq!(list.hits(), 1);
+        assert_eq!(pokemons[0].number, PokemonNumber::vulpix());
+        assert_eq!(pokemons[1].number, PokemonNumber::pikachu());
+    }
+
+    #[test]
+    fn it_should_ask_for_the_offset_and_the_limit_then_skip_the_offset() {
+        let server = prelude::MockServer::start();
+        let url = server.url("/test/api");
+        let repo = AirtableRepository::new_test(url.as_str(), APIKEY);
+
+        let list = server.mock(|when, then| {
+            when.method(prelude::GET)
+                .path("/test/api")
+                .query_param("sort[0][field]", "number")
+                .query_param("sort[0][direction]", "asc")
+                .query_param("pageSize", "2")
+                .query_param("maxRecords", "2");
+            then.status(200)
+                .json_body(json!({"records": [pikachu_record(), vulpix_record()]}));
+        });
+
+        let query = FetchAllQuery {
+            limit: Some(1),
+            offset: 1,
+            ..FetchAllQuery::default()
+        };
+        let pokemons = repo.fetch_all(&query).expect("error while fetching pokemons");
+
+        assert_eq!(list.hits(), 1);
+        assert_eq!(pokemons.len(), 1);
+        assert_eq!(pokemons[0].number, PokemonNumber::vulpix());
+    }
+
+    #[test]
+    fn it_should_cap_the_records_asked_for_when_the_limit_is_huge() {
+        let server = prelude::MockServer::start();
+        let url = server.url("/test/api");
+        let repo = AirtableRepository::new_test(url.as_str(), APIKEY);
+
+        let list = server.mock(|when, then| {
+            when.method(prelude::GET)
+                .path("/test/api")
+                .query_param("pageSize", "100")
+                .query_param("maxRecords", usize::MAX.to_string());
+            then.status(200)
+                .json_body(json!({"records": [pikachu_record(), vulpix_record()]}));
+        });
+
+        let query = FetchAllQuery {
+            limit: Some(usize::MAX),
+            offset: 1,
+            ..FetchAllQuery::default()
+        };
+        let pokemons = repo.fetch_all(&query).expect("error while fetching pokemons");
+
+        assert_eq!(list.hits(), 1);
+        assert_eq!(pokemons.len(), 1);
+    }
+
+    /// Airtable as seen through httpmock: the mocks are rebuilt after every
+    /// write, and each write is only answered if the adapter sends exactly
+    /// the request Airtable expects. Lists are only answered with the sort the
+    /// adapter must send; the tests above pin its formulas and paging.
+    struct FakeAirtable {
+        server: prelude::MockServer,
+        repo: AirtableRepository,
+        pokemons: Mutex<BTreeMap<PokemonKey, Pokemon>>,
+        /// Keyed by the Pokemon they evolve into.
+        evolutions: Mutex<BTreeMap<u16, Evolution>>,
+        mocks: Mutex<Vec<usize>>,
+    }
+
+    impl FakeAirtable {
+        fn new() -> Self {
+            let server = prelude::MockServer::start();
+            let repo = AirtableRepository::new_test(&server.url("/pokemons"), APIKEY);
+            let fake = Self {
+                server,
+                repo,
+                pokemons: Mutex::new(BTreeMap::new()),
+                evolutions: Mutex::new(BTreeMap::new()),
+                mocks: Mutex::new(vec![]),
+            };
+            fake.sync();
+            fake
+        }
+
+        fn record_id(key: &PokemonKey) -> String {
+            match &key.form {
+                Some(form) => format!("rec{}{}", u16::from(key.number.clone()), form.as_str()),
+                None => format!("rec{}", u16::from(key.number.clone())),
+            }
+        }
+
+        fn fields(pokemon: &Pokemon) -> serde_json::Value {
+            let mut fields = json!({
+                "number": u16::from(pokemon.number.clone()),
+                "name": String::from(pokemon.name.clone()),
+                "types": Vec::<String>::from(pokemon.types.clone()),
+                "hp": pokemon.stats.hp(),
+                "attack": pokemon.stats.attack(),
+                "defense": pokemon.stats.defense(),
+                "special_attack": pokemon.stats.special_attack(),
+                "special_defense": pokemon.stats.special_defense(),
+                "speed": pokemon.stats.speed(),
+            });
+            if let Some(form) = &pokemon.form {
+                fields["form"] = json!(form.as_str());
+            }
+            fields
+        }
+
+        fn record(pokemon: &Pokemon) -> serde_json::Value {
+            json!({"id": Self::record_id(&pokemon.key()), "fields": Self::fields(pokemon)})
+        }
+
+        fn evolution_fields(evolution: &Evolution) -> serde_json::Value {
+            json!({
+                "from": u16::from(evolution.from.clone()),
+                "to": u16::from(evolution.to.clone()),
+                "trigger": String::from(evolution.trigger.clone()),
+                "conditions": evolution.conditions.join("\n"),
+            })
+        }
+
+        fn evolution_record(evolution: &Evolution) -> serde_json::Value {
+            let mut fields = Self::evolution_fields(evolution);
+            // Airtable leaves empty fields out of its responses.
+            if evolution.conditions.is_empty() {
+                fields.as_object_mut().unwrap().remove("conditions");
+            }
+            json!({"id": format!("evo{}", u16::from(evolution.to.clone())), "fields": fields})
+        }
+
+        /// Replaces the list mocks with ones serving the current records.
+        fn sync(&self) {
+            let mut mocks = self.mocks.lock().unwrap();
+            for id in mocks.drain(..) {
+                httpmock::Mock::new(id, &self.server).delete();
+            }
+            let pokemons = self.pokemons.lock().unwrap();
+
+            // httpmock answers with the first matching mock, so lookups by
+            // number come before the catch-all for unknown numbers.
+            let numbers = pokemons
+                .keys()
+                .map(|key| u16::from(key.number.clone()))
+                .collect::<std::collections::BTreeSet<u16>>();
+            for number in &numbers {
+                let formula = format!("AND({{number}} >= {number}, {{number}} <= {number})");
+                let records = pokemons
+                    .values()
+                    .filter(|pokemon| u16::from(pokemon.number.clone()) == *number)
+                    .map(Self::record)
+                    .collect::<Vec<_>>();
+                mocks.push(self.server.mock(|when, then| {
+                    when.method(prelude::GET)
+                        .path("/pokemons")
+                        .query_param("filterByFormula", formula)
+                        .query_param("sort[0][field]", "number")
+                        .query_param("sort[1][field]", "form");
+                    then.status(200).json_body(json!({ "records": records }));
+                }).id);
+            }
+            mocks.push(self.server.mock(|when, then| {
+                when.method(prelude::GET).path("/pokemons").query_param_exists("filterByFormula");
+                then.status(200).json_body(json!({ "records": [] }));
+            }).id);
+
+            for (field, direction) in [("number", "asc"), ("number", "desc"), ("name", "asc"), ("name", "desc")] {
+                let mut sorted = pokemons.values().collect::<Vec<&Pokemon>>();
+                // Airtable breaks ties on the form, in ascending order.
+                sorted.sort_by(|a, b| {
+                    let ordering = match field {
+                        "number" => a.number.cmp(&b.number),
+                        _ => a.name.as_str().cmp(b.name.as_str()),
+                    };
+                    match direction {
+                        "desc" => ordering.reverse(),
+                        _ => ordering,
+                    }
+                    .then_with(|| a.form.cmp(&b.form))
+                });
+                let records = sorted.into_iter().map(Self::record).collect::<Vec<_>>();
+                mocks.push(self.server.mock(|when, then| {
+                    when.method(prelude::GET)
+                        .path("/pokemons")
+                        .header("Authorization", format!("Bearer {APIKEY}"))
+                        .query_param("sort[0][field]", field)
+                        .query_param("sort[0][direction]", direction)
+                        .query_param("sort[1][field]", "form")
+                        .query_param("sort[1][direction]", "asc")
+                        .matches(without_formula);
+                    then.status(200).json_body(json!({ "records": records }));
+                }).id);
+            }
+
+            let evolutions = self.evolutions.lock().unwrap();
+            let mut sorted = evolutions.values().collect::<Vec<&Evolution>>();
+            sorted.sort_by_key(|evolution| (evolution.from.clone(), evolution.to.clone()));
+            let records = |keep: &dyn Fn(&Evolution) -> bool| {
+                sorted
+                    .iter()
+                    .filter(|evolution| keep(evolution))
+                    .map(|evolution| Self::evolution_record(evolution))
+                    .collect::<Vec<_>>()
+            };
+            for number in &numbers {
+                let formula = format!("OR({{from}} = {number}, {{to}} = {number})");
+                let linked = records(&|evolution| {
+                    [&evolution.from, &evolution.to].contains(&&PokemonNumber::try_from(*number).unwrap())
+                });
+                mocks.push(self.server.mock(|when, then| {
+                    when.method(prelude::GET).path("/evolutions").query_param("filterByFormula", formula);
+                    then.status(200).json_body(json!({ "records": linked }));
+                }).id);
+            }
+            for (to, evolution) in evolutions.iter() {
+                let record = Self::evolution_record(evolution);
+                mocks.push(self.server.mock(|when, then| {
+                    when.method(prelude::GET)
+                        .path("/evolutions")
+                        .query_param("filterByFormula", format!("{{to}} = {to}"));
+                    then.status(200).json_body(json!({ "records": [record] }));
+                }).id);
+            }
+            mocks.push(self.server.mock(|when, then| {
+                when.method(prelude::GET).path("/evolutions").query_param_exists("filterByFormula");
+                then.status(200).json_body(json!({ "records": [] }));
+            }).id);
+            let all = records(&|_| true);
+            mocks.push(self.server.mock(|when, then| {
+                when.method(prelude::GET)
+                    .path("/evolutions")
+                    .query_param("sort[0][field]", "from")
+                    .query_param("sort[1][field]", "to");
+                then.status(200).json_body(json!({ "records": all }));
+            }).id);
+        }
     }
-    
+
+    impl Repository for FakeAirtable {
+        fn insert(
+            &self,
+            key: PokemonKey,
+            name: PokemonName,
+            types: PokemonTypes,
+            stats: PokemonStats,
+        ) -> Result<Pokemon, InsertError> {
+            let pokemon = Pokemon::new(key.number.clone(), name.clone(), types.clone(), stats.clone())
+                .with_form(key.form.clone());
+            let mut post = self.server.mock(|when, then| {
+                when.method(prelude::POST)
+                    .path("/pokemons")
+                    .json_body(json!({"records": [{"fields": Self::fields(&pokemon)}]}));
+                then.status(200).json_body(json!({"records": [Self::record(&pokemon)]}));
+            });
+            let res = self.repo.insert(key, name, types, stats);
+            post.delete();
+            if res.is_ok() {
+                self.pokemons.lock().unwrap().insert(pokemon.key(), pokemon);
+                self.sync();
+            }
+            res
+        }
+
+        fn fetch_all(&self, query: &FetchAllQuery) -> Result<Vec<Pokemon>, FetchAllError> {
+            self.repo.fetch_all(query)
+        }
+
+        fn fetch_one(&self, key: PokemonKey) -> Result<Pokemon, FetchOneError> {
+            self.repo.fetch_one(key)
+        }
+
+        fn update(
+            &self,
+            key: PokemonKey,
+            name: PokemonName,
+            types: PokemonTypes,
+            stats: PokemonStats,
+        ) -> Result<Pokemon, UpdateError> {
+            let pokemon = Pokemon::new(key.number.clone(), name.clone(), types.clone(), stats.clone())
+                .with_form(key.form.clone());
+            let mut fields = Self::fields(&pokemon);
+            fields.as_object_mut().unwrap().remove("number");
+            fields.as_object_mut().unwrap().remove("form");
+            let mut patch = self.server.mock(|when, then| {
+                when.method(httpmock::Method::PATCH)
+                    .path(format!("/pokemons/{}", Self::record_id(&key)))
+                    .json_body(json!({ "fields": fields }));
+                then.status(200).json_body(Self::record(&pokemon));
+            });
+            let res = self.repo.update(key, name, types, stats);
+            patch.delete();
+            if res.is_ok() {
+                self.pokemons.lock().unwrap().insert(pokemon.key(), pokemon);
+                self.sync();
+            }
+            res
+        }
+
+        fn delete(&self, key: PokemonKey) -> Result<(), DeleteError> {
+            let number = key.number.clone();
+            // Deleting the base form deletes the whole national number.
+            let deleted = self
+                .pokemons
+                .lock()
+                .unwrap()
+                .keys()
+                .filter(|stored| **stored == key || (key.is_base() && stored.number == number))
+                .cloned()
+                .collect::<Vec<PokemonKey>>();
+            let mut deletes = deleted
+                .iter()
+                .map(|stored| {
+                    self.server.mock(|when, then| {
+                        when.method(prelude::DELETE)
+                            .path(format!("/pokemons/{}", Self::record_id(stored)));
+                        then.status(200);
+                    })
+                })
+                .collect::<Vec<_>>();
+            let linked = self
+                .evolutions
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|evolution| key.is_base() && (evolution.from == number || evolution.to == number))
+                .map(|evolution| u16::from(evolution.to.clone()))
+                .collect::<Vec<u16>>();
+            for to in &linked {
+                deletes.push(self.server.mock(|when, then| {
+                    when.method(prelude::DELETE).path(format!("/evolutions/evo{to}"));
+                    then.status(200);
+                }));
+            }
+            let res = self.repo.delete(key);
+            for delete in &mut deletes {
+                delete.delete();
+            }
+            if res.is_ok() {
+                let mut pokemons = self.pokemons.lock().unwrap();
+                for stored in deleted {
+                    pokemons.remove(&stored);
+                }
+                drop(pokemons);
+                let mut evolutions = self.evolutions.lock().unwrap();
+                for to in linked {
+                    evolutions.remove(&to);
+                }
+                drop(evolutions);
+                self.sync();
+            }
+            res
+        }
+
+        fn insert_evolution(&self, evolution: Evolution) -> Result<Evolution, InsertEvolutionError> {
+            let mut post = self.server.mock(|when, then| {
+                when.method(prelude::POST)
+                    .path("/evolutions")
+                    .json_body(json!({"records": [{"fields": Self::evolution_fields(&evolution)}]}));
+                then.status(200).json_body(json!({"records": [Self::evolution_record(&evolution)]}));
+            });
+            let res = self.repo.insert_evolution(evolution.clone());
+            post.delete();
+            if res.is_ok() {
+                self.evolutions.lock().unwrap().insert(u16::from(evolution.to.clone()), evolution);
+                self.sync();
+            }
+            res
+        }
+
+        fn fetch_evolutions(&self) -> Result<Vec<Evolution>, StorageError> {
+            self.repo.fetch_evolutions()
+        }
+    }
+
+    fn repository() -> Arc<dyn Repository> {
+        Arc::new(FakeAirtable::new())
+    }
+
+    repository_conformance!(repository);
 }
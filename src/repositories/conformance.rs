@@ -0,0 +1,642 @@
+//! The contract every `Repository` adapter must honor, written once and run
+//! against each of them with `repository_conformance!`. Adapters that store
+//! moves, abilities, regional dexes and trainers also run
+//! `learnset_conformance!`, `ability_conformance!`, `regional_dex_conformance!`
+//! and `trainer_conformance!`.
+
+use std::sync::Arc;
+
+use super::pokemon::{
+    DeleteError, DeleteMoveError, FetchAllQuery, FetchMoveError, FetchOneError,
+    FetchTrainerError, InsertAbilityError, InsertError, InsertEvolutionError, InsertMoveError,
+    InsertTrainerError, Repository, SortDirection, SortField, StorageError, UpdateError,
+    UpdateMoveError,
+};
+use crate::domain::entities::{
+    Ability, DexEntry, DexIndex, DexMark, DexName, DexStatus, Evolution, EvolutionTrigger,
+    LearnMethod, LearnsetEntry, Move, MoveAccuracy, MoveId, Pokemon, PokemonAbilities,
+    PokemonForm, PokemonKey, PokemonName, PokemonNumber, PokemonStats, PokemonTypes, Trainer,
+    TrainerId,
+};
+
+/// Builds an empty repository, once per check.
+pub type Factory<'a> = &'a dyn Fn() -> Arc<dyn Repository>;
+
+/// Expands into one `#[test]` per check of the contract, each using a fresh
+/// repository built by `$factory`.
+macro_rules! repository_conformance {
+    ($factory:expr) => {
+        mod conformance {
+            use super::*;
+            use crate::repositories::conformance;
+
+            #[test]
+            fn it_should_fetch_all_sorted_by_number_by_default() {
+                conformance::it_should_fetch_all_sorted_by_number_by_default(&$factory)
+            }
+
+            #[test]
+            fn it_should_fetch_all_in_the_requested_order() {
+                conformance::it_should_fetch_all_in_the_requested_order(&$factory)
+            }
+
+            #[test]
+            fn it_should_detect_conflicts_on_insert() {
+                conformance::it_should_detect_conflicts_on_insert(&$factory)
+            }
+
+            #[test]
+            fn it_should_report_missing_pokemons_as_not_found() {
+                conformance::it_should_report_missing_pokemons_as_not_found(&$factory)
+            }
+
+            #[test]
+            fn it_should_read_back_what_was_written() {
+                conformance::it_should_read_back_what_was_written(&$factory)
+            }
+
+            #[test]
+            fn it_should_keep_the_order_of_types() {
+                conformance::it_should_keep_the_order_of_types(&$factory)
+            }
+
+            #[test]
+            fn it_should_read_back_evolutions() {
+                conformance::it_should_read_back_evolutions(&$factory)
+            }
+
+            #[test]
+            fn it_should_drop_the_evolutions_of_a_deleted_pokemon() {
+                conformance::it_should_drop_the_evolutions_of_a_deleted_pokemon(&$factory)
+            }
+
+            #[test]
+            fn it_should_keep_the_forms_of_a_number_apart() {
+                conformance::it_should_keep_the_forms_of_a_number_apart(&$factory)
+            }
+
+            #[test]
+            fn it_should_delete_every_form_with_the_base_form() {
+                conformance::it_should_delete_every_form_with_the_base_form(&$factory)
+            }
+        }
+    };
+}
+
+/// The moves and learnsets part of the contract.
+macro_rules! learnset_conformance {
+    ($factory:expr) => {
+        mod learnset_conformance {
+            use super::*;
+            use crate::repositories::conformance;
+
+            #[test]
+            fn it_should_read_back_moves() {
+                conformance::it_should_read_back_moves(&$factory)
+            }
+
+            #[test]
+            fn it_should_read_back_learnsets_in_order() {
+                conformance::it_should_read_back_learnsets_in_order(&$factory)
+            }
+
+            #[test]
+            fn it_should_drop_learnset_entries_with_their_move_or_pokemon() {
+                conformance::it_should_drop_learnset_entries_with_their_move_or_pokemon(&$factory)
+            }
+        }
+    };
+}
+
+/// The abilities part of the contract.
+macro_rules! ability_conformance {
+    ($factory:expr) => {
+        mod ability_conformance {
+            use super::*;
+            use crate::repositories::conformance;
+
+            #[test]
+            fn it_should_read_back_abilities() {
+                conformance::it_should_read_back_abilities(&$factory)
+            }
+
+            #[test]
+            fn it_should_find_the_holders_of_an_ability() {
+                conformance::it_should_find_the_holders_of_an_ability(&$factory)
+            }
+        }
+    };
+}
+
+/// The regional dex part of the contract.
+macro_rules! regional_dex_conformance {
+    ($factory:expr) => {
+        mod regional_dex_conformance {
+            use super::*;
+            use crate::repositories::conformance;
+
+            #[test]
+            fn it_should_read_back_regional_dexes() {
+                conformance::it_should_read_back_regional_dexes(&$factory)
+            }
+
+            #[test]
+            fn it_should_refuse_invalid_regional_dexes() {
+                conformance::it_should_refuse_invalid_regional_dexes(&$factory)
+            }
+        }
+    };
+}
+
+/// The trainers part of the contract.
+macro_rules! trainer_conformance {
+    ($factory:expr) => {
+        mod trainer_conformance {
+            use super::*;
+            use crate::repositories::conformance;
+
+            #[test]
+            fn it_should_read_back_trainers() {
+                conformance::it_should_read_back_trainers(&$factory)
+            }
+
+            #[test]
+            fn it_should_read_back_trainer_dexes() {
+                conformance::it_should_read_back_trainer_dexes(&$factory)
+            }
+        }
+    };
+}
+
+fn gyarados() -> Pokemon {
+    Pokemon::new(
+        PokemonNumber::try_from(130).unwrap(),
+        PokemonName::try_from(String::from("Gyarados")).unwrap(),
+        PokemonTypes::try_from(vec![String::from("Water"), String::from("Flying")]).unwrap(),
+        PokemonStats::try_from([95, 125, 79, 60, 100, 81]).unwrap(),
+    )
+}
+
+fn alolan_vulpix() -> Pokemon {
+    Pokemon {
+        types: PokemonTypes::alolan_vulpix(),
+        ..Pokemon::vulpix()
+    }
+    .with_form(Some(PokemonForm::alola()))
+}
+
+fn seeded(factory: Factory, pokemons: Vec<Pokemon>) -> Arc<dyn Repository> {
+    let repo = factory();
+    for pokemon in pokemons {
+        insert(&repo, pokemon).unwrap();
+    }
+    repo
+}
+
+fn insert(repo: &Arc<dyn Repository>, pokemon: Pokemon) -> Result<Pokemon, InsertError> {
+    let key = pokemon.key();
+    repo.insert(key, pokemon.name, pokemon.types, pokemon.stats)
+}
+
+fn update(repo: &Arc<dyn Repository>, pokemon: Pokemon) -> Result<Pokemon, UpdateError> {
+    let key = pokemon.key();
+    repo.update(key, pokemon.name, pokemon.types, pokemon.stats)
+}
+
+fn numbers(repo: &Arc<dyn Repository>, query: &FetchAllQuery) -> Vec<u16> {
+    repo.fetch_all(query)
+        .unwrap()
+        .into_iter()
+        .map(|pokemon| u16::from(pokemon.number))
+        .collect()
+}
+
+pub fn it_should_fetch_all_sorted_by_number_by_default(factory: Factory) {
+    let repo = seeded(factory, vec![gyarados(), Pokemon::vulpix(), Pokemon::pikachu()]);
+
+    assert_eq!(numbers(&repo, &FetchAllQuery::default()), vec![25, 37, 130]);
+}
+
+pub fn it_should_fetch_all_in_the_requested_order(factory: Factory) {
+    let repo = seeded(factory, vec![Pokemon::pikachu(), gyarados(), Pokemon::vulpix()]);
+
+    let descending = FetchAllQuery {
+        direction: SortDirection::Descending,
+        ..FetchAllQuery::default()
+    };
+    let by_name = FetchAllQuery {
+        sort: SortField::Name,
+        ..FetchAllQuery::default()
+    };
+
+    assert_eq!(numbers(&repo, &descending), vec![130, 37, 25]);
+    assert_eq!(numbers(&repo, &by_name), vec![130, 25, 37]);
+}
+
+pub fn it_should_detect_conflicts_on_insert(factory: Factory) {
+    let repo = seeded(factory, vec![Pokemon::pikachu()]);
+
+    let res = insert(&repo, Pokemon::pikachu());
+
+    assert!(matches!(res, Err(InsertError::Conflict)));
+    assert_eq!(numbers(&repo, &FetchAllQuery::default()), vec![25]);
+}
+
+pub fn it_should_report_missing_pokemons_as_not_found(factory: Factory) {
+    let repo = seeded(factory, vec![Pokemon::pikachu()]);
+
+    assert!(matches!(
+        repo.fetch_one(PokemonNumber::vulpix().into()),
+        Err(FetchOneError::NotFound)
+    ));
+    assert!(matches!(
+        update(&repo, Pokemon::vulpix()),
+        Err(UpdateError::NotFound)
+    ));
+    assert!(matches!(
+        repo.delete(PokemonNumber::vulpix().into()),
+        Err(DeleteError::NotFound)
+    ));
+    assert_eq!(numbers(&repo, &FetchAllQuery::default()), vec![25]);
+}
+
+pub fn it_should_read_back_what_was_written(factory: Factory) {
+    let repo = factory();
+
+    assert_eq!(insert(&repo, Pokemon::vulpix()).unwrap(), Pokemon::vulpix());
+    assert_eq!(repo.fetch_one(PokemonNumber::vulpix().into()).unwrap(), Pokemon::vulpix());
+
+    let ninetales = Pokemon::new(
+        PokemonNumber::vulpix(),
+        PokemonName::try_from(String::from("Ninetales")).unwrap(),
+        PokemonTypes::vulpix(),
+        PokemonStats::try_from([73, 76, 75, 81, 100, 100]).unwrap(),
+    );
+    assert_eq!(update(&repo, ninetales.clone()).unwrap(), ninetales);
+    assert_eq!(repo.fetch_one(PokemonNumber::vulpix().into()).unwrap(), ninetales);
+    assert_eq!(
+        repo.fetch_all(&FetchAllQuery::default()).unwrap(),
+        vec![ninetales]
+    );
+
+    repo.delete(PokemonNumber::vulpix().into()).unwrap();
+    assert!(matches!(
+        repo.fetch_one(PokemonNumber::vulpix().into()),
+        Err(FetchOneError::NotFound)
+    ));
+}
+
+pub fn it_should_keep_the_order_of_types(factory: Factory) {
+    let repo = seeded(factory, vec![gyarados()]);
+    let types = |repo: &Arc<dyn Repository>| {
+        Vec::<String>::from(repo.fetch_one(gyarados().key()).unwrap().types)
+    };
+
+    assert_eq!(types(&repo), vec!["Water", "Flying"]);
+
+    let swapped = Pokemon {
+        types: PokemonTypes::try_from(vec![String::from("Flying"), String::from("Water")])
+            .unwrap(),
+        ..gyarados()
+    };
+    update(&repo, swapped).unwrap();
+    assert_eq!(types(&repo), vec!["Flying", "Water"]);
+}
+
+fn evolution(from: PokemonNumber, to: PokemonNumber, trigger: &str) -> Evolution {
+    Evolution::new(
+        from,
+        to,
+        EvolutionTrigger::try_from(String::from(trigger)).unwrap(),
+        vec![],
+    )
+}
+
+pub fn it_should_read_back_evolutions(factory: Factory) {
+    let repo = seeded(factory, vec![Pokemon::pikachu(), Pokemon::vulpix(), gyarados()]);
+    let first = Evolution {
+        conditions: vec![String::from("daytime"), String::from("knows Ancient Power")],
+        ..evolution(PokemonNumber::vulpix(), gyarados().number, "level:20")
+    };
+    let second = evolution(PokemonNumber::pikachu(), PokemonNumber::vulpix(), "stone:Fire Stone");
+
+    assert_eq!(repo.insert_evolution(first.clone()).unwrap(), first);
+    assert_eq!(repo.insert_evolution(second.clone()).unwrap(), second);
+    let duplicate = evolution(PokemonNumber::pikachu(), gyarados().number, "trade");
+    assert!(matches!(
+        repo.insert_evolution(duplicate),
+        Err(InsertEvolutionError::Conflict)
+    ));
+
+    // Rewriting the Pokemons themselves keeps their evolutions.
+    repo.upsert_all(vec![Pokemon::pikachu(), Pokemon::vulpix()]).unwrap();
+    assert_eq!(repo.fetch_evolutions().unwrap(), vec![second, first]);
+}
+
+pub fn it_should_drop_the_evolutions_of_a_deleted_pokemon(factory: Factory) {
+    let repo = seeded(factory, vec![Pokemon::pikachu(), Pokemon::vulpix(), gyarados()]);
+    repo.insert_evolution(evolution(PokemonNumber::pikachu(), PokemonNumber::vulpix(), "friendship"))
+        .unwrap();
+    repo.insert_evolution(evolution(PokemonNumber::vulpix(), gyarados().number, "level:20"))
+        .unwrap();
+
+    repo.delete(PokemonNumber::vulpix().into()).unwrap();
+
+    assert_eq!(repo.fetch_evolutions().unwrap(), vec![]);
+}
+
+pub fn it_should_keep_the_forms_of_a_number_apart(factory: Factory) {
+    let repo = seeded(factory, vec![alolan_vulpix(), Pokemon::vulpix(), Pokemon::pikachu()]);
+    let alola = PokemonKey::new(PokemonNumber::vulpix(), Some(PokemonForm::alola()));
+
+    assert!(matches!(insert(&repo, alolan_vulpix()), Err(InsertError::Conflict)));
+    assert_eq!(repo.fetch_one(alola.clone()).unwrap(), alolan_vulpix());
+    assert_eq!(repo.fetch_one(PokemonNumber::vulpix().into()).unwrap(), Pokemon::vulpix());
+    assert_eq!(
+        repo.fetch_all(&FetchAllQuery::by_number(PokemonNumber::vulpix())).unwrap(),
+        vec![Pokemon::vulpix(), alolan_vulpix()]
+    );
+
+    let stronger = Pokemon {
+        stats: PokemonStats::try_from([73, 76, 75, 81, 100, 100]).unwrap(),
+        ..alolan_vulpix()
+    };
+    update(&repo, stronger.clone()).unwrap();
+    assert_eq!(repo.fetch_one(alola.clone()).unwrap(), stronger);
+    assert_eq!(repo.fetch_one(PokemonNumber::vulpix().into()).unwrap(), Pokemon::vulpix());
+
+    repo.delete(alola.clone()).unwrap();
+    assert!(matches!(repo.fetch_one(alola), Err(FetchOneError::NotFound)));
+    assert_eq!(numbers(&repo, &FetchAllQuery::default()), vec![25, 37]);
+}
+
+pub fn it_should_delete_every_form_with_the_base_form(factory: Factory) {
+    let repo = seeded(factory, vec![Pokemon::vulpix(), alolan_vulpix(), gyarados()]);
+    repo.insert_evolution(evolution(PokemonNumber::vulpix(), gyarados().number, "level:20"))
+        .unwrap();
+
+    repo.delete(PokemonNumber::vulpix().into()).unwrap();
+
+    assert_eq!(numbers(&repo, &FetchAllQuery::default()), vec![130]);
+    assert_eq!(repo.fetch_evolutions().unwrap(), vec![]);
+}
+
+fn learn(learned: &Move, method: &str) -> LearnsetEntry {
+    LearnsetEntry::new(
+        learned.id.clone(),
+        LearnMethod::try_from(String::from(method)).unwrap(),
+    )
+}
+
+pub fn it_should_read_back_moves(factory: Factory) {
+    let repo = factory();
+    let missing = MoveId::try_from(1).unwrap();
+
+    assert_eq!(repo.insert_move(Move::swift()).unwrap(), Move::swift());
+    assert_eq!(repo.insert_move(Move::thunder_shock()).unwrap(), Move::thunder_shock());
+    assert!(matches!(
+        repo.insert_move(Move::swift()),
+        Err(InsertMoveError::Conflict)
+    ));
+    assert_eq!(
+        repo.fetch_moves().unwrap(),
+        vec![Move::thunder_shock(), Move::swift()]
+    );
+
+    let sure_shot = Move {
+        accuracy: None,
+        ..Move::thunder_shock()
+    };
+    assert_eq!(repo.update_move(sure_shot.clone()).unwrap(), sure_shot);
+    assert_eq!(repo.fetch_move(sure_shot.id.clone()).unwrap(), sure_shot);
+    let accurate = Move {
+        accuracy: Some(MoveAccuracy::try_from(90).unwrap()),
+        ..Move::swift()
+    };
+    repo.update_move(accurate.clone()).unwrap();
+    assert_eq!(repo.fetch_move(accurate.id.clone()).unwrap(), accurate);
+
+    assert!(matches!(
+        repo.fetch_move(missing.clone()),
+        Err(FetchMoveError::NotFound)
+    ));
+    assert!(matches!(
+        repo.update_move(Move {
+            id: missing.clone(),
+            ..Move::swift()
+        }),
+        Err(UpdateMoveError::NotFound)
+    ));
+    assert!(matches!(
+        repo.delete_move(missing),
+        Err(DeleteMoveError::NotFound)
+    ));
+    repo.delete_move(Move::swift().id).unwrap();
+    assert_eq!(repo.fetch_moves().unwrap(), vec![sure_shot]);
+}
+
+pub fn it_should_read_back_learnsets_in_order(factory: Factory) {
+    let repo = seeded(factory, vec![Pokemon::pikachu()]);
+    repo.insert_move(Move::thunder_shock()).unwrap();
+    repo.insert_move(Move::swift()).unwrap();
+    let learnset = vec![
+        learn(&Move::swift(), "machine"),
+        learn(&Move::swift(), "level:20"),
+        learn(&Move::thunder_shock(), "level:1"),
+        learn(&Move::thunder_shock(), "machine"),
+    ];
+    let expected = vec![
+        learn(&Move::thunder_shock(), "level:1"),
+        learn(&Move::swift(), "level:20"),
+        learn(&Move::thunder_shock(), "machine"),
+        learn(&Move::swift(), "machine"),
+    ];
+
+    assert_eq!(repo.replace_learnset(PokemonNumber::pikachu(), learnset).unwrap(), expected);
+    assert_eq!(repo.fetch_learnset(PokemonNumber::pikachu()).unwrap(), expected);
+    assert_eq!(repo.fetch_learnset(PokemonNumber::vulpix()).unwrap(), vec![]);
+
+    let tutored = vec![learn(&Move::swift(), "tutor")];
+    repo.replace_learnset(PokemonNumber::pikachu(), tutored.clone())
+        .unwrap();
+    assert_eq!(repo.fetch_learnset(PokemonNumber::pikachu()).unwrap(), tutored);
+}
+
+pub fn it_should_drop_learnset_entries_with_their_move_or_pokemon(factory: Factory) {
+    let repo = seeded(factory, vec![Pokemon::pikachu(), Pokemon::vulpix()]);
+    repo.insert_move(Move::thunder_shock()).unwrap();
+    repo.insert_move(Move::swift()).unwrap();
+    let learnset = vec![
+        learn(&Move::thunder_shock(), "level:1"),
+        learn(&Move::swift(), "egg"),
+    ];
+    repo.replace_learnset(PokemonNumber::pikachu(), learnset.clone())
+        .unwrap();
+    repo.replace_learnset(PokemonNumber::vulpix(), learnset)
+        .unwrap();
+
+    repo.delete_move(Move::thunder_shock().id).unwrap();
+    repo.delete(PokemonNumber::vulpix().into()).unwrap();
+
+    assert_eq!(
+        repo.fetch_learnset(PokemonNumber::pikachu()).unwrap(),
+        vec![learn(&Move::swift(), "egg")]
+    );
+    assert_eq!(repo.fetch_learnset(PokemonNumber::vulpix()).unwrap(), vec![]);
+}
+
+fn abilities(regular: &[Ability], hidden: Option<Ability>) -> PokemonAbilities {
+    PokemonAbilities::try_from((
+        regular.iter().map(|ability| String::from(ability.name.clone())).collect(),
+        hidden.map(|ability| String::from(ability.name)),
+    ))
+    .unwrap()
+}
+
+pub fn it_should_read_back_abilities(factory: Factory) {
+    let repo = seeded(factory, vec![Pokemon::pikachu()]);
+
+    assert_eq!(repo.insert_ability(Ability::static_()).unwrap(), Ability::static_());
+    repo.insert_ability(Ability::lightning_rod()).unwrap();
+    assert!(matches!(
+        repo.insert_ability(Ability::static_()),
+        Err(InsertAbilityError::Conflict)
+    ));
+    assert_eq!(
+        repo.fetch_abilities().unwrap(),
+        vec![Ability::lightning_rod(), Ability::static_()]
+    );
+
+    let pikachu = abilities(&[Ability::static_()], Some(Ability::lightning_rod()));
+    assert_eq!(
+        repo.replace_abilities(PokemonNumber::pikachu(), pikachu.clone())
+            .unwrap(),
+        pikachu
+    );
+    assert_eq!(repo.fetch_pokemon_abilities(PokemonNumber::pikachu()).unwrap(), pikachu);
+    assert!(repo.fetch_pokemon_abilities(PokemonNumber::vulpix()).unwrap().is_empty());
+
+    let swapped = abilities(&[Ability::lightning_rod(), Ability::static_()], None);
+    repo.replace_abilities(PokemonNumber::pikachu(), swapped.clone())
+        .unwrap();
+    assert_eq!(repo.fetch_pokemon_abilities(PokemonNumber::pikachu()).unwrap(), swapped);
+}
+
+pub fn it_should_find_the_holders_of_an_ability(factory: Factory) {
+    let repo = seeded(factory, vec![Pokemon::pikachu(), Pokemon::vulpix(), gyarados()]);
+    repo.insert_ability(Ability::static_()).unwrap();
+    repo.insert_ability(Ability::lightning_rod()).unwrap();
+    repo.replace_abilities(gyarados().number, abilities(&[Ability::static_()], None))
+        .unwrap();
+    repo.replace_abilities(
+        PokemonNumber::pikachu(),
+        abilities(&[Ability::lightning_rod()], Some(Ability::static_())),
+    )
+    .unwrap();
+    repo.replace_abilities(PokemonNumber::vulpix(), abilities(&[Ability::static_()], None))
+        .unwrap();
+
+    repo.delete(PokemonNumber::vulpix().into()).unwrap();
+
+    assert_eq!(
+        repo.fetch_ability_holders(Ability::static_().name).unwrap(),
+        vec![(PokemonNumber::pikachu(), true), (gyarados().number, false)]
+    );
+    assert!(repo.fetch_pokemon_abilities(PokemonNumber::vulpix()).unwrap().is_empty());
+}
+
+fn dex_entry(index: u16, number: PokemonNumber) -> DexEntry {
+    DexEntry::new(DexIndex::from(index), number)
+}
+
+pub fn it_should_read_back_regional_dexes(factory: Factory) {
+    let repo = seeded(factory, vec![Pokemon::pikachu(), Pokemon::vulpix(), gyarados()]);
+    let johto = vec![
+        dex_entry(22, PokemonNumber::pikachu()),
+        dex_entry(0, PokemonNumber::vulpix()),
+        dex_entry(77, gyarados().number),
+    ];
+
+    let stored = repo.replace_regional_dex(DexName::johto(), johto).unwrap();
+
+    let indices = stored.iter().map(|entry| u16::from(entry.index)).collect::<Vec<u16>>();
+    assert_eq!(indices, vec![0, 22, 77]);
+    assert_eq!(repo.fetch_regional_dex(DexName::johto()).unwrap(), stored);
+    let kanto = DexName::try_from(String::from("kanto")).unwrap();
+    assert_eq!(repo.fetch_regional_dex(kanto).unwrap(), vec![]);
+
+    repo.delete(PokemonNumber::vulpix().into()).unwrap();
+    assert_eq!(repo.fetch_regional_dex(DexName::johto()).unwrap(), stored[1..].to_vec());
+
+    repo.replace_regional_dex(DexName::johto(), vec![]).unwrap();
+    assert_eq!(repo.fetch_regional_dex(DexName::johto()).unwrap(), vec![]);
+}
+
+pub fn it_should_refuse_invalid_regional_dexes(factory: Factory) {
+    let repo = seeded(factory, vec![Pokemon::pikachu(), Pokemon::vulpix()]);
+    repo.replace_regional_dex(DexName::johto(), vec![dex_entry(1, PokemonNumber::pikachu())])
+        .unwrap();
+
+    let unknown = vec![dex_entry(1, gyarados().number)];
+    let same_index = vec![
+        dex_entry(1, PokemonNumber::pikachu()),
+        dex_entry(1, PokemonNumber::vulpix()),
+    ];
+    let same_number = vec![
+        dex_entry(1, PokemonNumber::pikachu()),
+        dex_entry(2, PokemonNumber::pikachu()),
+    ];
+
+    for entries in [unknown, same_index, same_number] {
+        assert!(matches!(
+            repo.replace_regional_dex(DexName::johto(), entries),
+            Err(StorageError::ConstraintViolation(_))
+        ));
+    }
+    assert_eq!(
+        repo.fetch_regional_dex(DexName::johto()).unwrap(),
+        vec![dex_entry(1, PokemonNumber::pikachu())]
+    );
+}
+
+pub fn it_should_read_back_trainers(factory: Factory) {
+    let repo = factory();
+
+    assert_eq!(repo.insert_trainer(Trainer::red()).unwrap(), Trainer::red());
+    assert!(matches!(
+        repo.insert_trainer(Trainer::red()),
+        Err(InsertTrainerError::Conflict)
+    ));
+    assert_eq!(repo.fetch_trainer(Trainer::red().id).unwrap(), Trainer::red());
+    let blue = TrainerId::try_from(String::from("blue")).unwrap();
+    assert!(matches!(repo.fetch_trainer(blue), Err(FetchTrainerError::NotFound)));
+}
+
+pub fn it_should_read_back_trainer_dexes(factory: Factory) {
+    let repo = seeded(factory, vec![Pokemon::pikachu(), Pokemon::vulpix(), gyarados()]);
+    repo.insert_trainer(Trainer::red()).unwrap();
+    let red = || Trainer::red().id;
+    let shiny = DexStatus::default().mark(DexMark::Shiny);
+    let seen = DexStatus::default().mark(DexMark::Seen);
+
+    repo.replace_dex_status(red(), gyarados().number, shiny).unwrap();
+    repo.replace_dex_status(red(), PokemonNumber::pikachu(), shiny).unwrap();
+    repo.replace_dex_status(red(), PokemonNumber::vulpix(), seen).unwrap();
+    repo.replace_dex_status(red(), PokemonNumber::pikachu(), seen).unwrap();
+    repo.replace_dex_status(red(), gyarados().number, DexStatus::default())
+        .unwrap();
+    repo.delete(PokemonNumber::vulpix().into()).unwrap();
+
+    assert_eq!(
+        repo.fetch_trainer_dex(red()).unwrap(),
+        vec![(PokemonNumber::pikachu(), seen)]
+    );
+    let blue = TrainerId::try_from(String::from("blue")).unwrap();
+    assert_eq!(repo.fetch_trainer_dex(blue.clone()).unwrap(), vec![]);
+    for (trainer, number) in [(blue, gyarados().number), (red(), PokemonNumber::vulpix())] {
+        assert!(matches!(
+            repo.replace_dex_status(trainer, number, DexStatus::default()),
+            Err(StorageError::ConstraintViolation(_))
+        ));
+    }
+}
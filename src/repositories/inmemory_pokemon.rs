@@ -1,27 +1,71 @@
 
+use crate::domain::entities::PokemonStats;
 use crate::domain::entities::PokemonTypes;
+use crate::domain::entities::PokemonKey;
 use crate::domain::entities::PokemonName;
 use crate::domain::entities::PokemonNumber;
 
 use super::pokemon::DeleteError;
+use super::pokemon::DeleteMoveError;
 use super::pokemon::FetchAllError;
+use super::pokemon::FetchAllQuery;
+use super::pokemon::FetchMoveError;
 use super::pokemon::FetchOneError;
+use super::pokemon::FetchTrainerError;
+use super::pokemon::InsertAbilityError;
 use super::pokemon::InsertError;
+use super::pokemon::InsertEvolutionError;
+use super::pokemon::InsertMoveError;
+use super::pokemon::InsertTrainerError;
 use super::pokemon::Repository;
+use super::pokemon::SortDirection;
+use super::pokemon::SortField;
+use super::pokemon::StorageError;
+use super::pokemon::UpdateError;
+use super::pokemon::UpdateMoveError;
+use super::pokemon::UpsertAllError;
 
+use crate::domain::entities::Ability;
+use crate::domain::entities::DexEntry;
+use crate::domain::entities::DexName;
+use crate::domain::entities::DexStatus;
+use crate::domain::entities::Evolution;
+use crate::domain::entities::LearnsetEntry;
+use crate::domain::entities::Move;
+use crate::domain::entities::MoveId;
 use crate::domain::entities::Pokemon;
+use crate::domain::entities::PokemonAbilities;
+use crate::domain::entities::Trainer;
+use crate::domain::entities::TrainerId;
 
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Mutex;
 
 pub struct InMemoryRepository {
     pub(crate) error: bool,
     pub(crate) pokemons: Mutex<Vec<Pokemon>>,
+    pub(crate) evolutions: Mutex<Vec<Evolution>>,
+    pub(crate) moves: Mutex<Vec<Move>>,
+    pub(crate) learnsets: Mutex<HashMap<PokemonNumber, Vec<LearnsetEntry>>>,
+    pub(crate) abilities: Mutex<Vec<Ability>>,
+    pub(crate) pokemon_abilities: Mutex<HashMap<PokemonNumber, PokemonAbilities>>,
+    pub(crate) dexes: Mutex<HashMap<DexName, Vec<DexEntry>>>,
+    pub(crate) trainers: Mutex<Vec<Trainer>>,
+    pub(crate) trainer_dexes: Mutex<HashMap<TrainerId, BTreeMap<PokemonNumber, DexStatus>>>,
 }
 
 impl InMemoryRepository {
     pub fn new() -> Self {
         Self {
             pokemons: Mutex::new(vec![]),
+            evolutions: Mutex::new(vec![]),
+            moves: Mutex::new(vec![]),
+            learnsets: Mutex::new(HashMap::new()),
+            abilities: Mutex::new(vec![]),
+            pokemon_abilities: Mutex::new(HashMap::new()),
+            dexes: Mutex::new(HashMap::new()),
+            trainers: Mutex::new(vec![]),
+            trainer_dexes: Mutex::new(HashMap::new()),
             error: false,
         }
     }
@@ -35,72 +79,565 @@ impl InMemoryRepository {
     }
 }
 
+fn injected_error() -> StorageError {
+    StorageError::Backend("in-memory repository is set to fail".to_owned())
+}
+
+fn poisoned() -> StorageError {
+    StorageError::ConnectionLost("in-memory lock is poisoned".to_owned())
+}
+
+impl Default for InMemoryRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Repository for InMemoryRepository {
     fn insert(
         &self,
-        number: PokemonNumber,
+        key: PokemonKey,
         name: PokemonName,
         types: PokemonTypes,
+        stats: PokemonStats,
     ) -> Result<Pokemon, InsertError> {
         if self.error {
-            return Err(InsertError::Unknown);
+            return Err(InsertError::Storage(injected_error()));
         }
         let mut pokemons = match self.pokemons.lock() {
             Ok(lock) => lock,
-            _ => return Err(InsertError::Unknown),
+            Err(_) => return Err(InsertError::Storage(poisoned())),
         };
-        if pokemons.iter().any(|pokemon| pokemon.number == number) {
+        if pokemons.iter().any(|pokemon| pokemon.key() == key) {
             return Err(InsertError::Conflict);
         }
-        let pokemon = Pokemon::new(number, name, types);
+        let pokemon = Pokemon::new(key.number, name, types, stats).with_form(key.form);
         pokemons.push(pokemon.clone());
         Ok(pokemon)
     }
 
-    fn fetch_all(&self) -> Result<Vec<Pokemon>, FetchAllError> {
+    fn fetch_all(&self, query: &FetchAllQuery) -> Result<Vec<Pokemon>, FetchAllError> {
         if self.error {
-            return Err(FetchAllError::Unknown);
+            return Err(FetchAllError::Storage(injected_error()));
         }
 
         let mut pokemons = match self.pokemons.lock() {
-            Ok(lock) => lock.to_vec(),
-            Err(_) => return Err(FetchAllError::Unknown),
+            Ok(lock) => lock
+                .iter()
+                .filter(|p| query.matches(p))
+                .cloned()
+                .collect::<Vec<Pokemon>>(),
+            Err(_) => return Err(FetchAllError::Storage(poisoned())),
         };
 
-        pokemons.sort_by(|a, b| a.number.cmp(&b.number));
-        Ok(pokemons)
+        pokemons.sort_by(|a, b| {
+            let ordering = match query.sort {
+                SortField::Number => a.number.cmp(&b.number),
+                SortField::Name => a.name.as_str().cmp(b.name.as_str()),
+            };
+            let ordering = match query.direction {
+                SortDirection::Ascending => ordering,
+                SortDirection::Descending => ordering.reverse(),
+            };
+            ordering.then_with(|| a.key().cmp(&b.key()))
+        });
+        Ok(pokemons
+            .into_iter()
+            .skip(query.offset)
+            .take(query.limit.unwrap_or(usize::MAX))
+            .collect())
     }
 
-    fn fetch_one(&self, number: PokemonNumber) -> Result<Pokemon, FetchOneError> {
+    fn fetch_one(&self, key: PokemonKey) -> Result<Pokemon, FetchOneError> {
         if self.error {
-            return Err(FetchOneError::Unknown);
+            return Err(FetchOneError::Storage(injected_error()));
         }
 
         let pokemons = match self.pokemons.lock() {
             Ok(lock) => lock.to_vec(),
-            Err(_) => return Err(FetchOneError::Unknown),
+            Err(_) => return Err(FetchOneError::Storage(poisoned())),
         };
 
-        match pokemons.iter().find(|p| p.number == number) {
+        match pokemons.iter().find(|p| p.key() == key) {
             Some(pokemon) => Ok(pokemon.clone()),
             None => Err(FetchOneError::NotFound),
         }
     }
 
-    fn delete(&self, number: PokemonNumber) -> Result<(), DeleteError> {
+    fn update(
+        &self,
+        key: PokemonKey,
+        name: PokemonName,
+        types: PokemonTypes,
+        stats: PokemonStats,
+    ) -> Result<Pokemon, UpdateError> {
+        if self.error {
+            return Err(UpdateError::Storage(injected_error()));
+        }
+        let mut pokemons = match self.pokemons.lock() {
+            Ok(lock) => lock,
+            Err(_) => return Err(UpdateError::Storage(poisoned())),
+        };
+
+        let pokemon = match pokemons.iter_mut().find(|p| p.key() == key) {
+            Some(pokemon) => pokemon,
+            None => return Err(UpdateError::NotFound),
+        };
+        *pokemon = Pokemon::new(key.number, name, types, stats).with_form(key.form);
+        Ok(pokemon.clone())
+    }
+
+    fn delete(&self, key: PokemonKey) -> Result<(), DeleteError> {
         if self.error {
-            return Err(DeleteError::Unknown);
+            return Err(DeleteError::Storage(injected_error()));
         }
         let mut pokemons = match self.pokemons.lock() {
             Ok(lock) => lock,
-            Err(_) => return Err(DeleteError::Unknown),
+            Err(_) => return Err(DeleteError::Storage(poisoned())),
         };
 
-        let index = match pokemons.iter().position(|p| p.number == number) {
+        let index = match pokemons.iter().position(|p| p.key() == key) {
             Some(index) => index,
             None => return Err(DeleteError::NotFound),
         };
         pokemons.remove(index);
+        if !key.is_base() {
+            return Ok(());
+        }
+        let number = key.number;
+        pokemons.retain(|p| p.number != number);
+
+        let mut evolutions = match self.evolutions.lock() {
+            Ok(lock) => lock,
+            Err(_) => return Err(DeleteError::Storage(poisoned())),
+        };
+        evolutions.retain(|evolution| evolution.from != number && evolution.to != number);
+
+        match self.learnsets.lock() {
+            Ok(mut learnsets) => learnsets.remove(&number),
+            Err(_) => return Err(DeleteError::Storage(poisoned())),
+        };
+        match self.pokemon_abilities.lock() {
+            Ok(mut abilities) => abilities.remove(&number),
+            Err(_) => return Err(DeleteError::Storage(poisoned())),
+        };
+        match self.dexes.lock() {
+            Ok(mut dexes) => {
+                for entries in dexes.values_mut() {
+                    entries.retain(|entry| entry.number != number);
+                }
+                dexes.retain(|_, entries| !entries.is_empty());
+            }
+            Err(_) => return Err(DeleteError::Storage(poisoned())),
+        };
+        match self.trainer_dexes.lock() {
+            Ok(mut trainer_dexes) => {
+                for statuses in trainer_dexes.values_mut() {
+                    statuses.remove(&number);
+                }
+            }
+            Err(_) => return Err(DeleteError::Storage(poisoned())),
+        };
+        Ok(())
+    }
+
+    fn upsert_all(&self, new_pokemons: Vec<Pokemon>) -> Result<(), UpsertAllError> {
+        if self.error {
+            return Err(UpsertAllError::Storage(injected_error()));
+        }
+        let mut pokemons = match self.pokemons.lock() {
+            Ok(lock) => lock,
+            Err(_) => return Err(UpsertAllError::Storage(poisoned())),
+        };
+
+        for new_pokemon in new_pokemons {
+            match pokemons.iter_mut().find(|p| p.key() == new_pokemon.key()) {
+                Some(pokemon) => *pokemon = new_pokemon,
+                None => pokemons.push(new_pokemon),
+            }
+        }
+        Ok(())
+    }
+
+    fn insert_evolution(&self, evolution: Evolution) -> Result<Evolution, InsertEvolutionError> {
+        if self.error {
+            return Err(InsertEvolutionError::Storage(injected_error()));
+        }
+        let mut evolutions = match self.evolutions.lock() {
+            Ok(lock) => lock,
+            Err(_) => return Err(InsertEvolutionError::Storage(poisoned())),
+        };
+        if evolutions.iter().any(|e| e.to == evolution.to) {
+            return Err(InsertEvolutionError::Conflict);
+        }
+        evolutions.push(evolution.clone());
+        evolutions.sort_by(|a, b| (&a.from, &a.to).cmp(&(&b.from, &b.to)));
+        Ok(evolution)
+    }
+
+    fn fetch_evolutions(&self) -> Result<Vec<Evolution>, StorageError> {
+        if self.error {
+            return Err(injected_error());
+        }
+        match self.evolutions.lock() {
+            Ok(evolutions) => Ok(evolutions.clone()),
+            Err(_) => Err(poisoned()),
+        }
+    }
+
+    fn insert_move(&self, new_move: Move) -> Result<Move, InsertMoveError> {
+        if self.error {
+            return Err(InsertMoveError::Storage(injected_error()));
+        }
+        let mut moves = match self.moves.lock() {
+            Ok(lock) => lock,
+            Err(_) => return Err(InsertMoveError::Storage(poisoned())),
+        };
+        if moves.iter().any(|m| m.id == new_move.id) {
+            return Err(InsertMoveError::Conflict);
+        }
+        moves.push(new_move.clone());
+        moves.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(new_move)
+    }
+
+    fn fetch_moves(&self) -> Result<Vec<Move>, StorageError> {
+        if self.error {
+            return Err(injected_error());
+        }
+        match self.moves.lock() {
+            Ok(moves) => Ok(moves.clone()),
+            Err(_) => Err(poisoned()),
+        }
+    }
+
+    fn fetch_move(&self, id: MoveId) -> Result<Move, FetchMoveError> {
+        if self.error {
+            return Err(FetchMoveError::Storage(injected_error()));
+        }
+        let moves = match self.moves.lock() {
+            Ok(lock) => lock,
+            Err(_) => return Err(FetchMoveError::Storage(poisoned())),
+        };
+        match moves.iter().find(|m| m.id == id) {
+            Some(found) => Ok(found.clone()),
+            None => Err(FetchMoveError::NotFound),
+        }
+    }
+
+    fn update_move(&self, new_move: Move) -> Result<Move, UpdateMoveError> {
+        if self.error {
+            return Err(UpdateMoveError::Storage(injected_error()));
+        }
+        let mut moves = match self.moves.lock() {
+            Ok(lock) => lock,
+            Err(_) => return Err(UpdateMoveError::Storage(poisoned())),
+        };
+        match moves.iter_mut().find(|m| m.id == new_move.id) {
+            Some(found) => *found = new_move.clone(),
+            None => return Err(UpdateMoveError::NotFound),
+        }
+        Ok(new_move)
+    }
+
+    fn delete_move(&self, id: MoveId) -> Result<(), DeleteMoveError> {
+        if self.error {
+            return Err(DeleteMoveError::Storage(injected_error()));
+        }
+        let mut moves = match self.moves.lock() {
+            Ok(lock) => lock,
+            Err(_) => return Err(DeleteMoveError::Storage(poisoned())),
+        };
+        let index = match moves.iter().position(|m| m.id == id) {
+            Some(index) => index,
+            None => return Err(DeleteMoveError::NotFound),
+        };
+        moves.remove(index);
+
+        let mut learnsets = match self.learnsets.lock() {
+            Ok(lock) => lock,
+            Err(_) => return Err(DeleteMoveError::Storage(poisoned())),
+        };
+        for learnset in learnsets.values_mut() {
+            learnset.retain(|entry| entry.move_id != id);
+        }
         Ok(())
     }
+
+    fn replace_learnset(
+        &self,
+        number: PokemonNumber,
+        mut learnset: Vec<LearnsetEntry>,
+    ) -> Result<Vec<LearnsetEntry>, StorageError> {
+        if self.error {
+            return Err(injected_error());
+        }
+        let pokemons = self.pokemons.lock().map_err(|_| poisoned())?;
+        let moves = self.moves.lock().map_err(|_| poisoned())?;
+        let mut learnsets = self.learnsets.lock().map_err(|_| poisoned())?;
+        if !pokemons.iter().any(|p| p.number == number) {
+            return Err(StorageError::ConstraintViolation(
+                "the Pokemon does not exist".to_owned(),
+            ));
+        }
+        if let Some(entry) = learnset
+            .iter()
+            .find(|entry| !moves.iter().any(|m| m.id == entry.move_id))
+        {
+            return Err(StorageError::ConstraintViolation(format!(
+                "move {} does not exist",
+                u16::from(entry.move_id.clone())
+            )));
+        }
+
+        learnset.sort_by(|a, b| (&a.method, &a.move_id).cmp(&(&b.method, &b.move_id)));
+        learnsets.insert(number, learnset.clone());
+        Ok(learnset)
+    }
+
+    fn fetch_learnset(&self, number: PokemonNumber) -> Result<Vec<LearnsetEntry>, StorageError> {
+        if self.error {
+            return Err(injected_error());
+        }
+        match self.learnsets.lock() {
+            Ok(learnsets) => Ok(learnsets.get(&number).cloned().unwrap_or_default()),
+            Err(_) => Err(poisoned()),
+        }
+    }
+
+    fn insert_ability(&self, ability: Ability) -> Result<Ability, InsertAbilityError> {
+        if self.error {
+            return Err(InsertAbilityError::Storage(injected_error()));
+        }
+        let mut abilities = match self.abilities.lock() {
+            Ok(lock) => lock,
+            Err(_) => return Err(InsertAbilityError::Storage(poisoned())),
+        };
+        if abilities.iter().any(|a| a.name == ability.name) {
+            return Err(InsertAbilityError::Conflict);
+        }
+        abilities.push(ability.clone());
+        abilities.sort_by(|a, b| a.name.as_str().cmp(b.name.as_str()));
+        Ok(ability)
+    }
+
+    fn fetch_abilities(&self) -> Result<Vec<Ability>, StorageError> {
+        if self.error {
+            return Err(injected_error());
+        }
+        match self.abilities.lock() {
+            Ok(abilities) => Ok(abilities.clone()),
+            Err(_) => Err(poisoned()),
+        }
+    }
+
+    fn replace_abilities(
+        &self,
+        number: PokemonNumber,
+        new_abilities: PokemonAbilities,
+    ) -> Result<PokemonAbilities, StorageError> {
+        if self.error {
+            return Err(injected_error());
+        }
+        let pokemons = self.pokemons.lock().map_err(|_| poisoned())?;
+        let abilities = self.abilities.lock().map_err(|_| poisoned())?;
+        let mut pokemon_abilities = self.pokemon_abilities.lock().map_err(|_| poisoned())?;
+        if !pokemons.iter().any(|p| p.number == number) {
+            return Err(StorageError::ConstraintViolation(
+                "the Pokemon does not exist".to_owned(),
+            ));
+        }
+        let names = new_abilities.regular().iter().chain(new_abilities.hidden());
+        for name in names {
+            if !abilities.iter().any(|a| &a.name == name) {
+                return Err(StorageError::ConstraintViolation(format!(
+                    "ability {} does not exist",
+                    name.as_str()
+                )));
+            }
+        }
+
+        pokemon_abilities.insert(number, new_abilities.clone());
+        Ok(new_abilities)
+    }
+
+    fn fetch_pokemon_abilities(
+        &self,
+        number: PokemonNumber,
+    ) -> Result<PokemonAbilities, StorageError> {
+        if self.error {
+            return Err(injected_error());
+        }
+        match self.pokemon_abilities.lock() {
+            Ok(abilities) => Ok(abilities.get(&number).cloned().unwrap_or_default()),
+            Err(_) => Err(poisoned()),
+        }
+    }
+
+    fn fetch_ability_holders(
+        &self,
+        ability: PokemonName,
+    ) -> Result<Vec<(PokemonNumber, bool)>, StorageError> {
+        if self.error {
+            return Err(injected_error());
+        }
+        let pokemon_abilities = self.pokemon_abilities.lock().map_err(|_| poisoned())?;
+
+        let mut holders = pokemon_abilities
+            .iter()
+            .filter_map(|(number, abilities)| {
+                if abilities.hidden() == Some(&ability) {
+                    Some((number.clone(), true))
+                } else if abilities.regular().contains(&ability) {
+                    Some((number.clone(), false))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<(PokemonNumber, bool)>>();
+        holders.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(holders)
+    }
+
+    fn replace_regional_dex(
+        &self,
+        name: DexName,
+        mut entries: Vec<DexEntry>,
+    ) -> Result<Vec<DexEntry>, StorageError> {
+        if self.error {
+            return Err(injected_error());
+        }
+        let pokemons = self.pokemons.lock().map_err(|_| poisoned())?;
+        let mut dexes = self.dexes.lock().map_err(|_| poisoned())?;
+        if let Some(entry) = entries
+            .iter()
+            .find(|entry| !pokemons.iter().any(|p| p.number == entry.number))
+        {
+            return Err(StorageError::ConstraintViolation(format!(
+                "Pokemon {} does not exist",
+                u16::from(entry.number.clone())
+            )));
+        }
+        for (position, entry) in entries.iter().enumerate() {
+            let twice = entries[..position]
+                .iter()
+                .any(|other| other.index == entry.index || other.number == entry.number);
+            if twice {
+                return Err(StorageError::ConstraintViolation(format!(
+                    "index {} or its Pokemon is listed twice",
+                    u16::from(entry.index)
+                )));
+            }
+        }
+
+        entries.sort_by_key(|entry| entry.index);
+        match entries.is_empty() {
+            true => dexes.remove(&name),
+            false => dexes.insert(name, entries.clone()),
+        };
+        Ok(entries)
+    }
+
+    fn fetch_regional_dex(&self, name: DexName) -> Result<Vec<DexEntry>, StorageError> {
+        if self.error {
+            return Err(injected_error());
+        }
+        match self.dexes.lock() {
+            Ok(dexes) => Ok(dexes.get(&name).cloned().unwrap_or_default()),
+            Err(_) => Err(poisoned()),
+        }
+    }
+
+    fn insert_trainer(&self, trainer: Trainer) -> Result<Trainer, InsertTrainerError> {
+        if self.error {
+            return Err(InsertTrainerError::Storage(injected_error()));
+        }
+        let mut trainers = match self.trainers.lock() {
+            Ok(lock) => lock,
+            Err(_) => return Err(InsertTrainerError::Storage(poisoned())),
+        };
+        if trainers.iter().any(|t| t.id == trainer.id) {
+            return Err(InsertTrainerError::Conflict);
+        }
+        trainers.push(trainer.clone());
+        Ok(trainer)
+    }
+
+    fn fetch_trainer(&self, id: TrainerId) -> Result<Trainer, FetchTrainerError> {
+        if self.error {
+            return Err(FetchTrainerError::Storage(injected_error()));
+        }
+        let trainers = match self.trainers.lock() {
+            Ok(lock) => lock,
+            Err(_) => return Err(FetchTrainerError::Storage(poisoned())),
+        };
+        match trainers.iter().find(|t| t.id == id) {
+            Some(trainer) => Ok(trainer.clone()),
+            None => Err(FetchTrainerError::NotFound),
+        }
+    }
+
+    fn replace_dex_status(
+        &self,
+        trainer: TrainerId,
+        number: PokemonNumber,
+        status: DexStatus,
+    ) -> Result<DexStatus, StorageError> {
+        if self.error {
+            return Err(injected_error());
+        }
+        let pokemons = self.pokemons.lock().map_err(|_| poisoned())?;
+        let trainers = self.trainers.lock().map_err(|_| poisoned())?;
+        let mut trainer_dexes = self.trainer_dexes.lock().map_err(|_| poisoned())?;
+        if !trainers.iter().any(|t| t.id == trainer) {
+            return Err(StorageError::ConstraintViolation(
+                "the trainer does not exist".to_owned(),
+            ));
+        }
+        if !pokemons.iter().any(|p| p.number == number) {
+            return Err(StorageError::ConstraintViolation(
+                "the Pokemon does not exist".to_owned(),
+            ));
+        }
+
+        let statuses = trainer_dexes.entry(trainer).or_default();
+        match status.is_empty() {
+            true => statuses.remove(&number),
+            false => statuses.insert(number, status),
+        };
+        Ok(status)
+    }
+
+    fn fetch_trainer_dex(
+        &self,
+        trainer: TrainerId,
+    ) -> Result<Vec<(PokemonNumber, DexStatus)>, StorageError> {
+        if self.error {
+            return Err(injected_error());
+        }
+        let trainer_dexes = self.trainer_dexes.lock().map_err(|_| poisoned())?;
+        Ok(trainer_dexes
+            .get(&trainer)
+            .into_iter()
+            .flatten()
+            .map(|(number, status)| (number.clone(), *status))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    fn repository() -> Arc<dyn Repository> {
+        Arc::new(InMemoryRepository::new())
+    }
+
+    repository_conformance!(repository);
+    learnset_conformance!(repository);
+    ability_conformance!(repository);
+    regional_dex_conformance!(repository);
+    trainer_conformance!(repository);
 }
@@ -1,4 +1,142 @@
+#[cfg(test)]
+#[macro_use]
+mod conformance;
 pub mod pokemon;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_migrations;
+#[cfg(feature = "sqlite")]
 pub mod sqlite_pokemon;
+#[cfg(feature = "airtable")]
 pub mod airtable_pokemon;
-pub mod inmemory_pokemon;
\ No newline at end of file
+pub mod inmemory_pokemon;
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(feature = "airtable")]
+use airtable_pokemon::AirtableRepository;
+use inmemory_pokemon::InMemoryRepository;
+use pokemon::{Repository, StorageError};
+#[cfg(feature = "sqlite")]
+use sqlite_pokemon::SqliteRepository;
+
+/// Backends compiled in this build, each behind the cargo feature of the same name.
+pub const BACKENDS: &[&str] = &[
+    "memory",
+    #[cfg(feature = "sqlite")]
+    "sqlite",
+    #[cfg(feature = "airtable")]
+    "airtable",
+];
+
+#[derive(Debug)]
+pub enum OpenError {
+    /// The spec doesn't name a known backend, or misses its settings.
+    InvalidSpec(String),
+    /// The backend exists but its cargo feature was left out of this build.
+    Disabled(&'static str),
+    Storage(StorageError),
+}
+
+impl fmt::Display for OpenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OpenError::InvalidSpec(spec) => write!(
+                f,
+                "invalid backend {spec:?}, expected memory, sqlite:PATH or airtable:API_KEY,WORKSPACE_ID"
+            ),
+            OpenError::Disabled(backend) => write!(
+                f,
+                "the {backend} backend is not available in this build, \
+                rebuild with `--features {backend}` to enable it"
+            ),
+            OpenError::Storage(e) => write!(f, "error while opening the backend: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for OpenError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            OpenError::Storage(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Opens the repository described by `spec`, one of `memory`, `sqlite:PATH`
+/// or `airtable:API_KEY,WORKSPACE_ID`.
+pub fn open(spec: &str, timeout: Duration) -> Result<Arc<dyn Repository>, OpenError> {
+    let invalid = || OpenError::InvalidSpec(spec.to_owned());
+    match spec.split_once(':') {
+        None if spec == "memory" => Ok(Arc::new(InMemoryRepository::new())),
+        Some(("sqlite", path)) if !path.is_empty() => open_sqlite(path, timeout),
+        Some(("airtable", settings)) => match settings.split_once(',') {
+            Some((api_key, workspace_id)) if !api_key.is_empty() && !workspace_id.is_empty() => {
+                open_airtable(api_key, workspace_id, timeout)
+            }
+            _ => Err(invalid()),
+        },
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(feature = "sqlite")]
+pub fn open_sqlite(path: &str, timeout: Duration) -> Result<Arc<dyn Repository>, OpenError> {
+    match SqliteRepository::try_new(path, timeout) {
+        Ok(repo) => Ok(Arc::new(repo)),
+        Err(e) => Err(OpenError::Storage(e)),
+    }
+}
+
+#[cfg(not(feature = "sqlite"))]
+pub fn open_sqlite(_path: &str, _timeout: Duration) -> Result<Arc<dyn Repository>, OpenError> {
+    Err(OpenError::Disabled("sqlite"))
+}
+
+#[cfg(feature = "airtable")]
+pub fn open_airtable(
+    api_key: &str,
+    workspace_id: &str,
+    timeout: Duration,
+) -> Result<Arc<dyn Repository>, OpenError> {
+    match AirtableRepository::try_new(api_key, workspace_id, timeout) {
+        Ok(repo) => Ok(Arc::new(repo)),
+        Err(e) => Err(OpenError::Storage(e)),
+    }
+}
+
+#[cfg(not(feature = "airtable"))]
+pub fn open_airtable(
+    _api_key: &str,
+    _workspace_id: &str,
+    _timeout: Duration,
+) -> Result<Arc<dyn Repository>, OpenError> {
+    Err(OpenError::Disabled("airtable"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_reject_unknown_backends() {
+        for spec in ["postgres:db", "sqlite:", "airtable:key", "airtable:,base"] {
+            match open(spec, Duration::from_secs(1)) {
+                Err(OpenError::InvalidSpec(_)) => {}
+                _ => unreachable!("{spec}"),
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_open_known_backends() {
+        assert!(open("memory", Duration::from_secs(1)).is_ok());
+        match open("sqlite::memory:", Duration::from_secs(1)) {
+            Ok(_) => assert!(BACKENDS.contains(&"sqlite")),
+            Err(OpenError::Disabled("sqlite")) => assert!(!BACKENDS.contains(&"sqlite")),
+            Err(e) => unreachable!("{e}"),
+        }
+    }
+}
@@ -1,36 +1,664 @@
-use crate::domain::entities::{Pokemon, PokemonName, PokemonNumber, PokemonTypes};
+use std::error::Error;
+use std::fmt;
+
+use crate::domain::entities::{
+    Ability, DexEntry, DexName, DexStatus, Evolution, LearnsetEntry, Move, MoveId, Pokemon,
+    PokemonAbilities, PokemonKey, PokemonName, PokemonNumber, PokemonStats, PokemonType,
+    PokemonTypes, Trainer, TrainerId,
+};
+
+/// Why a storage backend failed, independently of the operation that was running.
+#[derive(Debug)]
+pub enum StorageError {
+    /// The backend could not be reached, or the connection to it is unusable.
+    ConnectionLost(String),
+    /// The backend refused a write that breaks one of its constraints.
+    ConstraintViolation(String),
+    /// A stored record can't be turned back into a valid Pokemon.
+    CorruptRow(String),
+    /// A remote backend answered with an error status.
+    HttpStatus { status: u16, body: String },
+    Timeout,
+    /// A remote backend answered with a body that can't be read.
+    Deserialization(String),
+    /// Any other failure reported by the backend itself.
+    Backend(String),
+    /// The backend does not store this kind of data at all.
+    Unsupported(&'static str),
+}
+
+impl StorageError {
+    /// Stable, machine-readable identifier of the failure.
+    pub fn code(&self) -> &'static str {
+        match self {
+            StorageError::ConnectionLost(_) => "connection_lost",
+            StorageError::ConstraintViolation(_) => "constraint_violation",
+            StorageError::CorruptRow(_) => "corrupt_row",
+            StorageError::HttpStatus { .. } => "upstream_http_status",
+            StorageError::Timeout => "timeout",
+            StorageError::Deserialization(_) => "deserialization",
+            StorageError::Backend(_) => "backend",
+            StorageError::Unsupported(_) => "unsupported",
+        }
+    }
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::ConnectionLost(msg) => write!(f, "connection lost: {msg}"),
+            StorageError::ConstraintViolation(msg) => write!(f, "constraint violation: {msg}"),
+            StorageError::CorruptRow(msg) => write!(f, "corrupt row: {msg}"),
+            StorageError::HttpStatus { status, body } => {
+                write!(f, "remote answered with status {status}: {body}")
+            }
+            StorageError::Timeout => write!(f, "timed out"),
+            StorageError::Deserialization(msg) => write!(f, "deserialization failed: {msg}"),
+            StorageError::Backend(msg) => write!(f, "backend error: {msg}"),
+            StorageError::Unsupported(what) => write!(f, "{what} are not supported by this backend"),
+        }
+    }
+}
+
+impl Error for StorageError {}
 
 #[derive(Debug)]
 pub enum InsertError {
     Conflict,
-    Unknown,
+    Storage(StorageError),
+}
+
+impl fmt::Display for InsertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InsertError::Conflict => write!(f, "the Pokemon already exists"),
+            InsertError::Storage(e) => write!(f, "error while inserting: {e}"),
+        }
+    }
+}
+
+impl Error for InsertError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InsertError::Storage(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SortField {
+    #[default]
+    Number,
+    Name,
+}
+
+impl TryFrom<String> for SortField {
+    type Error = ();
+
+    fn try_from(field: String) -> Result<Self, Self::Error> {
+        match field.to_lowercase().as_str() {
+            "number" => Ok(SortField::Number),
+            "name" => Ok(SortField::Name),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SortDirection {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+impl TryFrom<String> for SortDirection {
+    type Error = ();
+
+    fn try_from(direction: String) -> Result<Self, Self::Error> {
+        match direction.to_lowercase().as_str() {
+            "asc" => Ok(SortDirection::Ascending),
+            "desc" => Ok(SortDirection::Descending),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Narrows down, orders and pages the result of `Repository::fetch_all`.
+/// The default query returns every Pokemon sorted by number. Every form of a
+/// number is returned, the base form first then the others by form.
+#[derive(Clone, Debug, Default)]
+pub struct FetchAllQuery {
+    pub tipe: Option<PokemonType>,
+    /// Case-insensitive substring of the name.
+    pub name: Option<String>,
+    pub min_number: Option<PokemonNumber>,
+    pub max_number: Option<PokemonNumber>,
+    pub sort: SortField,
+    pub direction: SortDirection,
+    pub limit: Option<usize>,
+    pub offset: usize,
+}
+
+impl FetchAllQuery {
+    /// Every form of the national number.
+    pub fn by_number(number: PokemonNumber) -> Self {
+        Self {
+            min_number: Some(number.clone()),
+            max_number: Some(number),
+            ..Self::default()
+        }
+    }
+
+    pub fn matches(&self, pokemon: &Pokemon) -> bool {
+        let name = pokemon.name.as_str().to_lowercase();
+        self.tipe
+            .is_none_or(|tipe| pokemon.types.as_slice().contains(&tipe))
+            && self
+                .name
+                .as_ref()
+                .is_none_or(|part| name.contains(&part.to_lowercase()))
+            && self.min_number.as_ref().is_none_or(|min| pokemon.number >= *min)
+            && self.max_number.as_ref().is_none_or(|max| pokemon.number <= *max)
+    }
 }
 
 #[derive(Debug)]
 pub enum FetchAllError {
-    Unknown,
+    /// The backend failed after returning only part of the result.
+    Interrupted(StorageError),
+    Storage(StorageError),
+}
+
+impl fmt::Display for FetchAllError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchAllError::Interrupted(e) => write!(f, "fetching all was interrupted: {e}"),
+            FetchAllError::Storage(e) => write!(f, "error while fetching all: {e}"),
+        }
+    }
+}
+
+impl Error for FetchAllError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FetchAllError::Interrupted(e) | FetchAllError::Storage(e) => Some(e),
+        }
+    }
 }
 
 #[derive(Debug)]
 pub enum FetchOneError {
-    Unknown,
+    Storage(StorageError),
     NotFound,
 }
 
+impl fmt::Display for FetchOneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchOneError::Storage(e) => write!(f, "error while fetching: {e}"),
+            FetchOneError::NotFound => write!(f, "the Pokemon does not exist"),
+        }
+    }
+}
+
+impl Error for FetchOneError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FetchOneError::Storage(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum DeleteError {
-    Unknown,
+    Storage(StorageError),
+    NotFound,
+}
+
+impl fmt::Display for DeleteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeleteError::Storage(e) => write!(f, "error while deleting: {e}"),
+            DeleteError::NotFound => write!(f, "the Pokemon does not exist"),
+        }
+    }
+}
+
+impl Error for DeleteError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DeleteError::Storage(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum UpdateError {
+    Storage(StorageError),
+    NotFound,
+}
+
+impl fmt::Display for UpdateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UpdateError::Storage(e) => write!(f, "error while updating: {e}"),
+            UpdateError::NotFound => write!(f, "the Pokemon does not exist"),
+        }
+    }
+}
+
+impl Error for UpdateError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            UpdateError::Storage(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum UpsertAllError {
+    /// The backend failed after writing only the first `written` Pokemons.
+    Interrupted { written: usize, source: StorageError },
+    Storage(StorageError),
+}
+
+impl fmt::Display for UpsertAllError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UpsertAllError::Interrupted { written, source } => {
+                write!(f, "writing was interrupted after {written} Pokemons: {source}")
+            }
+            UpsertAllError::Storage(e) => write!(f, "error while writing: {e}"),
+        }
+    }
+}
+
+impl Error for UpsertAllError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            UpsertAllError::Interrupted { source: e, .. } | UpsertAllError::Storage(e) => Some(e),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InsertEvolutionError {
+    /// The target Pokemon already evolves from another one.
+    Conflict,
+    Storage(StorageError),
+}
+
+impl fmt::Display for InsertEvolutionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InsertEvolutionError::Conflict => write!(f, "the Pokemon already evolves from another one"),
+            InsertEvolutionError::Storage(e) => write!(f, "error while inserting the evolution: {e}"),
+        }
+    }
+}
+
+impl Error for InsertEvolutionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InsertEvolutionError::Storage(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InsertMoveError {
+    /// A move with the same id already exists.
+    Conflict,
+    Storage(StorageError),
+}
+
+impl fmt::Display for InsertMoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InsertMoveError::Conflict => write!(f, "a move with the same id already exists"),
+            InsertMoveError::Storage(e) => write!(f, "error while inserting the move: {e}"),
+        }
+    }
+}
+
+impl Error for InsertMoveError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InsertMoveError::Storage(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum FetchMoveError {
     NotFound,
+    Storage(StorageError),
+}
+
+impl fmt::Display for FetchMoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchMoveError::NotFound => write!(f, "the move does not exist"),
+            FetchMoveError::Storage(e) => write!(f, "error while fetching the move: {e}"),
+        }
+    }
+}
+
+impl Error for FetchMoveError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FetchMoveError::Storage(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum UpdateMoveError {
+    NotFound,
+    Storage(StorageError),
+}
+
+impl fmt::Display for UpdateMoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UpdateMoveError::NotFound => write!(f, "the move does not exist"),
+            UpdateMoveError::Storage(e) => write!(f, "error while updating the move: {e}"),
+        }
+    }
+}
+
+impl Error for UpdateMoveError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            UpdateMoveError::Storage(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum DeleteMoveError {
+    NotFound,
+    Storage(StorageError),
+}
+
+impl fmt::Display for DeleteMoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeleteMoveError::NotFound => write!(f, "the move does not exist"),
+            DeleteMoveError::Storage(e) => write!(f, "error while deleting the move: {e}"),
+        }
+    }
+}
+
+impl Error for DeleteMoveError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DeleteMoveError::Storage(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InsertAbilityError {
+    /// An ability with the same name already exists.
+    Conflict,
+    Storage(StorageError),
+}
+
+impl fmt::Display for InsertAbilityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InsertAbilityError::Conflict => write!(f, "an ability with the same name already exists"),
+            InsertAbilityError::Storage(e) => write!(f, "error while inserting the ability: {e}"),
+        }
+    }
+}
+
+impl Error for InsertAbilityError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InsertAbilityError::Storage(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InsertTrainerError {
+    /// A trainer with the same id already exists.
+    Conflict,
+    Storage(StorageError),
+}
+
+impl fmt::Display for InsertTrainerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InsertTrainerError::Conflict => write!(f, "a trainer with the same id already exists"),
+            InsertTrainerError::Storage(e) => write!(f, "error while inserting the trainer: {e}"),
+        }
+    }
+}
+
+impl Error for InsertTrainerError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InsertTrainerError::Storage(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum FetchTrainerError {
+    NotFound,
+    Storage(StorageError),
+}
+
+impl fmt::Display for FetchTrainerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchTrainerError::NotFound => write!(f, "the trainer does not exist"),
+            FetchTrainerError::Storage(e) => write!(f, "error while fetching the trainer: {e}"),
+        }
+    }
+}
+
+impl Error for FetchTrainerError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FetchTrainerError::Storage(e) => Some(e),
+            _ => None,
+        }
+    }
 }
 
 pub trait Repository: Send + Sync {
     fn insert(
         &self,
-        number: PokemonNumber,
+        key: PokemonKey,
         name: PokemonName,
         types: PokemonTypes,
+        stats: PokemonStats,
     ) -> Result<Pokemon, InsertError>;
-    fn fetch_all(&self) -> Result<Vec<Pokemon>, FetchAllError>;
-    fn fetch_one(&self, number: PokemonNumber) -> Result<Pokemon, FetchOneError>;
-    fn delete(&self, number: PokemonNumber) -> Result<(), DeleteError>;
+    fn fetch_all(&self, query: &FetchAllQuery) -> Result<Vec<Pokemon>, FetchAllError>;
+    fn fetch_one(&self, key: PokemonKey) -> Result<Pokemon, FetchOneError>;
+    fn update(
+        &self,
+        key: PokemonKey,
+        name: PokemonName,
+        types: PokemonTypes,
+        stats: PokemonStats,
+    ) -> Result<Pokemon, UpdateError>;
+    /// Deleting the base form deletes every form of the number, along with
+    /// its evolutions, learnset and abilities. Deleting another form only
+    /// deletes that form.
+    fn delete(&self, key: PokemonKey) -> Result<(), DeleteError>;
+
+    /// Inserts the Pokemons, replacing the ones that already exist.
+    ///
+    /// The default implementation writes them one at a time. Backends that
+    /// support transactions should override it to write all of them or none.
+    fn upsert_all(&self, pokemons: Vec<Pokemon>) -> Result<(), UpsertAllError> {
+        for (written, pokemon) in pokemons.into_iter().enumerate() {
+            let interrupted = |source| match written {
+                0 => UpsertAllError::Storage(source),
+                written => UpsertAllError::Interrupted { written, source },
+            };
+            let key = pokemon.key();
+            let Pokemon { name, types, stats, .. } = pokemon;
+            match self.update(key.clone(), name.clone(), types.clone(), stats.clone()) {
+                Ok(_) => {}
+                Err(UpdateError::NotFound) => match self.insert(key, name, types, stats) {
+                    Ok(_) => {}
+                    Err(InsertError::Conflict) => {
+                        return Err(interrupted(StorageError::ConstraintViolation(
+                            "the Pokemon was inserted concurrently".to_owned(),
+                        )))
+                    }
+                    Err(InsertError::Storage(e)) => return Err(interrupted(e)),
+                },
+                Err(UpdateError::Storage(e)) => return Err(interrupted(e)),
+            }
+        }
+        Ok(())
+    }
+
+    /// Stores an evolution between two existing Pokemons. A Pokemon evolves
+    /// from at most one other, so `evolution.to` must not have one yet.
+    fn insert_evolution(&self, _evolution: Evolution) -> Result<Evolution, InsertEvolutionError> {
+        Err(InsertEvolutionError::Storage(StorageError::Unsupported("evolutions")))
+    }
+
+    /// Every stored evolution, sorted by `from` then `to`.
+    fn fetch_evolutions(&self) -> Result<Vec<Evolution>, StorageError> {
+        Err(StorageError::Unsupported("evolutions"))
+    }
+
+    fn insert_move(&self, _move: Move) -> Result<Move, InsertMoveError> {
+        Err(InsertMoveError::Storage(StorageError::Unsupported("moves")))
+    }
+
+    /// Every stored move, sorted by id.
+    fn fetch_moves(&self) -> Result<Vec<Move>, StorageError> {
+        Err(StorageError::Unsupported("moves"))
+    }
+
+    fn fetch_move(&self, _id: MoveId) -> Result<Move, FetchMoveError> {
+        Err(FetchMoveError::Storage(StorageError::Unsupported("moves")))
+    }
+
+    fn update_move(&self, _move: Move) -> Result<Move, UpdateMoveError> {
+        Err(UpdateMoveError::Storage(StorageError::Unsupported("moves")))
+    }
+
+    /// Also removes the move from every learnset.
+    fn delete_move(&self, _id: MoveId) -> Result<(), DeleteMoveError> {
+        Err(DeleteMoveError::Storage(StorageError::Unsupported("moves")))
+    }
+
+    /// Replaces the whole learnset of an existing Pokemon. Every entry must
+    /// refer to an existing move.
+    fn replace_learnset(
+        &self,
+        _number: PokemonNumber,
+        _learnset: Vec<LearnsetEntry>,
+    ) -> Result<Vec<LearnsetEntry>, StorageError> {
+        Err(StorageError::Unsupported("moves"))
+    }
+
+    /// The learnset of a Pokemon, sorted by method then move id. Unknown
+    /// Pokemons have an empty learnset.
+    fn fetch_learnset(&self, _number: PokemonNumber) -> Result<Vec<LearnsetEntry>, StorageError> {
+        Err(StorageError::Unsupported("moves"))
+    }
+
+    fn insert_ability(&self, _ability: Ability) -> Result<Ability, InsertAbilityError> {
+        Err(InsertAbilityError::Storage(StorageError::Unsupported("abilities")))
+    }
+
+    /// Every stored ability, sorted by name.
+    fn fetch_abilities(&self) -> Result<Vec<Ability>, StorageError> {
+        Err(StorageError::Unsupported("abilities"))
+    }
+
+    /// Replaces the abilities of an existing Pokemon. Every ability must
+    /// already be stored, under the exact same name.
+    fn replace_abilities(
+        &self,
+        _number: PokemonNumber,
+        _abilities: PokemonAbilities,
+    ) -> Result<PokemonAbilities, StorageError> {
+        Err(StorageError::Unsupported("abilities"))
+    }
+
+    /// Unknown Pokemons have no abilities.
+    fn fetch_pokemon_abilities(
+        &self,
+        _number: PokemonNumber,
+    ) -> Result<PokemonAbilities, StorageError> {
+        Err(StorageError::Unsupported("abilities"))
+    }
+
+    /// Every Pokemon having the ability, sorted by number, with whether it
+    /// is their hidden ability.
+    fn fetch_ability_holders(
+        &self,
+        _ability: PokemonName,
+    ) -> Result<Vec<(PokemonNumber, bool)>, StorageError> {
+        Err(StorageError::Unsupported("abilities"))
+    }
+
+    /// Replaces the whole regional dex, an empty one deleting it. Every
+    /// entry must refer to an existing Pokemon, and neither an index nor a
+    /// number may appear twice. Deleting a Pokemon leaves a gap in the dexes
+    /// listing it.
+    fn replace_regional_dex(
+        &self,
+        _name: DexName,
+        _entries: Vec<DexEntry>,
+    ) -> Result<Vec<DexEntry>, StorageError> {
+        Err(StorageError::Unsupported("regional dexes"))
+    }
+
+    /// The entries of a regional dex, sorted by index. Unknown dexes are empty.
+    fn fetch_regional_dex(&self, _name: DexName) -> Result<Vec<DexEntry>, StorageError> {
+        Err(StorageError::Unsupported("regional dexes"))
+    }
+
+    fn insert_trainer(&self, _trainer: Trainer) -> Result<Trainer, InsertTrainerError> {
+        Err(InsertTrainerError::Storage(StorageError::Unsupported("trainers")))
+    }
+
+    fn fetch_trainer(&self, _id: TrainerId) -> Result<Trainer, FetchTrainerError> {
+        Err(FetchTrainerError::Storage(StorageError::Unsupported("trainers")))
+    }
+
+    /// Sets what an existing trainer recorded about an existing Pokemon, an
+    /// empty status forgetting it. Deleting a Pokemon forgets it for everyone.
+    fn replace_dex_status(
+        &self,
+        _trainer: TrainerId,
+        _number: PokemonNumber,
+        _status: DexStatus,
+    ) -> Result<DexStatus, StorageError> {
+        Err(StorageError::Unsupported("trainers"))
+    }
+
+    /// Every Pokemon the trainer recorded, sorted by number. Unknown trainers
+    /// have recorded none.
+    fn fetch_trainer_dex(
+        &self,
+        _trainer: TrainerId,
+    ) -> Result<Vec<(PokemonNumber, DexStatus)>, StorageError> {
+        Err(StorageError::Unsupported("trainers"))
+    }
 }
@@ -0,0 +1,321 @@
+use rusqlite::Connection;
+
+use super::pokemon::StorageError;
+use super::sqlite_pokemon::storage_error;
+
+/// Forward migrations, applied in order. The schema version stored in
+/// `user_version` is the number of migrations already applied, so a new
+/// migration must only ever be appended to this list.
+const MIGRATIONS: &[&str] = &[
+    "create table if not exists pokemons (
+        number integer primary key not null,
+        name text not null
+    );
+    create table if not exists types (
+        pokemon_number integer not null references pokemons(number) on delete cascade,
+        name text not null
+    );",
+    "create table if not exists stats (
+        pokemon_number integer primary key not null references pokemons(number) on delete cascade,
+        hp integer not null,
+        attack integer not null,
+        defense integer not null,
+        special_attack integer not null,
+        special_defense integer not null,
+        speed integer not null
+    );",
+    "create table if not exists evolutions (
+        to_number integer primary key not null references pokemons(number) on delete cascade,
+        from_number integer not null references pokemons(number) on delete cascade,
+        trigger text not null
+    );
+    create table if not exists evolution_conditions (
+        to_number integer not null references evolutions(to_number) on delete cascade,
+        condition text not null
+    );",
+    "create table if not exists moves (
+        id integer primary key not null,
+        name text not null,
+        type text not null,
+        category text not null,
+        power integer,
+        accuracy integer,
+        pp integer not null
+    );
+    create table if not exists learnsets (
+        pokemon_number integer not null references pokemons(number) on delete cascade,
+        move_id integer not null references moves(id) on delete cascade,
+        method text not null,
+        primary key (pokemon_number, move_id, method)
+    );",
+    "create table if not exists abilities (
+        name text primary key not null,
+        description text not null
+    );
+    create table if not exists pokemon_abilities (
+        pokemon_number integer not null references pokemons(number) on delete cascade,
+        ability text not null references abilities(name),
+        hidden integer not null,
+        primary key (pokemon_number, ability)
+    );",
+    // Several forms share a national number, so the tables keyed by number
+    // now point to `species`, and the ones describing a Pokemon to its form.
+    "create table species (
+        number integer primary key not null
+    );
+    insert into species select number from pokemons;
+    create table new_pokemons (
+        number integer not null references species(number) on delete cascade,
+        form text not null default '',
+        name text not null,
+        primary key (number, form)
+    );
+    insert into new_pokemons select number, '', name from pokemons;
+    create table new_types (
+        pokemon_number integer not null,
+        form text not null default '',
+        name text not null,
+        foreign key (pokemon_number, form) references pokemons(number, form) on delete cascade
+    );
+    insert into new_types select pokemon_number, '', name from types order by rowid;
+    create table new_stats (
+        pokemon_number integer not null,
+        form text not null default '',
+        hp integer not null,
+        attack integer not null,
+        defense integer not null,
+        special_attack integer not null,
+        special_defense integer not null,
+        speed integer not null,
+        primary key (pokemon_number, form),
+        foreign key (pokemon_number, form) references pokemons(number, form) on delete cascade
+    );
+    insert into new_stats select pokemon_number, '', hp, attack, defense, special_attack,
+        special_defense, speed from stats;
+    create table new_evolutions (
+        to_number integer primary key not null references species(number) on delete cascade,
+        from_number integer not null references species(number) on delete cascade,
+        trigger text not null
+    );
+    insert into new_evolutions select to_number, from_number, trigger from evolutions;
+    create table new_learnsets (
+        pokemon_number integer not null references species(number) on delete cascade,
+        move_id integer not null references moves(id) on delete cascade,
+        method text not null,
+        primary key (pokemon_number, move_id, method)
+    );
+    insert into new_learnsets select pokemon_number, move_id, method from learnsets;
+    create table new_pokemon_abilities (
+        pokemon_number integer not null references species(number) on delete cascade,
+        ability text not null references abilities(name),
+        hidden integer not null,
+        primary key (pokemon_number, ability)
+    );
+    insert into new_pokemon_abilities
+        select pokemon_number, ability, hidden from pokemon_abilities order by rowid;
+    drop table types;
+    drop table stats;
+    drop table evolutions;
+    drop table learnsets;
+    drop table pokemon_abilities;
+    drop table pokemons;
+    alter table new_pokemons rename to pokemons;
+    alter table new_types rename to types;
+    alter table new_stats rename to stats;
+    alter table new_evolutions rename to evolutions;
+    alter table new_learnsets rename to learnsets;
+    alter table new_pokemon_abilities rename to pokemon_abilities;",
+    "create table if not exists regional_dexes (
+        dex text not null,
+        dex_index integer not null,
+        pokemon_number integer not null references species(number) on delete cascade,
+        primary key (dex, dex_index),
+        unique (dex, pokemon_number)
+    );",
+    "create table if not exists trainers (
+        id text primary key not null,
+        name text not null
+    );
+    create table if not exists trainer_dexes (
+        trainer_id text not null references trainers(id) on delete cascade,
+        pokemon_number integer not null references species(number) on delete cascade,
+        seen integer not null,
+        caught integer not null,
+        shiny integer not null,
+        primary key (trainer_id, pokemon_number)
+    );",
+];
+
+/// The schema version this build of the application knows about.
+pub fn latest_version() -> usize {
+    MIGRATIONS.len()
+}
+
+pub fn version(conn: &Connection) -> Result<usize, rusqlite::Error> {
+    conn.pragma_query_value(None, "user_version", |row| row.get::<usize, i64>(0))
+        .map(|version| version as usize)
+}
+
+/// Brings the schema up to the latest version. Each migration runs in its
+/// own transaction together with the version bump, so a failure leaves the
+/// database at the last version that was fully applied.
+///
+/// Foreign keys are not enforced while migrating, so that a table can be
+/// rebuilt without cascading to the rows referencing it; every migration is
+/// checked against them before being committed instead.
+pub fn run(conn: &mut Connection) -> Result<(), StorageError> {
+    let current = version(conn).map_err(storage_error)?;
+    if current > latest_version() {
+        return Err(StorageError::Backend(format!(
+            "schema version {current} is newer than the latest known version {}",
+            latest_version()
+        )));
+    }
+    if current == latest_version() {
+        return Ok(());
+    }
+
+    let foreign_keys = conn
+        .pragma_query_value(None, "foreign_keys", |row| row.get::<usize, bool>(0))
+        .map_err(storage_error)?;
+    conn.pragma_update(None, "foreign_keys", false)
+        .map_err(storage_error)?;
+    let applied = MIGRATIONS
+        .iter()
+        .enumerate()
+        .skip(current)
+        .try_for_each(|(index, migration)| apply(conn, migration, index + 1));
+    conn.pragma_update(None, "foreign_keys", foreign_keys)
+        .map_err(storage_error)?;
+
+    applied
+}
+
+fn apply(conn: &mut Connection, migration: &str, version: usize) -> Result<(), StorageError> {
+    let transaction = conn.transaction().map_err(storage_error)?;
+    transaction
+        .execute_batch(migration)
+        .map_err(storage_error)?;
+    let violations = transaction
+        .prepare("pragma foreign_key_check")
+        .and_then(|mut stmt| stmt.exists([]))
+        .map_err(storage_error)?;
+    if violations {
+        return Err(StorageError::ConstraintViolation(format!(
+            "migration {version} leaves rows referencing missing ones"
+        )));
+    }
+    transaction
+        .pragma_update(None, "user_version", version as i64)
+        .map_err(storage_error)?;
+    transaction.commit().map_err(storage_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_create_the_schema_on_an_empty_database() {
+        let mut conn = Connection::open_in_memory().unwrap();
+
+        run(&mut conn).unwrap();
+
+        assert_eq!(version(&conn).unwrap(), latest_version());
+        let tables = [
+            "pokemons",
+            "types",
+            "stats",
+            "evolutions",
+            "evolution_conditions",
+            "moves",
+            "learnsets",
+            "abilities",
+            "pokemon_abilities",
+            "species",
+            "regional_dexes",
+            "trainers",
+            "trainer_dexes",
+        ];
+        for table in tables {
+            let count: i64 = conn
+                .query_row(
+                    "select count(*) from sqlite_master where type = 'table' and name = ?",
+                    [table],
+                    |row| row.get(0),
+                )
+                .unwrap();
+            assert_eq!(count, 1, "missing table {table}");
+        }
+    }
+
+    #[test]
+    fn it_should_only_apply_pending_migrations() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(MIGRATIONS[0]).unwrap();
+        conn.pragma_update(None, "user_version", 1).unwrap();
+        conn.execute("insert into pokemons values (25, 'Pikachu')", [])
+            .unwrap();
+
+        run(&mut conn).unwrap();
+        run(&mut conn).unwrap();
+
+        assert_eq!(version(&conn).unwrap(), latest_version());
+        let count: i64 = conn
+            .query_row("select count(*) from pokemons", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn it_should_keep_pokemons_and_their_relations_when_adding_forms() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.pragma_update(None, "foreign_keys", true).unwrap();
+        for (index, migration) in MIGRATIONS.iter().enumerate().take(5) {
+            apply(&mut conn, migration, index + 1).unwrap();
+        }
+        conn.execute_batch(
+            "insert into pokemons values (25, 'Pikachu'), (26, 'Raichu');
+            insert into types values (25, 'Electric'), (26, 'Electric'), (26, 'Psychic');
+            insert into stats values (25, 35, 55, 40, 50, 50, 90);
+            insert into evolutions values (26, 25, 'stone:Thunder Stone');
+            insert into evolution_conditions values (26, 'daytime');",
+        )
+        .unwrap();
+
+        run(&mut conn).unwrap();
+
+        let count = |sql: &str| {
+            conn.query_row(sql, [], |row| row.get::<usize, i64>(0))
+                .unwrap()
+        };
+        assert_eq!(count("select count(*) from pokemons where form = ''"), 2);
+        assert_eq!(
+            count("select count(*) from types where pokemon_number = 26"),
+            2
+        );
+        assert_eq!(count("select count(*) from evolution_conditions"), 1);
+        assert!(conn
+            .pragma_query_value(None, "foreign_keys", |row| row.get::<usize, bool>(0))
+            .unwrap());
+        conn.execute("delete from species where number = 26", [])
+            .unwrap();
+        assert_eq!(count("select count(*) from types"), 1);
+        assert_eq!(count("select count(*) from evolutions"), 0);
+        assert_eq!(count("select count(*) from evolution_conditions"), 0);
+    }
+
+    #[test]
+    fn it_should_refuse_a_schema_newer_than_the_application() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.pragma_update(None, "user_version", latest_version() as i64 + 1)
+            .unwrap();
+
+        let res = run(&mut conn);
+
+        match res {
+            Err(StorageError::Backend(_)) => {}
+            _ => unreachable!(),
+        };
+    }
+}
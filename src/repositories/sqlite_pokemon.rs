@@ -1,215 +1,1072 @@
 use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
 
-use rusqlite::{params, params_from_iter, Connection, OpenFlags};
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection, ErrorCode, OpenFlags, Transaction};
 
-use crate::domain::entities::{Pokemon, PokemonName, PokemonNumber, PokemonTypes};
+use crate::domain::entities::{
+    Ability, DexEntry, DexIndex, DexName, DexStatus, Evolution, EvolutionTrigger, LearnMethod,
+    LearnsetEntry, Move, MoveAccuracy, MoveCategory, MoveId, MovePower, MovePp, Pokemon,
+    PokemonAbilities, PokemonForm, PokemonKey, PokemonName, PokemonNumber, PokemonStats,
+    PokemonType, PokemonTypes, Trainer, TrainerId,
+};
 
-use super::pokemon::{Repository, InsertError, FetchAllError, FetchOneError, DeleteError};
+use super::pokemon::{
+    DeleteError, DeleteMoveError, FetchAllError, FetchAllQuery, FetchMoveError, FetchOneError,
+    FetchTrainerError, InsertAbilityError, InsertError, InsertEvolutionError, InsertMoveError,
+    InsertTrainerError, Repository, SortDirection, SortField, StorageError, UpdateError,
+    UpdateMoveError, UpsertAllError,
+};
+use super::sqlite_migrations;
 
 pub struct SqliteRepository {
     conn: Mutex<Connection>,
 }
 
-impl SqliteRepository {
-    pub fn try_new(path: &str) -> Result<Self, ()> {
-        let conn = match Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE) {
-            Ok(conn) => conn,
-            Err(_) => return Err(()),
-        };
-        match conn.execute("pragma foreign_keys =1", []) {
-            Ok(_) => Ok(Self {
-                conn: Mutex::new(conn),
-            }),
-            Err(_) => Err(()),
+type PokemonRow = (u16, String, String, [u16; 6]);
+
+type EvolutionRow = (u16, u16, String);
+
+type MoveRow = (u16, String, String, String, Option<u16>, Option<u16>, u16);
+
+pub(super) fn storage_error(e: rusqlite::Error) -> StorageError {
+    match e {
+        rusqlite::Error::SqliteFailure(err, msg) => {
+            let msg = msg.unwrap_or_else(|| err.to_string());
+            match err.code {
+                ErrorCode::ConstraintViolation => StorageError::ConstraintViolation(msg),
+                ErrorCode::CannotOpen | ErrorCode::PermissionDenied | ErrorCode::SystemIoFailure => {
+                    StorageError::ConnectionLost(msg)
+                }
+                ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked => StorageError::Timeout,
+                _ => StorageError::Backend(msg),
+            }
         }
+        rusqlite::Error::FromSqlConversionFailure(_, _, _)
+        | rusqlite::Error::IntegralValueOutOfRange(_, _)
+        | rusqlite::Error::InvalidColumnType(_, _, _) => StorageError::CorruptRow(e.to_string()),
+        e => StorageError::Backend(e.to_string()),
+    }
+}
+
+fn poisoned() -> StorageError {
+    StorageError::ConnectionLost("sqlite connection lock is poisoned".to_owned())
+}
+
+// Extended result codes, not exported by the bundled bindings.
+const SQLITE_CONSTRAINT_PRIMARYKEY: i32 = 1555;
+const SQLITE_CONSTRAINT_UNIQUE: i32 = 2067;
+
+fn is_conflict(e: &rusqlite::Error) -> bool {
+    matches!(
+        e,
+        rusqlite::Error::SqliteFailure(err, _)
+            if err.extended_code == SQLITE_CONSTRAINT_PRIMARYKEY
+                || err.extended_code == SQLITE_CONSTRAINT_UNIQUE
+    )
+}
+
+impl SqliteRepository {
+    /// Writes wait up to `timeout` for a lock held by another connection to the same file.
+    pub fn try_new(path: &str, timeout: Duration) -> Result<Self, StorageError> {
+        let mut conn = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_WRITE | OpenFlags::SQLITE_OPEN_CREATE,
+        )
+        .map_err(storage_error)?;
+        conn.execute("pragma foreign_keys =1", [])
+            .map_err(storage_error)?;
+        conn.busy_timeout(timeout).map_err(storage_error)?;
+        sqlite_migrations::run(&mut conn)?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
     }
 
     fn fetch_pokemon_rows(
         lock: &MutexGuard<'_, Connection>,
-        number: Option<u16>,
-    ) -> Result<Vec<(u16, String)>, ()> {
-        let (query, params) = match number {
-            Some(number) => (
-                "select number, name from pokemons where number = ?",
-                vec![number],
-            ),
-            None => ("select number, name from pokemons", vec![]),
-        };
+        query: &FetchAllQuery,
+    ) -> Result<Vec<PokemonRow>, StorageError> {
+        let mut conditions = vec![];
+        let mut params: Vec<Value> = vec![];
+        if let Some(tipe) = query.tipe {
+            conditions.push(
+                "exists (select 1 from types t \
+                where t.pokemon_number = p.number and t.form = p.form and t.name = ?)",
+            );
+            params.push(Value::Text(String::from(tipe)));
+        }
+        if let Some(name) = &query.name {
+            conditions.push("instr(lower(p.name), lower(?)) > 0");
+            params.push(Value::Text(name.clone()));
+        }
+        if let Some(min) = &query.min_number {
+            conditions.push("p.number >= ?");
+            params.push(Value::Integer(u16::from(min.clone()).into()));
+        }
+        if let Some(max) = &query.max_number {
+            conditions.push("p.number <= ?");
+            params.push(Value::Integer(u16::from(max.clone()).into()));
+        }
 
-        let mut stmt = match lock.prepare(query) {
-            Ok(s) => s,
-            Err(_) => return Err(()),
+        let mut query_str = String::from(
+            "select p.number, p.form, p.name, s.hp, s.attack, s.defense, s.special_attack, \
+            s.special_defense, s.speed from pokemons p \
+            left join stats s on s.pokemon_number = p.number and s.form = p.form",
+        );
+        if !conditions.is_empty() {
+            query_str.push_str(&format!(" where {}", conditions.join(" and ")));
+        }
+        let column = match query.sort {
+            SortField::Number => "p.number",
+            SortField::Name => "p.name",
         };
-
-        let mut rows = match stmt.query(params_from_iter(params)) {
-            Ok(rows) => rows,
-            Err(_) => return Err(()),
+        let direction = match query.direction {
+            SortDirection::Ascending => "asc",
+            SortDirection::Descending => "desc",
         };
+        query_str.push_str(&format!(
+            " order by {column} {direction}, p.number, p.form limit ? offset ?"
+        ));
+        // -1 is no limit in SQLite, and larger values are as good as none
+        let limit = query.limit.map_or(-1, |limit| i64::try_from(limit).unwrap_or(i64::MAX));
+        params.push(Value::Integer(limit));
+        params.push(Value::Integer(i64::try_from(query.offset).unwrap_or(i64::MAX)));
 
-        let mut pokemon_rows = vec![];
+        let mut stmt = lock.prepare(&query_str).map_err(storage_error)?;
+        let mut rows = stmt
+            .query(params_from_iter(params))
+            .map_err(storage_error)?;
 
-        while let Ok(Some(row)) = rows.next() {
-            match (row.get::<usize, u16>(0), row.get::<usize, String>(1)) {
-                (Ok(number), Ok(name)) => pokemon_rows.push((number, name)),
-                _ => return Err(()),
+        let mut pokemon_rows = vec![];
+        while let Some(row) = rows.next().map_err(storage_error)? {
+            let mut stats = [0; 6];
+            for (i, stat) in stats.iter_mut().enumerate() {
+                *stat = row.get::<usize, u16>(i + 3).map_err(storage_error)?;
             }
+            let number = row.get::<usize, u16>(0).map_err(storage_error)?;
+            let form = row.get::<usize, String>(1).map_err(storage_error)?;
+            let name = row.get::<usize, String>(2).map_err(storage_error)?;
+            pokemon_rows.push((number, form, name, stats));
         }
         Ok(pokemon_rows)
     }
 
-    fn fetch_type_rows(lock: &MutexGuard<'_, Connection>, number: u16) -> Result<Vec<String>, ()> {
-        let mut stmt = match lock.prepare("select name from types where pokemon_number = ?") {
-            Ok(s) => s,
-            Err(_) => return Err(()),
-        };
+    /// The base form is stored with an empty form, so that it can be part of
+    /// the primary key.
+    fn key_params(key: &PokemonKey) -> (u16, String) {
+        (
+            u16::from(key.number.clone()),
+            key.form.clone().map(String::from).unwrap_or_default(),
+        )
+    }
 
-        let mut rows = match stmt.query([number]) {
-            Ok(rows) => rows,
-            _ => return Err(()),
-        };
+    fn insert_stats(
+        transaction: &Transaction<'_>,
+        key: &PokemonKey,
+        stats: &PokemonStats,
+    ) -> Result<usize, rusqlite::Error> {
+        let (number, form) = Self::key_params(key);
+        let [hp, attack, defense, special_attack, special_defense, speed] =
+            <[u16; 6]>::from(stats.clone());
+        transaction.execute(
+            "insert into stats values (?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                number,
+                form,
+                hp,
+                attack,
+                defense,
+                special_attack,
+                special_defense,
+                speed
+            ],
+        )
+    }
+
+    fn fetch_type_rows(
+        lock: &MutexGuard<'_, Connection>,
+        row: &PokemonRow,
+    ) -> Result<Vec<String>, StorageError> {
+        let mut stmt = lock
+            .prepare("select name from types where pokemon_number = ? and form = ? order by rowid")
+            .map_err(storage_error)?;
+        let mut rows = stmt.query(params![row.0, row.1]).map_err(storage_error)?;
 
         let mut type_rows = vec![];
-        while let Ok(Some(row)) = rows.next() {
-            match row.get::<usize, String>(0) {
-                Ok(name) => type_rows.push(name),
-                Err(_) => return Err(()),
-            }
+        while let Some(row) = rows.next().map_err(storage_error)? {
+            type_rows.push(row.get::<usize, String>(0).map_err(storage_error)?);
         }
         Ok(type_rows)
     }
+
+    fn to_pokemon(row: PokemonRow, type_rows: Vec<String>) -> Result<Pokemon, StorageError> {
+        let corrupt = |e: &dyn std::fmt::Display| {
+            StorageError::CorruptRow(format!("pokemon({}): {e}", row.0))
+        };
+        let number = PokemonNumber::try_from(row.0).map_err(|e| corrupt(&e))?;
+        let form = match row.1.is_empty() {
+            true => None,
+            false => Some(PokemonForm::try_from(row.1.clone()).map_err(|e| corrupt(&e))?),
+        };
+        let name = PokemonName::try_from(row.2.clone()).map_err(|e| corrupt(&e))?;
+        let types = PokemonTypes::try_from(type_rows).map_err(|e| corrupt(&e))?;
+        let stats = PokemonStats::try_from(row.3).map_err(|e| corrupt(&e))?;
+        Ok(Pokemon::new(number, name, types, stats).with_form(form))
+    }
+
+    fn write_details(
+        transaction: &Transaction<'_>,
+        key: &PokemonKey,
+        types: &PokemonTypes,
+        stats: &PokemonStats,
+    ) -> Result<(), StorageError> {
+        let (number, form) = Self::key_params(key);
+        for tipe in Vec::from(types.clone()) {
+            transaction
+                .execute("insert into types values(?, ?, ?)", params![number, form, tipe])
+                .map_err(storage_error)?;
+        }
+        Self::insert_stats(transaction, key, stats).map_err(storage_error)?;
+        Ok(())
+    }
+
+    fn fetch_condition_rows(
+        lock: &MutexGuard<'_, Connection>,
+        to: u16,
+    ) -> Result<Vec<String>, StorageError> {
+        let mut stmt = lock
+            .prepare("select condition from evolution_conditions where to_number = ? order by rowid")
+            .map_err(storage_error)?;
+        let mut rows = stmt.query([to]).map_err(storage_error)?;
+
+        let mut condition_rows = vec![];
+        while let Some(row) = rows.next().map_err(storage_error)? {
+            condition_rows.push(row.get::<usize, String>(0).map_err(storage_error)?);
+        }
+        Ok(condition_rows)
+    }
+
+    fn to_evolution(row: EvolutionRow, conditions: Vec<String>) -> Result<Evolution, StorageError> {
+        let corrupt = |e: &dyn std::fmt::Display| {
+            StorageError::CorruptRow(format!("evolution({}): {e}", row.1))
+        };
+        let from = PokemonNumber::try_from(row.0).map_err(|e| corrupt(&e))?;
+        let to = PokemonNumber::try_from(row.1).map_err(|e| corrupt(&e))?;
+        let trigger = EvolutionTrigger::try_from(row.2).map_err(|e| corrupt(&e))?;
+        Ok(Evolution::new(from, to, trigger, conditions))
+    }
+
+    fn fetch_move_rows(
+        lock: &MutexGuard<'_, Connection>,
+        id: Option<u16>,
+    ) -> Result<Vec<MoveRow>, StorageError> {
+        let mut stmt = lock
+            .prepare(
+                "select id, name, type, category, power, accuracy, pp from moves \
+                where ?1 is null or id = ?1 order by id",
+            )
+            .map_err(storage_error)?;
+        let mut rows = stmt.query([id]).map_err(storage_error)?;
+
+        let mut move_rows = vec![];
+        while let Some(row) = rows.next().map_err(storage_error)? {
+            move_rows.push((
+                row.get(0).map_err(storage_error)?,
+                row.get(1).map_err(storage_error)?,
+                row.get(2).map_err(storage_error)?,
+                row.get(3).map_err(storage_error)?,
+                row.get(4).map_err(storage_error)?,
+                row.get(5).map_err(storage_error)?,
+                row.get(6).map_err(storage_error)?,
+            ));
+        }
+        Ok(move_rows)
+    }
+
+    fn to_move(row: MoveRow) -> Result<Move, StorageError> {
+        let corrupt =
+            |e: &dyn std::fmt::Display| StorageError::CorruptRow(format!("move({}): {e}", row.0));
+        let id = MoveId::try_from(row.0).map_err(|e| corrupt(&e))?;
+        let name = PokemonName::try_from(row.1).map_err(|e| corrupt(&e))?;
+        let tipe = PokemonType::try_from(row.2).map_err(|e| corrupt(&e))?;
+        let category = MoveCategory::try_from(row.3).map_err(|e| corrupt(&e))?;
+        let power = row.4.map(MovePower::try_from).transpose().map_err(|e| corrupt(&e))?;
+        let accuracy = row.5.map(MoveAccuracy::try_from).transpose().map_err(|e| corrupt(&e))?;
+        let pp = MovePp::try_from(row.6).map_err(|e| corrupt(&e))?;
+        Ok(Move::new(id, name, tipe, category, power, accuracy, pp))
+    }
+
+    fn move_params(new_move: &Move) -> MoveRow {
+        (
+            u16::from(new_move.id.clone()),
+            String::from(new_move.name.clone()),
+            String::from(new_move.tipe),
+            String::from(new_move.category),
+            new_move.power.map(u16::from),
+            new_move.accuracy.map(u16::from),
+            u16::from(new_move.pp),
+        )
+    }
 }
 
 impl Repository for SqliteRepository {
     fn insert(
         &self,
-        number: PokemonNumber,
+        key: PokemonKey,
         name: PokemonName,
         types: PokemonTypes,
+        stats: PokemonStats,
     ) -> Result<Pokemon, InsertError> {
         let mut lock = match self.conn.lock() {
             Ok(lock) => lock,
-            Err(_) => return Err(InsertError::Unknown),
+            Err(_) => return Err(InsertError::Storage(poisoned())),
         };
         let transaction = match lock.transaction() {
             Ok(t) => t,
-            Err(e) => {
-                println!("error while starting transaction: {e}");
-                return Err(InsertError::Unknown);
-            }
+            Err(e) => return Err(InsertError::Storage(storage_error(e))),
         };
 
+        let (number, form) = Self::key_params(&key);
+        if let Err(e) = transaction.execute("insert or ignore into species values (?)", [number]) {
+            return Err(InsertError::Storage(storage_error(e)));
+        }
         match transaction.execute(
-            "insert into pokemons values (?, ?)",
-            params![u16::from(number.clone()), String::from(name.clone())],
+            "insert into pokemons values (?, ?, ?)",
+            params![number, form, String::from(name.clone())],
         ) {
             Ok(_) => {}
-            Err(rusqlite::Error::SqliteFailure(_, Some(msg))) => {
-                if msg == "UNIQUE constraint failed: pokemon.number" {
-                    return Err(InsertError::Conflict);
-                }
-                return Err(InsertError::Unknown);
-            }
-            Err(e) => {
-                println!("error while inserting pokemon: {e}");
-                return Err(InsertError::Unknown);
+            Err(e) if is_conflict(&e) => return Err(InsertError::Conflict),
+            Err(e) => return Err(InsertError::Storage(storage_error(e))),
+        }
+
+        if let Err(e) = Self::write_details(&transaction, &key, &types, &stats) {
+            return Err(InsertError::Storage(e));
+        }
+
+        match transaction.commit() {
+            Ok(_) => Ok(Pokemon::new(key.number, name, types, stats).with_form(key.form)),
+            Err(e) => Err(InsertError::Storage(storage_error(e))),
+        }
+    }
+
+    fn fetch_all(&self, query: &FetchAllQuery) -> Result<Vec<Pokemon>, FetchAllError> {
+        let lock = match self.conn.lock() {
+            Ok(lock) => lock,
+            Err(_) => return Err(FetchAllError::Storage(poisoned())),
+        };
+
+        let pokemon_rows = match Self::fetch_pokemon_rows(&lock, query) {
+            Ok(rows) => rows,
+            Err(e) => return Err(FetchAllError::Storage(e)),
+        };
+
+        let mut pokemons = Vec::with_capacity(pokemon_rows.len());
+        for pokemon_row in pokemon_rows {
+            let pokemon = Self::fetch_type_rows(&lock, &pokemon_row)
+                .and_then(|type_rows| Self::to_pokemon(pokemon_row, type_rows));
+            match pokemon {
+                Ok(pokemon) => pokemons.push(pokemon),
+                Err(e) => return Err(FetchAllError::Storage(e)),
             }
         }
 
-        for tipe in Vec::from(types.clone()) {
+        Ok(pokemons)
+    }
+
+    fn fetch_one(&self, key: PokemonKey) -> Result<Pokemon, FetchOneError> {
+        let lock = match self.conn.lock() {
+            Ok(lock) => lock,
+            Err(_) => return Err(FetchOneError::Storage(poisoned())),
+        };
+
+        let query = FetchAllQuery::by_number(key.number.clone());
+        let (_, form) = Self::key_params(&key);
+        let pokemon_rows = match Self::fetch_pokemon_rows(&lock, &query) {
+            Ok(rows) => rows,
+            Err(e) => return Err(FetchOneError::Storage(e)),
+        };
+        let pokemon_row = match pokemon_rows.into_iter().find(|row| row.1 == form) {
+            Some(row) => row,
+            None => return Err(FetchOneError::NotFound),
+        };
+
+        Self::fetch_type_rows(&lock, &pokemon_row)
+            .and_then(|type_rows| Self::to_pokemon(pokemon_row, type_rows))
+            .map_err(FetchOneError::Storage)
+    }
+
+    fn update(
+        &self,
+        key: PokemonKey,
+        name: PokemonName,
+        types: PokemonTypes,
+        stats: PokemonStats,
+    ) -> Result<Pokemon, UpdateError> {
+        let mut lock = match self.conn.lock() {
+            Ok(lock) => lock,
+            Err(_) => return Err(UpdateError::Storage(poisoned())),
+        };
+        let transaction = match lock.transaction() {
+            Ok(t) => t,
+            Err(e) => return Err(UpdateError::Storage(storage_error(e))),
+        };
+
+        let (number, form) = Self::key_params(&key);
+        match transaction.execute(
+            "update pokemons set name = ? where number = ? and form = ?",
+            params![String::from(name.clone()), number, form],
+        ) {
+            Ok(0) => return Err(UpdateError::NotFound),
+            Ok(_) => {}
+            Err(e) => return Err(UpdateError::Storage(storage_error(e))),
+        }
+
+        for table in ["types", "stats"] {
             if let Err(e) = transaction.execute(
-                "insert into types values(?, ?)",
-                params![u16::from(number.clone()), tipe],
+                &format!("delete from {table} where pokemon_number = ? and form = ?"),
+                params![number, form],
             ) {
-                println!("error in inserting type: {e}");
-                return Err(InsertError::Unknown);
+                return Err(UpdateError::Storage(storage_error(e)));
             }
         }
 
+        if let Err(e) = Self::write_details(&transaction, &key, &types, &stats) {
+            return Err(UpdateError::Storage(e));
+        }
+
         match transaction.commit() {
-            Ok(_) => Ok(Pokemon::new(number, name, types)),
-            Err(e) => {
-                println!("error while commiting transaction: {e}");
-                Err(InsertError::Unknown)
+            Ok(_) => Ok(Pokemon::new(key.number, name, types, stats).with_form(key.form)),
+            Err(e) => Err(UpdateError::Storage(storage_error(e))),
+        }
+    }
+
+    fn delete(&self, key: PokemonKey) -> Result<(), DeleteError> {
+        let mut lock = match self.conn.lock() {
+            Ok(lock) => lock,
+            Err(_) => return Err(DeleteError::Storage(poisoned())),
+        };
+        let transaction = match lock.transaction() {
+            Ok(t) => t,
+            Err(e) => return Err(DeleteError::Storage(storage_error(e))),
+        };
+
+        let (number, form) = Self::key_params(&key);
+        match transaction.execute(
+            "delete from pokemons where number = ? and form = ?",
+            params![number, form],
+        ) {
+            Ok(0) => return Err(DeleteError::NotFound),
+            Ok(_) => {}
+            Err(e) => return Err(DeleteError::Storage(storage_error(e))),
+        }
+
+        // Dropping the species cascades to its other forms and everything
+        // attached to the national number.
+        let species = match key.is_base() {
+            true => "delete from species where number = ?1",
+            false => {
+                "delete from species where number = ?1 \
+                and not exists (select 1 from pokemons where number = ?1)"
             }
+        };
+        if let Err(e) = transaction.execute(species, [number]) {
+            return Err(DeleteError::Storage(storage_error(e)));
+        }
+
+        match transaction.commit() {
+            Ok(_) => Ok(()),
+            Err(e) => Err(DeleteError::Storage(storage_error(e))),
         }
     }
 
-    fn fetch_all(&self) -> Result<Vec<Pokemon>, FetchAllError> {
-        let lock = match self.conn.lock() {
+    fn upsert_all(&self, pokemons: Vec<Pokemon>) -> Result<(), UpsertAllError> {
+        let mut lock = match self.conn.lock() {
             Ok(lock) => lock,
-            Err(_) => return Err(FetchAllError::Unknown),
+            Err(_) => return Err(UpsertAllError::Storage(poisoned())),
+        };
+        let transaction = match lock.transaction() {
+            Ok(t) => t,
+            Err(e) => return Err(UpsertAllError::Storage(storage_error(e))),
         };
 
-        let pokemon_rows = match Self::fetch_pokemon_rows(&lock, None) {
-            Ok(rows) => rows,
-            Err(_) => return Err(FetchAllError::Unknown),
+        for pokemon in &pokemons {
+            let key = pokemon.key();
+            let (number, form) = Self::key_params(&key);
+            // Replacing the row itself would cascade to its types and stats.
+            let written = transaction
+                .execute("insert or ignore into species values (?)", [number])
+                .and_then(|_| {
+                    transaction.execute(
+                        "insert into pokemons values (?, ?, ?)
+                        on conflict (number, form) do update set name = excluded.name",
+                        params![number, form, String::from(pokemon.name.clone())],
+                    )
+                })
+                .and_then(|_| {
+                    transaction.execute(
+                        "delete from types where pokemon_number = ? and form = ?",
+                        params![number, form],
+                    )
+                })
+                .and_then(|_| {
+                    transaction.execute(
+                        "delete from stats where pokemon_number = ? and form = ?",
+                        params![number, form],
+                    )
+                })
+                .map_err(storage_error)
+                .and_then(|_| {
+                    Self::write_details(&transaction, &key, &pokemon.types, &pokemon.stats)
+                });
+            if let Err(e) = written {
+                return Err(UpsertAllError::Storage(e));
+            }
+        }
+
+        match transaction.commit() {
+            Ok(_) => Ok(()),
+            Err(e) => Err(UpsertAllError::Storage(storage_error(e))),
+        }
+    }
+
+    fn insert_evolution(&self, evolution: Evolution) -> Result<Evolution, InsertEvolutionError> {
+        let mut lock = match self.conn.lock() {
+            Ok(lock) => lock,
+            Err(_) => return Err(InsertEvolutionError::Storage(poisoned())),
+        };
+        let transaction = match lock.transaction() {
+            Ok(t) => t,
+            Err(e) => return Err(InsertEvolutionError::Storage(storage_error(e))),
         };
 
-        let mut pokemons = Vec::with_capacity(pokemon_rows.len());
-        for pokemon_row in pokemon_rows {
-            let type_rows = match Self::fetch_type_rows(&lock, pokemon_row.0) {
-                Ok(rows) => rows,
-                Err(_) => return Err(FetchAllError::Unknown),
-            };
-            let pokemon = match (
-                PokemonNumber::try_from(pokemon_row.0),
-                PokemonName::try_from(pokemon_row.1),
-                PokemonTypes::try_from(type_rows),
+        let to = u16::from(evolution.to.clone());
+        match transaction.execute(
+            "insert into evolutions values (?, ?, ?)",
+            params![
+                to,
+                u16::from(evolution.from.clone()),
+                String::from(evolution.trigger.clone())
+            ],
+        ) {
+            Ok(_) => {}
+            Err(e) if is_conflict(&e) => return Err(InsertEvolutionError::Conflict),
+            Err(e) => return Err(InsertEvolutionError::Storage(storage_error(e))),
+        }
+
+        for condition in &evolution.conditions {
+            if let Err(e) = transaction.execute(
+                "insert into evolution_conditions values (?, ?)",
+                params![to, condition],
             ) {
-                (Ok(number), Ok(name), Ok(types)) => Pokemon::new(number, name, types),
-                _ => return Err(FetchAllError::Unknown),
-            };
+                return Err(InsertEvolutionError::Storage(storage_error(e)));
+            }
+        }
 
-            pokemons.push(pokemon);
+        match transaction.commit() {
+            Ok(_) => Ok(evolution),
+            Err(e) => Err(InsertEvolutionError::Storage(storage_error(e))),
         }
+    }
 
-        Ok(pokemons)
+    fn fetch_evolutions(&self) -> Result<Vec<Evolution>, StorageError> {
+        let lock = self.conn.lock().map_err(|_| poisoned())?;
+
+        let mut stmt = lock
+            .prepare("select from_number, to_number, trigger from evolutions order by from_number, to_number")
+            .map_err(storage_error)?;
+        let mut rows = stmt.query([]).map_err(storage_error)?;
+        let mut evolution_rows: Vec<EvolutionRow> = vec![];
+        while let Some(row) = rows.next().map_err(storage_error)? {
+            evolution_rows.push((
+                row.get(0).map_err(storage_error)?,
+                row.get(1).map_err(storage_error)?,
+                row.get(2).map_err(storage_error)?,
+            ));
+        }
+
+        let mut evolutions = Vec::with_capacity(evolution_rows.len());
+        for row in evolution_rows {
+            let conditions = Self::fetch_condition_rows(&lock, row.1)?;
+            evolutions.push(Self::to_evolution(row, conditions)?);
+        }
+        Ok(evolutions)
     }
 
-    fn fetch_one(&self, number: PokemonNumber) -> Result<Pokemon, FetchOneError> {
+    fn insert_move(&self, new_move: Move) -> Result<Move, InsertMoveError> {
         let lock = match self.conn.lock() {
             Ok(lock) => lock,
-            Err(_) => return Err(FetchOneError::Unknown),
+            Err(_) => return Err(InsertMoveError::Storage(poisoned())),
         };
 
-        let number = u16::from(number);
-        let mut pokemon_rows = match Self::fetch_pokemon_rows(&lock, Some(number)) {
+        let (id, name, tipe, category, power, accuracy, pp) = Self::move_params(&new_move);
+        match lock.execute(
+            "insert into moves values (?, ?, ?, ?, ?, ?, ?)",
+            params![id, name, tipe, category, power, accuracy, pp],
+        ) {
+            Ok(_) => Ok(new_move),
+            Err(e) if is_conflict(&e) => Err(InsertMoveError::Conflict),
+            Err(e) => Err(InsertMoveError::Storage(storage_error(e))),
+        }
+    }
+
+    fn fetch_moves(&self) -> Result<Vec<Move>, StorageError> {
+        let lock = self.conn.lock().map_err(|_| poisoned())?;
+
+        Self::fetch_move_rows(&lock, None)?
+            .into_iter()
+            .map(Self::to_move)
+            .collect()
+    }
+
+    fn fetch_move(&self, id: MoveId) -> Result<Move, FetchMoveError> {
+        let lock = match self.conn.lock() {
+            Ok(lock) => lock,
+            Err(_) => return Err(FetchMoveError::Storage(poisoned())),
+        };
+
+        let mut move_rows = match Self::fetch_move_rows(&lock, Some(u16::from(id))) {
             Ok(rows) => rows,
-            Err(_) => return Err(FetchOneError::Unknown),
+            Err(e) => return Err(FetchMoveError::Storage(e)),
         };
-        if pokemon_rows.is_empty() {
-            return Err(FetchOneError::NotFound);
+        match move_rows.pop() {
+            Some(row) => Self::to_move(row).map_err(FetchMoveError::Storage),
+            None => Err(FetchMoveError::NotFound),
         }
-        let pokemon_row = pokemon_rows.remove(0);
+    }
 
-        let type_rows = match Self::fetch_type_rows(&lock, number) {
-            Ok(rows) => rows,
-            Err(_) => return Err(FetchOneError::Unknown),
+    fn update_move(&self, new_move: Move) -> Result<Move, UpdateMoveError> {
+        let lock = match self.conn.lock() {
+            Ok(lock) => lock,
+            Err(_) => return Err(UpdateMoveError::Storage(poisoned())),
         };
 
-        match (
-            PokemonNumber::try_from(pokemon_row.0),
-            PokemonName::try_from(pokemon_row.1),
-            PokemonTypes::try_from(type_rows),
+        let (id, name, tipe, category, power, accuracy, pp) = Self::move_params(&new_move);
+        match lock.execute(
+            "update moves set name = ?, type = ?, category = ?, power = ?, accuracy = ?, pp = ? \
+            where id = ?",
+            params![name, tipe, category, power, accuracy, pp, id],
         ) {
-            (Ok(number), Ok(name), Ok(types)) => Ok(Pokemon::new(number, name, types)),
-            _ => Err(FetchOneError::Unknown),
+            Ok(0) => Err(UpdateMoveError::NotFound),
+            Ok(_) => Ok(new_move),
+            Err(e) => Err(UpdateMoveError::Storage(storage_error(e))),
         }
     }
 
-    fn delete(&self, number: PokemonNumber) -> Result<(), DeleteError> {
+    fn delete_move(&self, id: MoveId) -> Result<(), DeleteMoveError> {
         let lock = match self.conn.lock() {
             Ok(lock) => lock,
-            Err(_) => return Err(DeleteError::Unknown),
+            Err(_) => return Err(DeleteMoveError::Storage(poisoned())),
+        };
+
+        match lock.execute("delete from moves where id = ?", params![u16::from(id)]) {
+            Ok(0) => Err(DeleteMoveError::NotFound),
+            Ok(_) => Ok(()),
+            Err(e) => Err(DeleteMoveError::Storage(storage_error(e))),
+        }
+    }
+
+    fn replace_learnset(
+        &self,
+        number: PokemonNumber,
+        mut learnset: Vec<LearnsetEntry>,
+    ) -> Result<Vec<LearnsetEntry>, StorageError> {
+        let mut lock = self.conn.lock().map_err(|_| poisoned())?;
+        let transaction = lock.transaction().map_err(storage_error)?;
+
+        let number = u16::from(number);
+        transaction
+            .execute("delete from learnsets where pokemon_number = ?", [number])
+            .map_err(storage_error)?;
+        for entry in &learnset {
+            transaction
+                .execute(
+                    "insert into learnsets values (?, ?, ?)",
+                    params![
+                        number,
+                        u16::from(entry.move_id.clone()),
+                        String::from(entry.method.clone())
+                    ],
+                )
+                .map_err(storage_error)?;
+        }
+        transaction.commit().map_err(storage_error)?;
+
+        learnset.sort_by(|a, b| (&a.method, &a.move_id).cmp(&(&b.method, &b.move_id)));
+        Ok(learnset)
+    }
+
+    fn fetch_learnset(&self, number: PokemonNumber) -> Result<Vec<LearnsetEntry>, StorageError> {
+        let lock = self.conn.lock().map_err(|_| poisoned())?;
+
+        let mut stmt = lock
+            .prepare("select move_id, method from learnsets where pokemon_number = ?")
+            .map_err(storage_error)?;
+        let mut rows = stmt.query([u16::from(number)]).map_err(storage_error)?;
+        let mut learnset = vec![];
+        while let Some(row) = rows.next().map_err(storage_error)? {
+            let move_id = row.get::<usize, u16>(0).map_err(storage_error)?;
+            let method = row.get::<usize, String>(1).map_err(storage_error)?;
+            let corrupt =
+                |e: &dyn std::fmt::Display| StorageError::CorruptRow(format!("learnset({move_id}): {e}"));
+            learnset.push(LearnsetEntry::new(
+                MoveId::try_from(move_id).map_err(|e| corrupt(&e))?,
+                LearnMethod::try_from(method).map_err(|e| corrupt(&e))?,
+            ));
+        }
+
+        // Level-up moves sort by level, which the stored text can't do.
+        learnset.sort_by(|a, b| (&a.method, &a.move_id).cmp(&(&b.method, &b.move_id)));
+        Ok(learnset)
+    }
+
+    fn insert_ability(&self, ability: Ability) -> Result<Ability, InsertAbilityError> {
+        let lock = match self.conn.lock() {
+            Ok(lock) => lock,
+            Err(_) => return Err(InsertAbilityError::Storage(poisoned())),
         };
 
         match lock.execute(
-            "delete from pokemons where number = ?",
-            params![u16::from(number)],
+            "insert into abilities values (?, ?)",
+            params![String::from(ability.name.clone()), ability.description],
         ) {
-            Ok(0) => Err(DeleteError::NotFound),
-            Ok(_) => Ok(()),
-            _ => Err(DeleteError::Unknown),
+            Ok(_) => Ok(ability),
+            Err(e) if is_conflict(&e) => Err(InsertAbilityError::Conflict),
+            Err(e) => Err(InsertAbilityError::Storage(storage_error(e))),
+        }
+    }
+
+    fn fetch_abilities(&self) -> Result<Vec<Ability>, StorageError> {
+        let lock = self.conn.lock().map_err(|_| poisoned())?;
+
+        let mut stmt = lock
+            .prepare("select name, description from abilities order by name")
+            .map_err(storage_error)?;
+        let mut rows = stmt.query([]).map_err(storage_error)?;
+        let mut abilities = vec![];
+        while let Some(row) = rows.next().map_err(storage_error)? {
+            let name = row.get::<usize, String>(0).map_err(storage_error)?;
+            let description = row.get::<usize, String>(1).map_err(storage_error)?;
+            let name = PokemonName::try_from(name)
+                .map_err(|e| StorageError::CorruptRow(format!("ability: {e}")))?;
+            abilities.push(Ability::new(name, description));
         }
+        Ok(abilities)
+    }
+
+    fn replace_abilities(
+        &self,
+        number: PokemonNumber,
+        abilities: PokemonAbilities,
+    ) -> Result<PokemonAbilities, StorageError> {
+        let mut lock = self.conn.lock().map_err(|_| poisoned())?;
+        let transaction = lock.transaction().map_err(storage_error)?;
+
+        let number = u16::from(number);
+        transaction
+            .execute("delete from pokemon_abilities where pokemon_number = ?", [number])
+            .map_err(storage_error)?;
+        let regular = abilities.regular().iter().map(|name| (name, false));
+        for (name, hidden) in regular.chain(abilities.hidden().map(|name| (name, true))) {
+            transaction
+                .execute(
+                    "insert into pokemon_abilities values (?, ?, ?)",
+                    params![number, name.as_str(), hidden],
+                )
+                .map_err(storage_error)?;
+        }
+        transaction.commit().map_err(storage_error)?;
+
+        Ok(abilities)
     }
-}
\ No newline at end of file
+
+    fn fetch_pokemon_abilities(
+        &self,
+        number: PokemonNumber,
+    ) -> Result<PokemonAbilities, StorageError> {
+        let lock = self.conn.lock().map_err(|_| poisoned())?;
+
+        let mut stmt = lock
+            .prepare(
+                "select ability, hidden from pokemon_abilities where pokemon_number = ? \
+                order by hidden, rowid",
+            )
+            .map_err(storage_error)?;
+        let mut rows = stmt.query([u16::from(number.clone())]).map_err(storage_error)?;
+        let mut regular = vec![];
+        let mut hidden = None;
+        while let Some(row) = rows.next().map_err(storage_error)? {
+            let name = row.get::<usize, String>(0).map_err(storage_error)?;
+            match row.get::<usize, bool>(1).map_err(storage_error)? {
+                true => hidden = Some(name),
+                false => regular.push(name),
+            }
+        }
+
+        PokemonAbilities::try_from((regular, hidden)).map_err(|e| {
+            StorageError::CorruptRow(format!("abilities({}): {e}", u16::from(number)))
+        })
+    }
+
+    fn fetch_ability_holders(
+        &self,
+        ability: PokemonName,
+    ) -> Result<Vec<(PokemonNumber, bool)>, StorageError> {
+        let lock = self.conn.lock().map_err(|_| poisoned())?;
+
+        let mut stmt = lock
+            .prepare(
+                "select pokemon_number, hidden from pokemon_abilities where ability = ? \
+                order by pokemon_number",
+            )
+            .map_err(storage_error)?;
+        let mut rows = stmt.query([ability.as_str()]).map_err(storage_error)?;
+        let mut holders = vec![];
+        while let Some(row) = rows.next().map_err(storage_error)? {
+            let number = row.get::<usize, u16>(0).map_err(storage_error)?;
+            let number = PokemonNumber::try_from(number)
+                .map_err(|e| StorageError::CorruptRow(format!("abilities({number}): {e}")))?;
+            holders.push((number, row.get::<usize, bool>(1).map_err(storage_error)?));
+        }
+        Ok(holders)
+    }
+
+    fn replace_regional_dex(
+        &self,
+        name: DexName,
+        mut entries: Vec<DexEntry>,
+    ) -> Result<Vec<DexEntry>, StorageError> {
+        let mut lock = self.conn.lock().map_err(|_| poisoned())?;
+        let transaction = lock.transaction().map_err(storage_error)?;
+
+        transaction
+            .execute("delete from regional_dexes where dex = ?", [name.as_str()])
+            .map_err(storage_error)?;
+        for entry in &entries {
+            transaction
+                .execute(
+                    "insert into regional_dexes values (?, ?, ?)",
+                    params![
+                        name.as_str(),
+                        u16::from(entry.index),
+                        u16::from(entry.number.clone())
+                    ],
+                )
+                .map_err(storage_error)?;
+        }
+        transaction.commit().map_err(storage_error)?;
+
+        entries.sort_by_key(|entry| entry.index);
+        Ok(entries)
+    }
+
+    fn fetch_regional_dex(&self, name: DexName) -> Result<Vec<DexEntry>, StorageError> {
+        let lock = self.conn.lock().map_err(|_| poisoned())?;
+
+        let mut stmt = lock
+            .prepare(
+                "select dex_index, pokemon_number from regional_dexes where dex = ? \
+                order by dex_index",
+            )
+            .map_err(storage_error)?;
+        let mut rows = stmt.query([name.as_str()]).map_err(storage_error)?;
+        let mut entries = vec![];
+        while let Some(row) = rows.next().map_err(storage_error)? {
+            let index = row.get::<usize, u16>(0).map_err(storage_error)?;
+            let number = row.get::<usize, u16>(1).map_err(storage_error)?;
+            let corrupt = |e: &dyn std::fmt::Display| {
+                StorageError::CorruptRow(format!("dex({}, {index}): {e}", name.as_str()))
+            };
+            entries.push(DexEntry::new(
+                DexIndex::from(index),
+                PokemonNumber::try_from(number).map_err(|e| corrupt(&e))?,
+            ));
+        }
+        Ok(entries)
+    }
+
+    fn insert_trainer(&self, trainer: Trainer) -> Result<Trainer, InsertTrainerError> {
+        let lock = match self.conn.lock() {
+            Ok(lock) => lock,
+            Err(_) => return Err(InsertTrainerError::Storage(poisoned())),
+        };
+
+        match lock.execute(
+            "insert into trainers values (?, ?)",
+            params![trainer.id.as_str(), trainer.name.as_str()],
+        ) {
+            Ok(_) => Ok(trainer),
+            Err(e) if is_conflict(&e) => Err(InsertTrainerError::Conflict),
+            Err(e) => Err(InsertTrainerError::Storage(storage_error(e))),
+        }
+    }
+
+    fn fetch_trainer(&self, id: TrainerId) -> Result<Trainer, FetchTrainerError> {
+        let lock = match self.conn.lock() {
+            Ok(lock) => lock,
+            Err(_) => return Err(FetchTrainerError::Storage(poisoned())),
+        };
+
+        let name = match lock.query_row(
+            "select name from trainers where id = ?",
+            [id.as_str()],
+            |row| row.get::<usize, String>(0),
+        ) {
+            Ok(name) => name,
+            Err(rusqlite::Error::QueryReturnedNoRows) => return Err(FetchTrainerError::NotFound),
+            Err(e) => return Err(FetchTrainerError::Storage(storage_error(e))),
+        };
+        match PokemonName::try_from(name) {
+            Ok(name) => Ok(Trainer::new(id, name)),
+            Err(e) => Err(FetchTrainerError::Storage(StorageError::CorruptRow(format!(
+                "trainer({}): {e}",
+                id.as_str()
+            )))),
+        }
+    }
+
+    fn replace_dex_status(
+        &self,
+        trainer: TrainerId,
+        number: PokemonNumber,
+        status: DexStatus,
+    ) -> Result<DexStatus, StorageError> {
+        let mut lock = self.conn.lock().map_err(|_| poisoned())?;
+        let transaction = lock.transaction().map_err(storage_error)?;
+
+        // Writing first checks that both the trainer and the Pokemon exist,
+        // even when the status is then forgotten.
+        let number = u16::from(number);
+        transaction
+            .execute(
+                "insert into trainer_dexes values (?1, ?2, ?3, ?4, ?5) \
+                on conflict (trainer_id, pokemon_number) \
+                do update set seen = ?3, caught = ?4, shiny = ?5",
+                params![
+                    trainer.as_str(),
+                    number,
+                    status.seen(),
+                    status.caught(),
+                    status.shiny()
+                ],
+            )
+            .map_err(storage_error)?;
+        if status.is_empty() {
+            transaction
+                .execute(
+                    "delete from trainer_dexes where trainer_id = ? and pokemon_number = ?",
+                    params![trainer.as_str(), number],
+                )
+                .map_err(storage_error)?;
+        }
+        transaction.commit().map_err(storage_error)?;
+
+        Ok(status)
+    }
+
+    fn fetch_trainer_dex(
+        &self,
+        trainer: TrainerId,
+    ) -> Result<Vec<(PokemonNumber, DexStatus)>, StorageError> {
+        let lock = self.conn.lock().map_err(|_| poisoned())?;
+
+        let mut stmt = lock
+            .prepare(
+                "select pokemon_number, seen, caught, shiny from trainer_dexes \
+                where trainer_id = ? order by pokemon_number",
+            )
+            .map_err(storage_error)?;
+        let mut rows = stmt.query([trainer.as_str()]).map_err(storage_error)?;
+        let mut statuses = vec![];
+        while let Some(row) = rows.next().map_err(storage_error)? {
+            let number = row.get::<usize, u16>(0).map_err(storage_error)?;
+            let number = PokemonNumber::try_from(number).map_err(|e| {
+                StorageError::CorruptRow(format!("trainer_dexes({}): {e}", trainer.as_str()))
+            })?;
+            let flags = (
+                row.get::<usize, bool>(1).map_err(storage_error)?,
+                row.get::<usize, bool>(2).map_err(storage_error)?,
+                row.get::<usize, bool>(3).map_err(storage_error)?,
+            );
+            statuses.push((number, DexStatus::from(flags)));
+        }
+        Ok(statuses)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    fn repository() -> Arc<dyn Repository> {
+        Arc::new(SqliteRepository::try_new(":memory:", Duration::from_secs(1)).unwrap())
+    }
+
+    repository_conformance!(repository);
+    learnset_conformance!(repository);
+    ability_conformance!(repository);
+    regional_dex_conformance!(repository);
+    trainer_conformance!(repository);
+
+    #[test]
+    fn it_should_create_a_missing_database_file() {
+        let path = std::env::temp_dir().join(format!("pokedex-{}.sqlite", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let repo = SqliteRepository::try_new(path.to_str().unwrap(), Duration::from_secs(1)).unwrap();
+        let res = repo.insert(
+            PokemonNumber::pikachu().into(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        );
+
+        assert!(res.is_ok());
+        assert_eq!(
+            String::from(repo.fetch_one(PokemonNumber::pikachu().into()).unwrap().name),
+            String::from(PokemonName::pikachu())
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn it_should_replace_existing_pokemons_when_upserting() {
+        let repo = SqliteRepository::try_new(":memory:", Duration::from_secs(1)).unwrap();
+        repo.insert(
+            PokemonNumber::pikachu().into(),
+            PokemonName::pikachu(),
+            PokemonTypes::pikachu(),
+            PokemonStats::pikachu(),
+        )
+        .unwrap();
+        let replaced = Pokemon::new(
+            PokemonNumber::pikachu(),
+            PokemonName::vulpix(),
+            PokemonTypes::vulpix(),
+            PokemonStats::vulpix(),
+        );
+
+        repo.upsert_all(vec![replaced, Pokemon::vulpix()]).unwrap();
+
+        let pokemons = repo.fetch_all(&FetchAllQuery::default()).unwrap();
+        assert_eq!(pokemons.len(), 2);
+        assert_eq!(pokemons[0].name.as_str(), "Vulpix");
+        assert_eq!(pokemons[0].types.as_slice(), PokemonTypes::vulpix().as_slice());
+        assert_eq!(pokemons[0].stats, PokemonStats::vulpix());
+    }
+    #[test]
+    fn it_should_treat_a_huge_limit_and_offset_as_unbounded() {
+        let repo = repository();
+        repo.upsert_all(vec![Pokemon::pikachu(), Pokemon::vulpix()]).unwrap();
+        let query = |offset| FetchAllQuery {
+            limit: Some(usize::MAX),
+            offset,
+            ..FetchAllQuery::default()
+        };
+
+        assert_eq!(repo.fetch_all(&query(0)).unwrap().len(), 2);
+        assert!(repo.fetch_all(&query(usize::MAX)).unwrap().is_empty());
+    }
+}